@@ -0,0 +1,231 @@
+//! `AnimatedSpriteNode` struct - represents a node which plays the animations
+//! of a `SpriteSheet`.
+
+use anyhow::Error;
+
+use crate::{BlendMode, Color, Texture, component::{BlendModeComponent, DrawComponent, PositionComponent}, rect::Rect, sprite::SpriteNode, spritesheet::{Animation, SpriteSheet}, texture::Filter};
+
+/// A node which plays one of the named animations of a `SpriteSheet`.
+/// Call `update` once per game tick with the time passed since the last tick
+/// to advance the animation, and draw it like any other node.
+/// ## Implemented components:
+/// - `DrawComponent`
+/// - `PositionComponent`
+/// - `BlendModeComponent`
+/// # Example
+/// ```rust
+/// let mut player = AnimatedSpriteNode::new(&sheet, "idle", 40.0, 30.0).unwrap();
+/// while window.is_open() {
+///     if walking {
+///         player.play("walk").unwrap(); // does nothing if already walking
+///     }
+///     player.update(delta_time);
+///     texture.add(&player);
+///     window.draw(&texture).unwrap();
+/// }
+/// ```
+#[non_exhaustive]
+#[expect(clippy::module_name_repetitions, reason = "struct should be called 'AnimatedSpriteNode' as it is standard.")]
+#[expect(clippy::partial_pub_fields, reason = "the playback state is changed through methods so it always matches the sheet")]
+pub struct AnimatedSpriteNode<'sheet> {
+
+    /// The sprite sheet that the frames and animations are taken from.
+    sheet: &'sheet SpriteSheet,
+
+    /// The name of the animation being played.
+    animation_name: String,
+
+    /// The animation being played.
+    animation: &'sheet Animation,
+
+    /// How far through the animation playback is, in seconds.
+    time: f64,
+
+    /// The x-coordinate of the top-left corner of the sprite.
+    pub x: f64,
+
+    /// The y-coordinate of the top-left corner of the sprite.
+    pub y: f64,
+
+    /// How many times wider than its frame the sprite is drawn.
+    pub scale_x: f64,
+
+    /// How many times taller than its frame the sprite is drawn.
+    pub scale_y: f64,
+
+    /// How fast the animation plays: 1.0 is normal speed, 2.0 is twice as
+    /// fast and 0.0 is paused.
+    pub speed: f64,
+
+    /// Whether the sprite is mirrored horizontally (left to right).
+    pub flip_x: bool,
+
+    /// Whether the sprite is mirrored vertically (top to bottom).
+    pub flip_y: bool,
+
+    /// A color multiplied with every pixel of the sprite, including its alpha
+    /// channel. Defaults to `Color::WHITE`, which leaves the sprite unchanged.
+    pub tint: Color,
+
+    /// How the sheet's texture is sampled when the sprite is scaled or drawn
+    /// between pixels. Defaults to `Filter::Nearest`.
+    pub filter: Filter,
+
+    /// How the sprite's pixels are combined with the existing pixels when
+    /// drawn. Defaults to `BlendMode::SourceOver`.
+    pub blend_mode: BlendMode,
+}
+
+impl<'sheet> AnimatedSpriteNode<'sheet> {
+    /// Create a new `AnimatedSpriteNode` playing the named animation from its
+    /// first frame, with its top-left corner at (`x`, `y`).
+    /// # Parameters
+    /// - `sheet`: The sprite sheet to take frames and animations from.
+    /// - `animation`: The name of the animation to start playing.
+    /// - `x`: The x-coordinate of the top-left corner of the sprite.
+    /// - `y`: The y-coordinate of the top-left corner of the sprite.
+    /// # Returns
+    /// A new `AnimatedSpriteNode` drawn at its frames' original size.
+    /// # Errors
+    /// Errors if the sheet has no animation called `animation`.
+    pub fn new(sheet: &'sheet SpriteSheet, animation: &str, x: f64, y: f64) -> Result<AnimatedSpriteNode<'sheet>, Error> {
+        Ok(AnimatedSpriteNode {
+            sheet,
+            animation_name: animation.to_owned(),
+            animation: sheet.get_animation(animation)
+                .ok_or_else(|| Error::msg("Pixl: AnimatedSpriteNode::new: sheet has no animation with that name"))?,
+            time: 0.0,
+            x, y,
+            scale_x: 1.0,
+            scale_y: 1.0,
+            speed: 1.0,
+            flip_x: false,
+            flip_y: false,
+            tint: Color::WHITE,
+            filter: Filter::Nearest,
+            blend_mode: BlendMode::SourceOver,
+        })
+    }
+
+    /// Switches to the named animation and plays it from its first frame. If
+    /// the animation is already playing, this does nothing, so it is safe to
+    /// call every tick.
+    /// # Parameters
+    /// - `animation`: The name of the animation to play.
+    /// # Errors
+    /// Errors if the sheet has no animation called `animation`. The current
+    /// animation keeps playing.
+    pub fn play(&mut self, animation: &str) -> Result<(), Error> {
+        if self.animation_name == animation {
+            return Ok(());
+        }
+        self.animation = self.sheet.get_animation(animation)
+            .ok_or_else(|| Error::msg("Pixl: play: sheet has no animation with that name"))?;
+        animation.clone_into(&mut self.animation_name);
+        self.time = 0.0f64;
+        Ok(())
+    }
+
+    /// Plays the current animation again from its first frame.
+    pub const fn restart(&mut self) {
+        self.time = 0.0f64;
+    }
+
+    /// Advances the animation.
+    /// # Parameters
+    /// - `delta`: The time passed since the last update, in seconds.
+    pub fn update(&mut self, delta: f64) {
+        let duration = self.animation.get_duration();
+        if duration <= 0.0f64 {
+            return;
+        }
+        let time = delta.mul_add(self.speed, self.time);
+        self.time = if self.animation.looping {
+            time.rem_euclid(duration)
+        } else {
+            time.clamp(0.0f64, duration)
+        };
+    }
+
+    /// Gets the name of the animation being played.
+    /// # Returns
+    /// The animation's name.
+    #[must_use]
+    pub fn get_animation(&self) -> &str {
+        &self.animation_name
+    }
+
+    /// Checks whether a non-looping animation has reached its end. Looping
+    /// animations never finish.
+    /// # Returns
+    /// `true` if the animation has stopped on its last frame.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        !self.animation.looping && self.time >= self.animation.get_duration()
+    }
+
+    /// Gets the index of the sprite sheet frame currently being shown.
+    /// # Returns
+    /// The index of the frame in the sprite sheet.
+    #[must_use]
+    pub fn get_frame_index(&self) -> usize {
+        let mut end = 0.0f64;
+        for frame in &self.animation.frames {
+            end += frame.duration.max(0.0f64);
+            if self.time < end {
+                return frame.frame;
+            }
+        }
+        self.animation.frames.last().map_or(0, |frame| frame.frame)
+    }
+
+    /// Gets the region of the sprite sheet's texture currently being shown.
+    /// # Returns
+    /// The `Rect` of the current frame.
+    #[must_use]
+    pub fn get_frame(&self) -> Rect {
+        // the sheet checks that every animation frame exists when it is added
+        self.sheet.get_frame(self.get_frame_index()).unwrap_or(Rect::new(0, 0, 0, 0))
+    }
+}
+
+impl DrawComponent for AnimatedSpriteNode<'_> {
+    fn draw(&self, texture: &mut Texture) {
+        let mut sprite = SpriteNode::from_region(self.sheet.get_texture(), self.get_frame(), self.x, self.y);
+        sprite.set_scale(self.scale_x, self.scale_y);
+        sprite.flip_x = self.flip_x;
+        sprite.flip_y = self.flip_y;
+        sprite.tint = self.tint;
+        sprite.filter = self.filter;
+        sprite.blend_mode = self.blend_mode;
+        sprite.draw(texture);
+    }
+}
+
+impl PositionComponent for AnimatedSpriteNode<'_> {
+    fn get_x(&self) -> f64 {
+        self.x
+    }
+
+    fn get_y(&self) -> f64 {
+        self.y
+    }
+
+    fn set_x(&mut self, x: f64) {
+        self.x = x;
+    }
+
+    fn set_y(&mut self, y: f64) {
+        self.y = y;
+    }
+}
+
+impl BlendModeComponent for AnimatedSpriteNode<'_> {
+    fn get_blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+}
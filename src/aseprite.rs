@@ -0,0 +1,781 @@
+//! Loading of Aseprite (`.ase` / `.aseprite`) files, behind the `aseprite`
+//! feature.
+//!
+//! An `Aseprite` file keeps everything the artist authored: every frame with
+//! its duration, every layer, and the tags naming each animation. Frames can
+//! be flattened into a single `Texture`, or each layer can be taken on its
+//! own, and the whole file can be turned straight into a `SpriteSheet` with
+//! one animation per tag.
+//! ```rust
+//! let player = Aseprite::load("assets/player.aseprite").unwrap();
+//! let sheet = player.to_sprite_sheet().unwrap();
+//! let mut node = AnimatedSpriteNode::new(&sheet, "walk", 40.0, 30.0).unwrap();
+//! ```
+//! RGBA, grayscale and indexed files are supported. Tilemap layers are
+//! skipped.
+
+use std::{fs, path::Path};
+
+use anyhow::{Context as _, Error};
+use miniz_oxide::inflate::decompress_to_vec_zlib_with_limit;
+
+use crate::{BlendMode, Color, Texture, reader::ByteReader, rect::Rect, spritesheet::{Animation, AnimationFrame, SpriteSheet}, texture::Filter, transform::Transform};
+
+/// The magic number at the start of every Aseprite file.
+const FILE_MAGIC: u16 = 0xA5E0;
+
+/// The magic number at the start of every frame.
+const FRAME_MAGIC: u16 = 0xF1FA;
+
+/// The size of the file header, in bytes.
+const HEADER_SIZE: usize = 128;
+
+/// The size of a frame header, in bytes.
+const FRAME_HEADER_SIZE: usize = 16;
+
+/// The size of a chunk header, in bytes.
+const CHUNK_HEADER_SIZE: usize = 6;
+
+/// The chunk type of an old palette with 8-bit color values.
+const CHUNK_OLD_PALETTE: u16 = 0x0004;
+
+/// The chunk type of an old palette with 6-bit color values.
+const CHUNK_OLD_PALETTE_64: u16 = 0x0011;
+
+/// The chunk type of a layer.
+const CHUNK_LAYER: u16 = 0x2004;
+
+/// The chunk type of a cel, the image of one layer in one frame.
+const CHUNK_CEL: u16 = 0x2005;
+
+/// The chunk type of the animation tags.
+const CHUNK_TAGS: u16 = 0x2018;
+
+/// The chunk type of a palette.
+const CHUNK_PALETTE: u16 = 0x2019;
+
+/// The most frames an animation is expanded into. Anything after them is
+/// left out, so that a tag repeated many times cannot use up all memory.
+const MAX_ANIMATION_FRAMES: usize = 0x1_0000;
+
+/// A fully transparent color, used for empty canvas pixels.
+const TRANSPARENT: Color = Color::rgba(0, 0, 0, 0);
+
+/// An Aseprite file, holding its frames, layers and tags.
+/// # Example
+/// ```rust
+/// let explosion = Aseprite::load("assets/explosion.aseprite").unwrap();
+/// for frame in 0..explosion.get_frame_count() {
+///     let texture = explosion.flatten_frame(frame).unwrap();
+///     // ...
+/// }
+/// ```
+#[non_exhaustive]
+pub struct Aseprite {
+    /// The width of the canvas in pixels.
+    width: usize,
+    /// The height of the canvas in pixels.
+    height: usize,
+    /// Every layer, from the bottom up.
+    layers: Vec<AsepriteLayer>,
+    /// Whether each layer and every group containing it are visible.
+    layer_visible: Vec<bool>,
+    /// Every frame, in order.
+    frames: Vec<Frame>,
+    /// The pixels of every cel. Linked cels share an image.
+    images: Vec<Texture>,
+    /// The animation tags, in the order they appear in the file.
+    tags: Vec<AsepriteTag>,
+}
+
+/// A layer of an Aseprite file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+#[expect(clippy::module_name_repetitions, reason = "the names would be ambiguous without the 'Aseprite' prefix")]
+pub struct AsepriteLayer {
+    /// The name of the layer.
+    pub name: String,
+    /// Whether the layer itself is visible. A visible layer inside a hidden
+    /// group is still not drawn when flattening.
+    pub visible: bool,
+    /// Whether the layer is a group of other layers, rather than holding
+    /// pixels itself.
+    pub is_group: bool,
+    /// Whether the layer is the opaque background layer.
+    pub is_background: bool,
+    /// How deeply the layer is nested in groups: 0 for a top-level layer, 1
+    /// for a layer in a top-level group, and so on.
+    pub child_level: usize,
+    /// The opacity of the layer, from 0 (invisible) to 255 (opaque).
+    pub opacity: u8,
+    /// How the layer is combined with the layers below it. Aseprite blend
+    /// modes which Pixl does not support are loaded as
+    /// `BlendMode::SourceOver`.
+    pub blend_mode: BlendMode,
+}
+
+/// A tag of an Aseprite file, naming a range of frames as an animation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+#[expect(clippy::module_name_repetitions, reason = "the names would be ambiguous without the 'Aseprite' prefix")]
+pub struct AsepriteTag {
+    /// The name of the tag, such as `"walk"`.
+    pub name: String,
+    /// The index of the first frame of the tag.
+    pub from: usize,
+    /// The index of the last frame of the tag (inclusive).
+    pub to: usize,
+    /// The direction the frames are played in.
+    pub direction: AsepriteDirection,
+    /// How many times the animation plays, or 0 to play it forever.
+    pub repeat: usize,
+}
+
+/// The direction an Aseprite tag plays its frames in.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+#[expect(clippy::module_name_repetitions, reason = "the names would be ambiguous without the 'Aseprite' prefix")]
+pub enum AsepriteDirection {
+    /// From the first frame to the last.
+    Forward,
+    /// From the last frame to the first.
+    Reverse,
+    /// From the first frame to the last, then back again.
+    PingPong,
+    /// From the last frame to the first, then back again.
+    PingPongReverse,
+}
+
+/// A single frame of an Aseprite file.
+struct Frame {
+    /// How long the frame is shown for, in seconds.
+    duration: f64,
+    /// The cels of the frame, in the order they appear in the file.
+    cels: Vec<Cel>,
+}
+
+/// The image of one layer in one frame.
+struct Cel {
+    /// The index of the layer the cel belongs to.
+    layer: usize,
+    /// The x-coordinate of the top-left corner of the cel on the canvas.
+    x: i16,
+    /// The y-coordinate of the top-left corner of the cel on the canvas.
+    y: i16,
+    /// The opacity of the cel, from 0 (invisible) to 255 (opaque).
+    opacity: u8,
+    /// Moves the cel up or down the layer order in this frame only.
+    z_index: i16,
+    /// The index of the cel's pixels in `Aseprite::images`.
+    image: usize,
+}
+
+/// A cel as it is stored in the file, before its pixels are converted to
+/// colors (which needs the palette, which may come later in the file).
+struct RawCel {
+    /// The index of the layer the cel belongs to.
+    layer: usize,
+    /// The x-coordinate of the top-left corner of the cel on the canvas.
+    x: i16,
+    /// The y-coordinate of the top-left corner of the cel on the canvas.
+    y: i16,
+    /// The opacity of the cel, from 0 (invisible) to 255 (opaque).
+    opacity: u8,
+    /// Moves the cel up or down the layer order in this frame only.
+    z_index: i16,
+    /// The pixels of the cel.
+    content: CelContent,
+}
+
+/// The pixels of a `RawCel`.
+enum CelContent {
+    /// The cel has its own pixels, in the file's color depth.
+    Pixels {
+        /// The width of the cel in pixels.
+        width: usize,
+        /// The height of the cel in pixels.
+        height: usize,
+        /// The uncompressed pixel data.
+        data: Vec<u8>,
+    },
+    /// The cel reuses the pixels of the same layer's cel in another frame.
+    Linked(usize),
+}
+
+/// A frame as it is stored in the file.
+struct RawFrame {
+    /// How long the frame is shown for, in seconds.
+    duration: f64,
+    /// The cels of the frame.
+    cels: Vec<RawCel>,
+}
+
+impl Aseprite {
+    /// Loads an Aseprite (`.ase` or `.aseprite`) file.
+    /// # Arguments
+    /// * `path` - The path of the Aseprite file to load.
+    /// # Returns
+    /// The `Aseprite` file's frames, layers and tags.
+    /// # Errors
+    /// Errors if the file cannot be read or is not a valid Aseprite file.
+    /// # Example
+    /// ```rust
+    /// let player = Aseprite::load("assets/player.aseprite")
+    ///     .expect("failed to load player");
+    /// ```
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Aseprite, Error> {
+        Aseprite::decode(&fs::read(path).context("Pixl: Aseprite::load: failed to read file")?)
+    }
+
+    /// Decodes an in-memory Aseprite file.
+    /// # Arguments
+    /// * `bytes` - The contents of an Aseprite file.
+    /// # Returns
+    /// The `Aseprite` file's frames, layers and tags.
+    /// # Errors
+    /// Errors if `bytes` is not a valid Aseprite file.
+    /// # Example
+    /// ```rust
+    /// let coin = Aseprite::decode(include_bytes!("coin.aseprite"))
+    ///     .expect("invalid coin animation");
+    /// ```
+    pub fn decode(bytes: &[u8]) -> Result<Aseprite, Error> {
+        let mut reader = ByteReader::new(bytes, "Aseprite::decode");
+        reader.skip(4)?; // file size
+        if reader.u16_le()? != FILE_MAGIC {
+            return Err(Error::msg("Pixl: Aseprite::decode: not an Aseprite file"));
+        }
+        let frame_count = reader.u16_le()?;
+        let width = usize::from(reader.u16_le()?);
+        let height = usize::from(reader.u16_le()?);
+        let depth = reader.u16_le()?;
+        if !matches!(depth, 8 | 16 | 32) {
+            return Err(Error::msg("Pixl: Aseprite::decode: unsupported color depth"));
+        }
+        let header_flags = reader.u32_le()?;
+        reader.skip(10)?; // speed and reserved bytes
+        let transparent_index = reader.u8()?;
+        reader.seek(HEADER_SIZE)?;
+
+        let mut layers = Vec::new();
+        let mut tags = Vec::new();
+        let mut palette = vec![TRANSPARENT; 256];
+        let mut has_new_palette = false;
+        let mut raw_frames = Vec::with_capacity(usize::from(frame_count));
+        for _ in 0..frame_count {
+            let frame_start = reader.position();
+            let frame_size = cast::usize(reader.u32_le()?);
+            // every frame and chunk is at least as long as its own header, so
+            // each one read moves further through the file
+            if frame_size < FRAME_HEADER_SIZE {
+                return Err(Error::msg("Pixl: Aseprite::decode: invalid frame size"));
+            }
+            let frame_end = frame_start.checked_add(frame_size)
+                .ok_or_else(|| Error::msg("Pixl: Aseprite::decode: invalid frame size"))?;
+            if reader.u16_le()? != FRAME_MAGIC {
+                return Err(Error::msg("Pixl: Aseprite::decode: invalid frame header"));
+            }
+            let old_chunk_count = reader.u16_le()?;
+            let duration = f64::from(reader.u16_le()?) / 1000.0f64;
+            reader.skip(2)?;
+            let new_chunk_count = reader.u32_le()?;
+            let chunk_count = if new_chunk_count == 0 { u32::from(old_chunk_count) } else { new_chunk_count };
+
+            let mut cels = Vec::new();
+            for _ in 0..chunk_count {
+                let chunk_start = reader.position();
+                let chunk_size = cast::usize(reader.u32_le()?);
+                let chunk_end = chunk_start.checked_add(chunk_size)
+                    .filter(|&end| chunk_size >= CHUNK_HEADER_SIZE && end <= frame_end)
+                    .ok_or_else(|| Error::msg("Pixl: Aseprite::decode: invalid chunk size"))?;
+                match reader.u16_le()? {
+                    CHUNK_LAYER => layers.push(read_layer(&mut reader, header_flags)?),
+                    CHUNK_CEL => cels.extend(read_cel(&mut reader, chunk_end, depth)?),
+                    CHUNK_TAGS => tags = read_tags(&mut reader, usize::from(frame_count))?,
+                    CHUNK_PALETTE => {
+                        read_palette(&mut reader, &mut palette)?;
+                        has_new_palette = true;
+                    },
+                    // old palettes are only used by files without a new one
+                    CHUNK_OLD_PALETTE if !has_new_palette => read_old_palette(&mut reader, &mut palette, false)?,
+                    CHUNK_OLD_PALETTE_64 if !has_new_palette => read_old_palette(&mut reader, &mut palette, true)?,
+                    _ => {},
+                }
+                reader.seek(chunk_end)?;
+            }
+            raw_frames.push(RawFrame { duration, cels });
+            reader.seek(frame_end)?;
+        }
+
+        let mut aseprite = Aseprite {
+            width, height,
+            layer_visible: layer_visibility(&layers),
+            layers,
+            frames: Vec::with_capacity(raw_frames.len()),
+            images: Vec::new(),
+            tags,
+        };
+        aseprite.resolve_frames(raw_frames, depth, &palette, transparent_index)?;
+        Ok(aseprite)
+    }
+
+    /// Gets the width of the canvas in pixels.
+    /// # Returns
+    /// The width of every flattened frame.
+    #[must_use]
+    pub const fn get_width(&self) -> usize {
+        self.width
+    }
+
+    /// Gets the height of the canvas in pixels.
+    /// # Returns
+    /// The height of every flattened frame.
+    #[must_use]
+    pub const fn get_height(&self) -> usize {
+        self.height
+    }
+
+    /// Gets the number of frames in the file.
+    /// # Returns
+    /// The number of frames.
+    #[must_use]
+    pub const fn get_frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Gets how long a frame is shown for.
+    /// # Arguments
+    /// * `frame` - The index of the frame.
+    /// # Returns
+    /// The duration of the frame in seconds, or `None` if there is no frame
+    /// with that index.
+    #[must_use]
+    pub fn get_frame_duration(&self, frame: usize) -> Option<f64> {
+        Some(self.frames.get(frame)?.duration)
+    }
+
+    /// Gets every layer of the file, from the bottom up. Layers are referred
+    /// to by their index in this slice.
+    /// # Returns
+    /// A slice of the file's layers.
+    #[must_use]
+    pub fn get_layers(&self) -> &[AsepriteLayer] {
+        &self.layers
+    }
+
+    /// Gets every tag of the file.
+    /// # Returns
+    /// A slice of the file's tags.
+    #[must_use]
+    pub fn get_tags(&self) -> &[AsepriteTag] {
+        &self.tags
+    }
+
+    /// Gets the animation described by a tag, with each frame's duration.
+    /// Reverse and ping-pong tags are expanded into the order the frames are
+    /// played in. Tags which repeat forever become looping animations, and
+    /// tags which repeat a fixed number of times are repeated that many times
+    /// and do not loop, up to a limit of 65536 frames.
+    /// # Arguments
+    /// * `name` - The name of the tag.
+    /// # Returns
+    /// The tag's `Animation`, or `None` if there is no tag with that name.
+    /// # Example
+    /// ```rust
+    /// let walk = player.get_animation("walk").unwrap();
+    /// ```
+    #[must_use]
+    pub fn get_animation(&self, name: &str) -> Option<Animation> {
+        let tag = self.tags.iter().find(|candidate| candidate.name == name)?;
+        let forward: Vec<usize> = (tag.from..=tag.to).collect();
+        let reverse: Vec<usize> = forward.iter().rev().copied().collect();
+        let (first, second) = match tag.direction {
+            AsepriteDirection::Forward => (&forward, None),
+            AsepriteDirection::Reverse => (&reverse, None),
+            AsepriteDirection::PingPong => (&forward, Some(&reverse)),
+            AsepriteDirection::PingPongReverse => (&reverse, Some(&forward)),
+        };
+        let order: Vec<usize> = if tag.repeat == 0 {
+            // one full cycle; the turning frames are not shown twice in a row
+            let back = second.map_or(&[][..], |frames| frames.get(1..frames.len().saturating_sub(1)).unwrap_or(&[]));
+            first.iter().chain(back).copied().collect()
+        } else {
+            let mut order = first.clone();
+            for pass in 1..tag.repeat {
+                if order.len() >= MAX_ANIMATION_FRAMES {
+                    break;
+                }
+                let frames = match second {
+                    Some(frames) if pass % 2 == 1 => frames,
+                    _ => first,
+                };
+                // ping-pong passes start on the frame the last one ended on
+                let skip = usize::from(second.is_some());
+                order.extend(frames.iter().skip(skip));
+            }
+            order
+        };
+        let frames = order.into_iter().take(MAX_ANIMATION_FRAMES)
+            .map(|frame| AnimationFrame::new(frame, self.get_frame_duration(frame).unwrap_or(0.0f64)))
+            .collect();
+        Some(Animation::new(frames, tag.repeat == 0))
+    }
+
+    /// Flattens a frame into a single `Texture`, drawing every visible layer
+    /// with its opacity and blend mode, as Aseprite shows it.
+    /// # Arguments
+    /// * `frame` - The index of the frame.
+    /// # Returns
+    /// A `Texture` the size of the canvas. Areas not covered by any layer are
+    /// transparent.
+    /// # Errors
+    /// Errors if there is no frame with that index.
+    /// # Example
+    /// ```rust
+    /// let first_frame = player.flatten_frame(0).unwrap();
+    /// ```
+    pub fn flatten_frame(&self, frame: usize) -> Result<Texture, Error> {
+        let cels = &self.frames.get(frame)
+            .ok_or_else(|| Error::msg("Pixl: flatten_frame: frame was out of bounds"))?
+            .cels;
+        let mut visible: Vec<&Cel> = cels.iter()
+            .filter(|cel| self.layer_visible.get(cel.layer).copied().unwrap_or(false))
+            .collect();
+        // Aseprite orders cels by their layer moved by their z-index, and
+        // breaks ties using the z-index alone
+        visible.sort_by_key(|cel| {
+            let layer = i64::try_from(cel.layer).unwrap_or(i64::MAX);
+            (layer.saturating_add(i64::from(cel.z_index)), cel.z_index)
+        });
+
+        let mut texture = blank_texture(self.width, self.height)?;
+        for cel in visible {
+            let Some(layer) = self.layers.get(cel.layer) else {
+                continue;
+            };
+            let opacity = cast::u8(u16::from(layer.opacity).saturating_mul(u16::from(cel.opacity)) / 255).unwrap_or(u8::MAX);
+            self.draw_cel(&mut texture, cel, opacity, layer.blend_mode);
+        }
+        Ok(texture)
+    }
+
+    /// Gets the pixels of a single layer in a frame as a `Texture`, ignoring
+    /// every other layer and the layer's own visibility, opacity and blend
+    /// mode.
+    /// # Arguments
+    /// * `layer` - The index of the layer.
+    /// * `frame` - The index of the frame.
+    /// # Returns
+    /// A `Texture` the size of the canvas. It is fully transparent if the
+    /// layer is empty in that frame.
+    /// # Errors
+    /// Errors if there is no layer or frame with those indices.
+    /// # Example
+    /// ```rust
+    /// let shadow_layer = player.get_layers().iter().position(|layer| layer.name == "shadow").unwrap();
+    /// let shadow = player.get_layer_frame(shadow_layer, 0).unwrap();
+    /// ```
+    pub fn get_layer_frame(&self, layer: usize, frame: usize) -> Result<Texture, Error> {
+        if layer >= self.layers.len() {
+            return Err(Error::msg("Pixl: get_layer_frame: layer was out of bounds"));
+        }
+        let cels = &self.frames.get(frame)
+            .ok_or_else(|| Error::msg("Pixl: get_layer_frame: frame was out of bounds"))?
+            .cels;
+        let mut texture = blank_texture(self.width, self.height)?;
+        for cel in cels.iter().filter(|cel| cel.layer == layer) {
+            self.draw_cel(&mut texture, cel, cel.opacity, BlendMode::SourceOver);
+        }
+        Ok(texture)
+    }
+
+    /// Creates a `SpriteSheet` containing every flattened frame, side by side
+    /// in order, with one animation for every tag (see `get_animation`).
+    /// Frame `n` of the file is frame `n` of the sheet.
+    /// # Returns
+    /// A new `SpriteSheet`.
+    /// # Errors
+    /// Errors if the file has no frames, or a tag refers to a frame which
+    /// does not exist.
+    /// # Example
+    /// ```rust
+    /// let sheet = Aseprite::load("assets/player.aseprite").unwrap().to_sprite_sheet().unwrap();
+    /// let player = AnimatedSpriteNode::new(&sheet, "idle", 40.0, 30.0).unwrap();
+    /// ```
+    pub fn to_sprite_sheet(&self) -> Result<SpriteSheet, Error> {
+        self.build_sprite_sheet(|frame| self.flatten_frame(frame))
+    }
+
+    /// Creates a `SpriteSheet` containing a single layer of every frame, side
+    /// by side in order, with one animation for every tag. This is useful
+    /// when layers are drawn separately, such as a character and their
+    /// swappable outfit.
+    /// # Arguments
+    /// * `layer` - The index of the layer.
+    /// # Returns
+    /// A new `SpriteSheet`.
+    /// # Errors
+    /// Errors if there is no layer with that index, the file has no frames,
+    /// or a tag refers to a frame which does not exist.
+    pub fn layer_to_sprite_sheet(&self, layer: usize) -> Result<SpriteSheet, Error> {
+        self.build_sprite_sheet(|frame| self.get_layer_frame(layer, frame))
+    }
+
+    /// Draws every frame produced by `frame_texture` side by side onto a new
+    /// sprite sheet, and adds an animation for every tag.
+    fn build_sprite_sheet<F>(&self, frame_texture: F) -> Result<SpriteSheet, Error>
+    where F: Fn(usize) -> Result<Texture, Error> {
+        let sheet_width = self.width.checked_mul(self.frames.len())
+            .ok_or_else(|| Error::msg("Pixl: to_sprite_sheet: sprite sheet was too large"))?;
+        let mut texture = blank_texture(sheet_width, self.height)?;
+        let region = Rect::new(0, 0, self.width, self.height);
+        for frame in 0..self.frames.len() {
+            let offset = cast::f64(frame.saturating_mul(self.width));
+            let transform = Transform::translation(offset, 0.0);
+            texture.draw_texture(&frame_texture(frame)?, region, transform, Filter::Nearest, Color::WHITE, BlendMode::Source);
+        }
+        let mut sheet = SpriteSheet::from_grid(texture, self.width, self.height)?;
+        for tag in &self.tags {
+            if let Some(animation) = self.get_animation(&tag.name) {
+                sheet.add_animation(&tag.name, animation)?;
+            }
+        }
+        Ok(sheet)
+    }
+
+    /// Draws a cel onto a canvas-sized texture.
+    fn draw_cel(&self, texture: &mut Texture, cel: &Cel, opacity: u8, blend_mode: BlendMode) {
+        let Some(image) = self.images.get(cel.image) else {
+            return;
+        };
+        let region = Rect::new(0, 0, image.get_width(), image.get_height());
+        let transform = Transform::translation(f64::from(cel.x), f64::from(cel.y));
+        texture.draw_texture(image, region, transform, Filter::Nearest, Color::rgba(255, 255, 255, opacity), blend_mode);
+    }
+
+    /// Converts the pixels of every cel into textures, now that the palette
+    /// is known, and points linked cels at the images they share.
+    fn resolve_frames(&mut self, raw_frames: Vec<RawFrame>, depth: u16, palette: &[Color], transparent_index: u8) -> Result<(), Error> {
+        for raw_frame in raw_frames {
+            let mut cels = Vec::with_capacity(raw_frame.cels.len());
+            for raw_cel in raw_frame.cels {
+                let image = match raw_cel.content {
+                    CelContent::Pixels { width, height, data } => {
+                        // the background layer is opaque, even in indexed files
+                        let is_background = self.layers.get(raw_cel.layer).is_some_and(|layer| layer.is_background);
+                        let transparent = (!is_background).then_some(transparent_index);
+                        let pixels = decode_pixels(&data, depth, palette, transparent);
+                        self.images.push(Texture::from_pixels(width, height, pixels)?);
+                        self.images.len().saturating_sub(1)
+                    },
+                    CelContent::Linked(frame) => {
+                        let Some(linked) = self.frames.get(frame)
+                            .and_then(|linked_frame| linked_frame.cels.iter().find(|cel| cel.layer == raw_cel.layer)) else {
+                            continue;
+                        };
+                        linked.image
+                    },
+                };
+                cels.push(Cel {
+                    layer: raw_cel.layer,
+                    x: raw_cel.x,
+                    y: raw_cel.y,
+                    opacity: raw_cel.opacity,
+                    z_index: raw_cel.z_index,
+                    image,
+                });
+            }
+            self.frames.push(Frame { duration: raw_frame.duration, cels });
+        }
+        Ok(())
+    }
+}
+
+/// Creates a fully transparent texture.
+fn blank_texture(width: usize, height: usize) -> Result<Texture, Error> {
+    let size = width.checked_mul(height)
+        .ok_or_else(|| Error::msg("Pixl: Aseprite: texture was too large"))?;
+    Texture::from_pixels(width, height, vec![TRANSPARENT; size])
+}
+
+/// Reads a string, stored as its length in bytes followed by UTF-8 text.
+fn read_string(reader: &mut ByteReader) -> Result<String, Error> {
+    let length = usize::from(reader.u16_le()?);
+    Ok(String::from_utf8_lossy(reader.bytes(length)?).into_owned())
+}
+
+/// Reads a layer chunk.
+#[expect(clippy::single_call_fn, reason = "kept separate from decode for readability")]
+fn read_layer(reader: &mut ByteReader, header_flags: u32) -> Result<AsepriteLayer, Error> {
+    let flags = reader.u16_le()?;
+    let layer_type = reader.u16_le()?;
+    let child_level = usize::from(reader.u16_le()?);
+    reader.skip(4)?; // default width and height, which are unused
+    let blend_mode = match reader.u16_le()? {
+        1 => BlendMode::Multiply,
+        2 => BlendMode::Screen,
+        3 => BlendMode::Overlay,
+        4 => BlendMode::Darken,
+        5 => BlendMode::Lighten,
+        16 => BlendMode::Additive,
+        _ => BlendMode::SourceOver,
+    };
+    let opacity = reader.u8()?;
+    reader.skip(3)?;
+    Ok(AsepriteLayer {
+        name: read_string(reader)?,
+        visible: flags & 1 != 0,
+        is_group: layer_type == 1,
+        is_background: flags & 8 != 0,
+        child_level,
+        // layer opacity is only stored if the header says it is valid
+        opacity: if header_flags & 1 == 0 { u8::MAX } else { opacity },
+        blend_mode,
+    })
+}
+
+/// Reads a cel chunk. Returns `None` for tilemap cels, which are not
+/// supported.
+#[expect(clippy::single_call_fn, reason = "kept separate from decode for readability")]
+fn read_cel(reader: &mut ByteReader, chunk_end: usize, depth: u16) -> Result<Option<RawCel>, Error> {
+    let layer = usize::from(reader.u16_le()?);
+    let x = reader.i16_le()?;
+    let y = reader.i16_le()?;
+    let opacity = reader.u8()?;
+    let cel_type = reader.u16_le()?;
+    let z_index = reader.i16_le()?;
+    reader.skip(5)?;
+    let content = match cel_type {
+        0 | 2 => {
+            let width = usize::from(reader.u16_le()?);
+            let height = usize::from(reader.u16_le()?);
+            let size = width.checked_mul(height)
+                .and_then(|area| area.checked_mul(usize::from(depth / 8)))
+                .ok_or_else(|| Error::msg("Pixl: Aseprite::decode: cel was too large"))?;
+            let data = if cel_type == 0 {
+                reader.bytes(size)?.to_vec()
+            } else {
+                let compressed = reader.bytes(chunk_end.saturating_sub(reader.position()))?;
+                decompress_to_vec_zlib_with_limit(compressed, size)
+                    .map_err(|error| Error::msg(format!("Pixl: Aseprite::decode: invalid compressed cel: {error}")))?
+            };
+            if data.len() != size {
+                return Err(Error::msg("Pixl: Aseprite::decode: cel had the wrong number of pixels"));
+            }
+            CelContent::Pixels { width, height, data }
+        },
+        1 => CelContent::Linked(usize::from(reader.u16_le()?)),
+        _ => return Ok(None),
+    };
+    Ok(Some(RawCel { layer, x, y, opacity, z_index, content }))
+}
+
+/// Reads a tags chunk, checking that every tag's frames are in the file.
+#[expect(clippy::single_call_fn, reason = "kept separate from decode for readability")]
+fn read_tags(reader: &mut ByteReader, frame_count: usize) -> Result<Vec<AsepriteTag>, Error> {
+    let count = reader.u16_le()?;
+    reader.skip(8)?;
+    let mut tags = Vec::with_capacity(usize::from(count));
+    for _ in 0..count {
+        let from = usize::from(reader.u16_le()?);
+        let to = usize::from(reader.u16_le()?);
+        if from > to || to >= frame_count {
+            return Err(Error::msg("Pixl: Aseprite::decode: tag had an invalid frame range"));
+        }
+        let direction = match reader.u8()? {
+            1 => AsepriteDirection::Reverse,
+            2 => AsepriteDirection::PingPong,
+            3 => AsepriteDirection::PingPongReverse,
+            _ => AsepriteDirection::Forward,
+        };
+        let repeat = usize::from(reader.u16_le()?);
+        reader.skip(10)?; // reserved bytes and the deprecated tag color
+        tags.push(AsepriteTag { name: read_string(reader)?, from, to, direction, repeat });
+    }
+    Ok(tags)
+}
+
+/// Reads a palette chunk into `palette`. Only the first 256 entries can be
+/// used by indexed pixels, so any after them are skipped.
+#[expect(clippy::single_call_fn, reason = "kept separate from decode for readability")]
+fn read_palette(reader: &mut ByteReader, palette: &mut [Color]) -> Result<(), Error> {
+    let size = cast::usize(reader.u32_le()?);
+    let first = cast::usize(reader.u32_le()?);
+    let last = cast::usize(reader.u32_le()?);
+    reader.skip(8)?;
+    if last < first || last >= size {
+        return Err(Error::msg("Pixl: Aseprite::decode: invalid palette"));
+    }
+    for entry in palette.iter_mut().take(last.saturating_add(1)).skip(first) {
+        let flags = reader.u16_le()?;
+        let [r, g, b, a] = reader.array()?;
+        *entry = Color::rgba(r, g, b, a);
+        if flags & 1 != 0 {
+            read_string(reader)?; // the color's name
+        }
+    }
+    Ok(())
+}
+
+/// Reads an old palette chunk into `palette`. If `six_bit` is set, color
+/// values range from 0 to 63 rather than 0 to 255.
+fn read_old_palette(reader: &mut ByteReader, palette: &mut [Color], six_bit: bool) -> Result<(), Error> {
+    let packets = reader.u16_le()?;
+    let mut index = 0usize;
+    for _ in 0..packets {
+        index = index.saturating_add(usize::from(reader.u8()?));
+        let count = match reader.u8()? {
+            0 => 256,
+            count => usize::from(count),
+        };
+        for _ in 0..count {
+            let [r, g, b] = reader.array()?;
+            let scale = |value: u8| if six_bit {
+                cast::u8(u16::from(value.min(63)).saturating_mul(255) / 63).unwrap_or(u8::MAX)
+            } else {
+                value
+            };
+            if let Some(entry) = palette.get_mut(index) {
+                *entry = Color::rgb(scale(r), scale(g), scale(b));
+            }
+            index = index.saturating_add(1);
+        }
+    }
+    Ok(())
+}
+
+/// Works out whether each layer is visible, taking into account the groups
+/// containing it.
+#[expect(clippy::single_call_fn, reason = "kept separate from decode for readability")]
+fn layer_visibility(layers: &[AsepriteLayer]) -> Vec<bool> {
+    // the visibility of the group at each nesting level above the current
+    // layer
+    let mut groups: Vec<bool> = Vec::new();
+    layers.iter().map(|layer| {
+        groups.truncate(layer.child_level);
+        let visible = layer.visible && groups.iter().all(|&group| group);
+        groups.push(visible);
+        visible && !layer.is_group
+    }).collect()
+}
+
+/// Converts uncompressed cel pixels into colors. `transparent` is the palette
+/// index which is fully transparent in indexed files, if there is one.
+#[expect(clippy::single_call_fn, reason = "kept separate from resolve_frames for readability")]
+fn decode_pixels(data: &[u8], depth: u16, palette: &[Color], transparent: Option<u8>) -> Vec<Color> {
+    match depth {
+        32 => data.chunks_exact(4).map(|pixel| match *pixel {
+            [r, g, b, a] => Color::rgba(r, g, b, a),
+            _ => TRANSPARENT,
+        }).collect(),
+        16 => data.chunks_exact(2).map(|pixel| match *pixel {
+            [value, a] => Color::rgba(value, value, value, a),
+            _ => TRANSPARENT,
+        }).collect(),
+        _ => data.iter().map(|&index| if Some(index) == transparent {
+            TRANSPARENT
+        } else {
+            palette.get(usize::from(index)).copied().unwrap_or(TRANSPARENT)
+        }).collect(),
+    }
+}
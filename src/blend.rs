@@ -0,0 +1,221 @@
+//! Module defining the `BlendMode` enum, which decides how a color being drawn
+//! is combined with the color already present on a `Texture`.
+//!
+//! Blend modes come in two families:
+//! - The Porter-Duff compositing operators (`SourceOver`, `SourceIn`,
+//!   `DestinationOut`, `Xor`, ...), which decide *which* parts of the source
+//!   and destination are kept, based on their alpha channels.
+//! - The separable blend modes (`Multiply`, `Screen`, `Overlay`, ...), which
+//!   mix the color channels together and are then composited "source-over".
+
+use crate::{Color, color::channel_from_f64};
+
+/// The way a color being drawn (the *source*) is combined with the color
+/// already on the texture (the *destination*).
+///
+/// `SourceOver` is the default, and is what you would normally expect from
+/// alpha blending: translucent colors let the destination show through.
+/// Note that the built-in nodes only ever touch the pixels they cover, so
+/// modes such as `Clear` or `SourceIn` only affect the shape's own area.
+/// # Example
+/// ```rust
+/// let mut rectangle = RectangleNode::new(10.0, 10.0, 20.0, 20.0, Color::rgb(80, 80, 80));
+/// rectangle.blend_mode = BlendMode::Additive;
+/// texture.add(&rectangle);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+#[expect(clippy::module_name_repetitions, reason = "enum should be called 'BlendMode' as it is standard.")]
+pub enum BlendMode {
+    /// Porter-Duff "clear": both the source and destination are removed,
+    /// leaving a fully transparent pixel.
+    Clear,
+    /// Porter-Duff "copy": the source replaces the destination entirely,
+    /// including its alpha channel. This is what `Texture::set_pixel` does.
+    Source,
+    /// Porter-Duff "destination": the destination is left untouched.
+    Destination,
+    /// Porter-Duff "source-over": the source is drawn on top of the
+    /// destination. This is standard alpha blending.
+    #[default]
+    SourceOver,
+    /// Porter-Duff "destination-over": the source is drawn *behind* the
+    /// destination.
+    DestinationOver,
+    /// Porter-Duff "source-in": the source is kept only where the destination
+    /// is opaque.
+    SourceIn,
+    /// Porter-Duff "destination-in": the destination is kept only where the
+    /// source is opaque.
+    DestinationIn,
+    /// Porter-Duff "source-out": the source is kept only where the
+    /// destination is transparent.
+    SourceOut,
+    /// Porter-Duff "destination-out": the destination is erased where the
+    /// source is opaque. Useful for cutting holes.
+    DestinationOut,
+    /// Porter-Duff "source-atop": the source is drawn only on top of the
+    /// opaque parts of the destination.
+    SourceAtop,
+    /// Porter-Duff "destination-atop": the destination is kept only where the
+    /// source is opaque, and is drawn on top of it.
+    DestinationAtop,
+    /// Porter-Duff "xor": the source and destination are kept only where they
+    /// do not overlap.
+    Xor,
+    /// Additive ("plus") blending: the source is added to the destination,
+    /// saturating at white. Good for glows, fire and lights.
+    Additive,
+    /// The color channels are multiplied, which always darkens. Good for
+    /// shadows.
+    Multiply,
+    /// The inverse color channels are multiplied, which always lightens.
+    Screen,
+    /// `Multiply` on dark destination colors and `Screen` on light ones,
+    /// increasing contrast.
+    Overlay,
+    /// The darker of each source and destination channel is kept.
+    Darken,
+    /// The lighter of each source and destination channel is kept.
+    Lighten,
+}
+
+impl BlendMode {
+    /// Combines a `source` color with a `destination` color using this blend
+    /// mode.
+    /// # Arguments
+    /// * `source` - The color being drawn.
+    /// * `destination` - The color already present on the texture.
+    /// # Returns
+    /// The resulting `Color`, including the resulting alpha.
+    /// # Example
+    /// ```rust
+    /// let shadow = BlendMode::Multiply.blend(Color::rgb(128, 128, 128), Color::WHITE);
+    /// ```
+    #[must_use]
+    pub fn blend(self, source: Color, destination: Color) -> Color {
+        let src_alpha = f64::from(source.a) / 255.0f64;
+        let dst_alpha = f64::from(destination.a) / 255.0f64;
+        match self {
+            BlendMode::Clear => Color::rgba(0, 0, 0, 0),
+            BlendMode::Source => source,
+            BlendMode::Destination => destination,
+            BlendMode::SourceOver => source.blend_over(destination),
+            BlendMode::DestinationOver => destination.blend_over(source),
+            BlendMode::SourceIn => porter_duff(source, destination, dst_alpha, 0.0f64),
+            BlendMode::DestinationIn => porter_duff(source, destination, 0.0f64, src_alpha),
+            BlendMode::SourceOut => porter_duff(source, destination, 1.0f64 - dst_alpha, 0.0f64),
+            BlendMode::DestinationOut => porter_duff(source, destination, 0.0f64, 1.0f64 - src_alpha),
+            BlendMode::SourceAtop => porter_duff(source, destination, dst_alpha, 1.0f64 - src_alpha),
+            BlendMode::DestinationAtop => porter_duff(source, destination, 1.0f64 - dst_alpha, src_alpha),
+            BlendMode::Xor => porter_duff(source, destination, 1.0f64 - dst_alpha, 1.0f64 - src_alpha),
+            BlendMode::Additive => additive(source, destination),
+            BlendMode::Multiply => separable(source, destination, |src, dst| src * dst),
+            BlendMode::Screen => separable(source, destination, screen),
+            BlendMode::Overlay => separable(source, destination, |src, dst| {
+                if dst <= 0.5f64 {
+                    2.0f64 * src * dst
+                }
+                else {
+                    screen(src, 2.0f64.mul_add(dst, -1.0f64))
+                }
+            }),
+            BlendMode::Darken => separable(source, destination, f64::min),
+            BlendMode::Lighten => separable(source, destination, f64::max),
+        }
+    }
+}
+
+/// A color with its channels normalized to the 0.0-1.0 range and its color
+/// channels premultiplied by its alpha channel, which is the form all
+/// compositing maths is done in.
+#[derive(Copy, Clone)]
+struct Premultiplied {
+    /// The red channel, multiplied by `a`.
+    r: f64,
+    /// The green channel, multiplied by `a`.
+    g: f64,
+    /// The blue channel, multiplied by `a`.
+    b: f64,
+    /// The alpha channel.
+    a: f64,
+}
+
+impl Premultiplied {
+    /// Normalizes and premultiplies a `Color`.
+    fn from_color(color: Color) -> Premultiplied {
+        let a = f64::from(color.a) / 255.0f64;
+        Premultiplied {
+            r: f64::from(color.r) / 255.0f64 * a,
+            g: f64::from(color.g) / 255.0f64 * a,
+            b: f64::from(color.b) / 255.0f64 * a,
+            a,
+        }
+    }
+
+    /// Converts back into a straight-alpha `Color`, clamping every channel.
+    fn to_color(self) -> Color {
+        if self.a <= 0.0f64 {
+            return Color::rgba(0, 0, 0, 0);
+        }
+        Color::rgba(
+            channel_from_f64(self.r / self.a * 255.0f64),
+            channel_from_f64(self.g / self.a * 255.0f64),
+            channel_from_f64(self.b / self.a * 255.0f64),
+            channel_from_f64(self.a * 255.0f64),
+        )
+    }
+}
+
+/// Applies a Porter-Duff operator, where `src_factor` and `dst_factor` are the
+/// fractions of the source and destination that are kept.
+fn porter_duff(source: Color, destination: Color, src_factor: f64, dst_factor: f64) -> Color {
+    let src = Premultiplied::from_color(source);
+    let dst = Premultiplied::from_color(destination);
+    Premultiplied {
+        r: src.r.mul_add(src_factor, dst.r * dst_factor),
+        g: src.g.mul_add(src_factor, dst.g * dst_factor),
+        b: src.b.mul_add(src_factor, dst.b * dst_factor),
+        a: src.a.mul_add(src_factor, dst.a * dst_factor),
+    }.to_color()
+}
+
+/// Adds the premultiplied source to the premultiplied destination, saturating
+/// every channel.
+#[expect(clippy::single_call_fn, reason = "kept separate from BlendMode::blend for readability")]
+fn additive(source: Color, destination: Color) -> Color {
+    let src = Premultiplied::from_color(source);
+    let dst = Premultiplied::from_color(destination);
+    let alpha = (src.a + dst.a).min(1.0f64);
+    Premultiplied {
+        r: (src.r + dst.r).min(alpha),
+        g: (src.g + dst.g).min(alpha),
+        b: (src.b + dst.b).min(alpha),
+        a: alpha,
+    }.to_color()
+}
+
+/// Applies a separable blend function to each color channel, then composites
+/// the result source-over, as described by the W3C compositing specification.
+/// The blend function takes the straight (not premultiplied) source and
+/// destination channels, in that order, in the 0.0-1.0 range.
+fn separable(source: Color, destination: Color, function: impl Fn(f64, f64) -> f64) -> Color {
+    let src = Premultiplied::from_color(source);
+    let dst = Premultiplied::from_color(destination);
+    let both = src.a * dst.a;
+    let mix = |src_channel: u8, dst_channel: u8, src_premul: f64, dst_premul: f64| {
+        let blended = function(f64::from(src_channel) / 255.0f64, f64::from(dst_channel) / 255.0f64);
+        both.mul_add(blended, src_premul.mul_add(1.0f64 - dst.a, dst_premul * (1.0f64 - src.a)))
+    };
+    Premultiplied {
+        r: mix(source.r, destination.r, src.r, dst.r),
+        g: mix(source.g, destination.g, src.g, dst.g),
+        b: mix(source.b, destination.b, src.b, dst.b),
+        a: src.a.mul_add(1.0f64 - dst.a, dst.a),
+    }.to_color()
+}
+
+/// The "screen" blend function, used by both `Screen` and `Overlay`.
+fn screen(src: f64, dst: f64) -> f64 {
+    src.mul_add(-dst, src + dst)
+}
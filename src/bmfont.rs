@@ -0,0 +1,333 @@
+//! Loading of `BMFont` fonts, the bitmap font format created by `AngelCode`
+//! and made by tools such as `BMFont`, Hiero and Littera.
+//!
+//! A `BMFont` is a descriptor file (`.fnt`), in either the text or binary
+//! format, along with one or more page images containing the characters.
+//! ```rust
+//! let font = BmFont::load("assets/fonts/arcade.fnt").unwrap();
+//! let title = TextNode::with_font(&font, "Press start", 10.0, 10.0, Color::WHITE);
+//! texture.add(&title);
+//! ```
+//! Pages are loaded based on their file extension, so any image format Pixl
+//! can load is supported (PNG pages need the `png` feature). Pages drawn in
+//! white are tinted to the color of the text.
+
+use std::{collections::HashMap, fs, path::Path};
+
+use anyhow::{Context as _, Error};
+
+use crate::{BlendMode, Color, Texture, font::Font, reader::ByteReader, rect::Rect, texture::Filter, transform::Transform};
+
+/// The magic bytes at the start of a binary `BMFont` descriptor.
+const BINARY_MAGIC: &[u8] = b"BMF";
+
+/// A font loaded from a `BMFont` descriptor and its page textures.
+/// Characters missing from the font are drawn as `?`, or skipped if the font
+/// has no `?` either.
+#[non_exhaustive]
+pub struct BmFont {
+    /// The distance between the tops of two lines of text, in pixels.
+    line_height: f64,
+    /// The distance from the top of a line to the baseline, in pixels.
+    base: f64,
+    /// The textures containing the characters.
+    pages: Vec<Texture>,
+    /// Every character in the font.
+    glyphs: HashMap<char, Glyph>,
+    /// Adjustments to the advance between specific pairs of characters.
+    kernings: HashMap<(char, char), f64>,
+}
+
+/// A single character of a `BmFont`.
+struct Glyph {
+    /// The index of the page containing the character.
+    page: usize,
+    /// The region of the page containing the character.
+    region: Rect,
+    /// How far right of the pen position the character is drawn.
+    offset_x: f64,
+    /// How far below the top of the line the character is drawn.
+    offset_y: f64,
+    /// How far to move the pen after drawing the character.
+    advance: f64,
+}
+
+impl BmFont {
+    /// Loads a `BMFont` descriptor file (text or binary) and its page images,
+    /// which are looked for relative to the descriptor.
+    /// # Arguments
+    /// * `path` - The path of the `.fnt` file to load.
+    /// # Returns
+    /// A `BmFont` which can be used to draw text.
+    /// # Errors
+    /// Errors if the descriptor or any page cannot be read, or either is
+    /// invalid.
+    /// # Example
+    /// ```rust
+    /// let font = BmFont::load("assets/fonts/arcade.fnt")
+    ///     .expect("failed to load font");
+    /// ```
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<BmFont, Error> {
+        let descriptor = fs::read(path.as_ref()).context("Pixl: BmFont::load: failed to read file")?;
+        let directory = path.as_ref().parent().unwrap_or_else(|| Path::new(""));
+        BmFont::decode(&descriptor, |file| load_page(&directory.join(file)))
+    }
+
+    /// Decodes an in-memory `BMFont` descriptor (text or binary), using
+    /// `load_page` to get the texture of each page from its file name.
+    /// # Arguments
+    /// * `bytes` - The contents of a `.fnt` file.
+    /// * `load_page` - Called with the file name of each page, in order, and
+    ///   returns its texture.
+    /// # Returns
+    /// A `BmFont` which can be used to draw text.
+    /// # Errors
+    /// Errors if the descriptor is invalid, or `load_page` returns an error.
+    /// # Example
+    /// ```rust
+    /// let font = BmFont::decode(include_bytes!("small.fnt"), |_| {
+    ///     Texture::decode_qoi(include_bytes!("small_0.qoi"))
+    /// }).expect("invalid font");
+    /// ```
+    pub fn decode<F>(bytes: &[u8], load_page: F) -> Result<BmFont, Error>
+    where F: FnMut(&str) -> Result<Texture, Error> {
+        if bytes.starts_with(BINARY_MAGIC) {
+            decode_binary(bytes, load_page)
+        } else {
+            decode_text(&String::from_utf8_lossy(bytes), load_page)
+        }
+    }
+
+    /// Gets the distance from the top of a line of text to its baseline, the
+    /// line most characters sit on.
+    /// # Returns
+    /// The baseline position in pixels.
+    #[must_use]
+    pub const fn get_base(&self) -> f64 {
+        self.base
+    }
+
+    /// Gets the character drawn for `character`: itself, or `?` if the font
+    /// does not contain it.
+    fn glyph(&self, character: char) -> Option<&Glyph> {
+        self.glyphs.get(&character).or_else(|| self.glyphs.get(&'?'))
+    }
+}
+
+impl Font for BmFont {
+    fn get_line_height(&self) -> f64 {
+        self.line_height
+    }
+
+    fn get_advance(&self, character: char) -> f64 {
+        self.glyph(character).map_or(0.0, |glyph| glyph.advance)
+    }
+
+    fn get_kerning(&self, left: char, right: char) -> f64 {
+        self.kernings.get(&(left, right)).copied().unwrap_or(0.0)
+    }
+
+    fn draw_char(&self, texture: &mut Texture, character: char, x: f64, y: f64, color: Color, blend_mode: BlendMode) {
+        let Some(glyph) = self.glyph(character) else {
+            return;
+        };
+        let Some(page) = self.pages.get(glyph.page) else {
+            return;
+        };
+        // pixel fonts are snapped to whole pixels to keep them crisp
+        let transform = Transform::translation((x + glyph.offset_x).round(), (y + glyph.offset_y).round());
+        texture.draw_texture(page, glyph.region, transform, Filter::Nearest, color, blend_mode);
+    }
+}
+
+/// Loads a page image, choosing the format from its file extension.
+#[expect(clippy::single_call_fn, reason = "kept separate from BmFont::load for readability")]
+fn load_page(path: &Path) -> Result<Texture, Error> {
+    let extension = path.extension()
+        .map(|extension| extension.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        #[cfg(feature = "png")]
+        "png" => Texture::load_png(path),
+        "bmp" => Texture::load_bmp(path),
+        "tga" => Texture::load_tga(path),
+        "qoi" => Texture::load_qoi(path),
+        "pbm" | "pgm" | "ppm" | "pnm" | "pam" => Texture::load_netpbm(path),
+        _ => Err(Error::msg(format!("Pixl: BmFont::load: unsupported page image format '{extension}'"))),
+    }
+}
+
+/// Decodes a text `BMFont` descriptor.
+#[expect(clippy::single_call_fn, reason = "kept separate from BmFont::decode for readability")]
+fn decode_text<F>(text: &str, mut load_page: F) -> Result<BmFont, Error>
+where F: FnMut(&str) -> Result<Texture, Error> {
+    let mut font = BmFont {
+        line_height: 0.0,
+        base: 0.0,
+        pages: Vec::new(),
+        glyphs: HashMap::new(),
+        kernings: HashMap::new(),
+    };
+    let mut page_files = Vec::new();
+    for line in text.lines() {
+        let mut tokens = tokens(line).into_iter();
+        let Some((tag, _)) = tokens.next() else {
+            continue;
+        };
+        let fields: Vec<(&str, &str)> = tokens.collect();
+        match tag {
+            "common" => {
+                font.line_height = number(&fields, "lineHeight")?;
+                font.base = number(&fields, "base")?;
+            },
+            "page" => {
+                let id = cast::usize(number(&fields, "id")?)
+                    .map_err(|error| Error::msg(format!("Pixl: BmFont::decode: invalid page id: {error}")))?;
+                let file = field(&fields, "file")
+                    .ok_or_else(|| Error::msg("Pixl: BmFont::decode: page was missing its file"))?;
+                page_files.push((id, file));
+            },
+            "char" => {
+                let Some(character) = character_field(&fields, "id")? else {
+                    continue;
+                };
+                font.glyphs.insert(character, Glyph {
+                    page: cast::usize(number(&fields, "page")?).unwrap_or(usize::MAX),
+                    region: Rect::new(
+                        cast::usize(number(&fields, "x")?).unwrap_or(0),
+                        cast::usize(number(&fields, "y")?).unwrap_or(0),
+                        cast::usize(number(&fields, "width")?).unwrap_or(0),
+                        cast::usize(number(&fields, "height")?).unwrap_or(0),
+                    ),
+                    offset_x: number(&fields, "xoffset")?,
+                    offset_y: number(&fields, "yoffset")?,
+                    advance: number(&fields, "xadvance")?,
+                });
+            },
+            "kerning" => {
+                if let (Some(first), Some(second)) = (character_field(&fields, "first")?, character_field(&fields, "second")?) {
+                    font.kernings.insert((first, second), number(&fields, "amount")?);
+                }
+            },
+            _ => {},
+        }
+    }
+    page_files.sort_by_key(|&(id, _)| id);
+    for (index, (id, file)) in page_files.into_iter().enumerate() {
+        if id != index {
+            return Err(Error::msg("Pixl: BmFont::decode: page ids were not numbered from 0"));
+        }
+        font.pages.push(load_page(file)?);
+    }
+    Ok(font)
+}
+
+/// Decodes a binary (version 3) `BMFont` descriptor.
+#[expect(clippy::single_call_fn, reason = "kept separate from BmFont::decode for readability")]
+fn decode_binary<F>(bytes: &[u8], mut load_page: F) -> Result<BmFont, Error>
+where F: FnMut(&str) -> Result<Texture, Error> {
+    let mut reader = ByteReader::new(bytes, "BmFont::decode");
+    reader.skip(BINARY_MAGIC.len())?;
+    if reader.u8()? != 3 {
+        return Err(Error::msg("Pixl: BmFont::decode: unsupported binary format version"));
+    }
+    let mut font = BmFont {
+        line_height: 0.0,
+        base: 0.0,
+        pages: Vec::new(),
+        glyphs: HashMap::new(),
+        kernings: HashMap::new(),
+    };
+    while reader.position() < bytes.len() {
+        let block_type = reader.u8()?;
+        let block_size = cast::usize(reader.u32_le()?);
+        let block = reader.bytes(block_size)?;
+        let mut block_reader = ByteReader::new(block, "BmFont::decode");
+        match block_type {
+            2 => {
+                font.line_height = f64::from(block_reader.u16_le()?);
+                font.base = f64::from(block_reader.u16_le()?);
+            },
+            3 => {
+                // page names are null-terminated, and all the same length
+                for file in block.split(|&byte| byte == 0).filter(|file| !file.is_empty()) {
+                    font.pages.push(load_page(&String::from_utf8_lossy(file))?);
+                }
+            },
+            4 => {
+                for _ in 0..block.len() / 20 {
+                    let id = block_reader.u32_le()?;
+                    let region = Rect::new(
+                        usize::from(block_reader.u16_le()?),
+                        usize::from(block_reader.u16_le()?),
+                        usize::from(block_reader.u16_le()?),
+                        usize::from(block_reader.u16_le()?),
+                    );
+                    let offset_x = f64::from(block_reader.i16_le()?);
+                    let offset_y = f64::from(block_reader.i16_le()?);
+                    let advance = f64::from(block_reader.i16_le()?);
+                    let page = usize::from(block_reader.u8()?);
+                    block_reader.skip(1)?; // channel
+                    if let Some(character) = char::from_u32(id) {
+                        font.glyphs.insert(character, Glyph { page, region, offset_x, offset_y, advance });
+                    }
+                }
+            },
+            5 => {
+                for _ in 0..block.len() / 10 {
+                    let first_id = block_reader.u32_le()?;
+                    let second_id = block_reader.u32_le()?;
+                    let amount = f64::from(block_reader.i16_le()?);
+                    if let (Some(first), Some(second)) = (char::from_u32(first_id), char::from_u32(second_id)) {
+                        font.kernings.insert((first, second), amount);
+                    }
+                }
+            },
+            _ => {},
+        }
+    }
+    Ok(font)
+}
+
+/// Splits a line of a text descriptor into its tag followed by `key=value`
+/// fields. Values may be quoted to include spaces. The tag is returned as a
+/// key with an empty value.
+#[expect(clippy::single_call_fn, reason = "kept separate from decode_text for readability")]
+fn tokens(line: &str) -> Vec<(&str, &str)> {
+    let mut tokens = Vec::new();
+    let mut rest = line.trim_start();
+    while !rest.is_empty() {
+        let key_end = rest.find(['=', ' ', '\t']).unwrap_or(rest.len());
+        let (key, after_key) = rest.split_at(key_end);
+        let (value, after_value) = after_key.strip_prefix('=').map_or(("", after_key), |value_start| {
+            value_start.strip_prefix('"').map_or_else(
+                || value_start.split_at(value_start.find([' ', '\t']).unwrap_or(value_start.len())),
+                |quoted| {
+                    let (value, remaining) = quoted.split_at(quoted.find('"').unwrap_or(quoted.len()));
+                    (value, remaining.strip_prefix('"').unwrap_or(remaining))
+                },
+            )
+        });
+        tokens.push((key, value));
+        rest = after_value.trim_start();
+    }
+    tokens
+}
+
+/// Gets the value of a field, if it is present.
+fn field<'line>(fields: &[(&str, &'line str)], name: &str) -> Option<&'line str> {
+    fields.iter().find(|&&(key, _)| key == name).map(|&(_, value)| value)
+}
+
+/// Gets the value of a numeric field, or 0 if it is missing.
+fn number(fields: &[(&str, &str)], name: &str) -> Result<f64, Error> {
+    field(fields, name).map_or(Ok(0.0), |value| value.parse::<f64>()
+        .map_err(|error| Error::msg(format!("Pixl: BmFont::decode: invalid value for {name}: {error}"))))
+}
+
+/// Gets the value of a field holding a character id, or `None` if it is not
+/// a valid character, such as the -1 which `BMFont` uses for its "invalid
+/// character" glyph.
+fn character_field(fields: &[(&str, &str)], name: &str) -> Result<Option<char>, Error> {
+    Ok(cast::u32(number(fields, name)?).ok().and_then(char::from_u32))
+}
@@ -0,0 +1,260 @@
+//! BMP loading and saving for `Texture`, with no extra dependencies.
+//!
+//! Uncompressed 24-bit and 32-bit Windows bitmaps can be loaded, including
+//! 32-bit bitmaps with channel bitmasks (`BI_BITFIELDS`) and an alpha channel.
+//! Textures are always saved as 32-bit bitmaps with an alpha channel.
+
+use std::{fs, path::Path};
+
+use anyhow::{Context as _, Error};
+
+use crate::{Color, Texture, reader::ByteReader};
+
+/// The `BI_RGB` compression type: uncompressed pixels in BGR(A) order.
+const BI_RGB: u32 = 0;
+/// The `BI_BITFIELDS` compression type: uncompressed pixels whose channels
+/// are located by bitmasks.
+const BI_BITFIELDS: u32 = 3;
+/// The `BI_ALPHABITFIELDS` compression type: like `BI_BITFIELDS`, with an
+/// alpha bitmask too.
+const BI_ALPHABITFIELDS: u32 = 6;
+/// The size of the BMP file header, in bytes.
+const FILE_HEADER_SIZE: u32 = 14;
+/// The size of the `BITMAPV4HEADER` info header written by `encode_bmp`.
+const V4_HEADER_SIZE: u32 = 108;
+
+#[expect(clippy::multiple_inherent_impl, reason = "each image format is kept in its own module")]
+impl Texture {
+    /// Loads a BMP image file into a new `Texture`.
+    /// # Arguments
+    /// * `path` - The path of the BMP file to load.
+    /// # Returns
+    /// A `Texture` with the same size and pixels as the image.
+    /// # Errors
+    /// Errors if the file cannot be read, or is not a 24-bit or 32-bit
+    /// uncompressed BMP image.
+    /// # Example
+    /// ```rust
+    /// let tiles = Texture::load_bmp("assets/tiles.bmp")
+    ///     .expect("failed to load tiles");
+    /// ```
+    pub fn load_bmp<P: AsRef<Path>>(path: P) -> Result<Texture, Error> {
+        let bytes = fs::read(path).context("Pixl: load_bmp: failed to read file")?;
+        Texture::decode_bmp(&bytes)
+    }
+
+    /// Saves the texture to a 32-bit BMP image file, including its alpha
+    /// channel.
+    /// # Arguments
+    /// * `path` - The path of the BMP file to create or overwrite.
+    /// # Errors
+    /// Errors if the texture is too large for a BMP image, or the file cannot
+    /// be written.
+    /// # Example
+    /// ```rust
+    /// texture.save_bmp("screenshot.bmp")
+    ///     .expect("failed to save screenshot");
+    /// ```
+    pub fn save_bmp<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        fs::write(path, self.encode_bmp()?).context("Pixl: save_bmp: failed to write file")
+    }
+
+    /// Decodes an in-memory BMP image into a new `Texture`.
+    /// # Arguments
+    /// * `bytes` - The contents of a 24-bit or 32-bit uncompressed BMP file.
+    /// # Returns
+    /// A `Texture` with the same size and pixels as the image.
+    /// # Errors
+    /// Errors if `bytes` is not a 24-bit or 32-bit uncompressed BMP image.
+    /// # Example
+    /// ```rust
+    /// let cursor = Texture::decode_bmp(include_bytes!("cursor.bmp"))
+    ///     .expect("invalid cursor image");
+    /// ```
+    #[expect(clippy::little_endian_bytes, reason = "BMP files are little-endian")]
+    pub fn decode_bmp(bytes: &[u8]) -> Result<Texture, Error> {
+        let mut reader = ByteReader::new(bytes, "decode_bmp");
+        if reader.array()? != *b"BM" {
+            return Err(Error::msg("Pixl: decode_bmp: missing BMP signature"));
+        }
+        reader.skip(8)?;
+        let pixel_offset = cast::usize(reader.u32_le()?);
+
+        let header_size = reader.u32_le()?;
+        if header_size < 40 {
+            return Err(Error::msg("Pixl: decode_bmp: unsupported BMP info header"));
+        }
+        let raw_width = reader.i32_le()?;
+        let raw_height = reader.i32_le()?;
+        reader.skip(2)?;
+        let bits_per_pixel = reader.u16_le()?;
+        let compression = reader.u32_le()?;
+        reader.skip(20)?;
+
+        let masks = match (bits_per_pixel, compression) {
+            (24 | 32, BI_RGB) => Masks::DEFAULT_BGRA,
+            (32, BI_BITFIELDS | BI_ALPHABITFIELDS) => {
+                // the masks follow the 40-byte header, and are part of the
+                // header itself in V4 and V5 headers
+                let mut masks = Masks {
+                    red: reader.u32_le()?,
+                    green: reader.u32_le()?,
+                    blue: reader.u32_le()?,
+                    alpha: 0,
+                };
+                if compression == BI_ALPHABITFIELDS || header_size >= V4_HEADER_SIZE {
+                    masks.alpha = reader.u32_le()?;
+                }
+                masks
+            },
+            _ => return Err(Error::msg("Pixl: decode_bmp: only uncompressed 24-bit and 32-bit BMP images are supported")),
+        };
+
+        let width = usize::try_from(raw_width).context("Pixl: decode_bmp: negative image width")?;
+        // a negative height means the rows are stored top-down
+        let top_down = raw_height.is_negative();
+        let height = cast::usize(raw_height.unsigned_abs());
+        let bytes_per_pixel = usize::from(bits_per_pixel / 8);
+        let row_size = width.checked_mul(bytes_per_pixel)
+            .and_then(|size| size.checked_next_multiple_of(4))
+            .ok_or_else(|| Error::msg("Pixl: decode_bmp: image is too large"))?;
+
+        // the size is checked against the pixel data before anything is
+        // allocated, so a header can't ask for more memory than the file backs
+        let data_end = height.checked_mul(row_size)
+            .and_then(|size| size.checked_add(pixel_offset))
+            .ok_or_else(|| Error::msg("Pixl: decode_bmp: image is too large"))?;
+        if data_end > bytes.len() {
+            return Err(Error::msg("Pixl: decode_bmp: pixel data is truncated"));
+        }
+        if width == 0 {
+            return Texture::from_pixels(0, height, Vec::new());
+        }
+
+        let mut pixels = Vec::with_capacity(width.saturating_mul(height));
+        for row in 0..height {
+            let file_row = if top_down { row } else { height.saturating_sub(row).saturating_sub(1) };
+            reader.seek(file_row.checked_mul(row_size)
+                .and_then(|offset| offset.checked_add(pixel_offset))
+                .ok_or_else(|| Error::msg("Pixl: decode_bmp: image is too large"))?)?;
+            let row_bytes = reader.bytes(width.saturating_mul(bytes_per_pixel))?;
+            for pixel in row_bytes.chunks_exact(bytes_per_pixel) {
+                pixels.push(match *pixel {
+                    [b, g, r] => Color::rgb(r, g, b),
+                    [b0, b1, b2, b3] => masks.extract(u32::from_le_bytes([b0, b1, b2, b3])),
+                    // only 24-bit and 32-bit images are accepted above
+                    _ => Color::rgba(0, 0, 0, 0),
+                });
+            }
+        }
+
+        // 32-bit BI_RGB images officially have no alpha channel, and most
+        // writers leave the unused byte as zero
+        if compression == BI_RGB && pixels.iter().all(|pixel| pixel.a == 0) {
+            for pixel in &mut pixels {
+                pixel.a = u8::MAX;
+            }
+        }
+        Texture::from_pixels(width, height, pixels)
+    }
+
+    /// Encodes the texture as an in-memory 32-bit BMP image, including its
+    /// alpha channel.
+    /// # Returns
+    /// The contents of the BMP file.
+    /// # Errors
+    /// Errors if the texture is too large to be stored in a BMP image.
+    /// # Example
+    /// ```rust
+    /// let bytes = texture.encode_bmp().expect("failed to encode texture");
+    /// ```
+    #[expect(clippy::little_endian_bytes, reason = "BMP files are little-endian")]
+    pub fn encode_bmp(&self) -> Result<Vec<u8>, Error> {
+        let width = i32::try_from(self.get_width()).context("Pixl: encode_bmp: texture is too wide")?;
+        let height = i32::try_from(self.get_height()).context("Pixl: encode_bmp: texture is too tall")?;
+        let image_size = u32::try_from(self.get_pixels().len())
+            .ok()
+            .and_then(|count| count.checked_mul(4))
+            .ok_or_else(|| Error::msg("Pixl: encode_bmp: texture is too large"))?;
+        let pixel_offset = FILE_HEADER_SIZE.saturating_add(V4_HEADER_SIZE);
+        let file_size = image_size.checked_add(pixel_offset)
+            .ok_or_else(|| Error::msg("Pixl: encode_bmp: texture is too large"))?;
+
+        let mut bytes = Vec::with_capacity(cast::usize(file_size));
+        // file header
+        bytes.extend_from_slice(b"BM");
+        bytes.extend_from_slice(&file_size.to_le_bytes());
+        bytes.extend_from_slice(&[0; 4]);
+        bytes.extend_from_slice(&pixel_offset.to_le_bytes());
+        // BITMAPV4HEADER
+        bytes.extend_from_slice(&V4_HEADER_SIZE.to_le_bytes());
+        bytes.extend_from_slice(&width.to_le_bytes());
+        bytes.extend_from_slice(&height.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&32u16.to_le_bytes());
+        bytes.extend_from_slice(&BI_BITFIELDS.to_le_bytes());
+        bytes.extend_from_slice(&image_size.to_le_bytes());
+        // 2835 pixels per metre is 72 DPI
+        bytes.extend_from_slice(&2835u32.to_le_bytes());
+        bytes.extend_from_slice(&2835u32.to_le_bytes());
+        bytes.extend_from_slice(&[0; 8]);
+        for mask in [Masks::DEFAULT_BGRA.red, Masks::DEFAULT_BGRA.green, Masks::DEFAULT_BGRA.blue, Masks::DEFAULT_BGRA.alpha] {
+            bytes.extend_from_slice(&mask.to_le_bytes());
+        }
+        // the sRGB color space, with no endpoints or gamma
+        bytes.extend_from_slice(b"BGRs");
+        bytes.extend_from_slice(&[0; 48]);
+
+        // rows are stored bottom-up, and never need padding at 32 bits
+        for row in self.get_pixels().chunks(self.get_width().max(1)).rev() {
+            for pixel in row {
+                bytes.extend_from_slice(&[pixel.b, pixel.g, pixel.r, pixel.a]);
+            }
+        }
+        Ok(bytes)
+    }
+}
+
+/// The bitmasks locating each channel within a 32-bit BMP pixel.
+#[derive(Copy, Clone)]
+struct Masks {
+    /// The bits holding the red channel.
+    red: u32,
+    /// The bits holding the green channel.
+    green: u32,
+    /// The bits holding the blue channel.
+    blue: u32,
+    /// The bits holding the alpha channel, or 0 if there is no alpha channel.
+    alpha: u32,
+}
+
+impl Masks {
+    /// The masks for pixels stored as B, G, R, A bytes.
+    const DEFAULT_BGRA: Masks = Masks {
+        red: 0x00FF_0000,
+        green: 0x0000_FF00,
+        blue: 0x0000_00FF,
+        alpha: 0xFF00_0000,
+    };
+
+    /// Extracts a `Color` from a 32-bit pixel value.
+    fn extract(self, pixel: u32) -> Color {
+        Color::rgba(
+            extract_channel(pixel, self.red),
+            extract_channel(pixel, self.green),
+            extract_channel(pixel, self.blue),
+            if self.alpha == 0 { u8::MAX } else { extract_channel(pixel, self.alpha) },
+        )
+    }
+}
+
+/// Extracts the bits of `pixel` selected by `mask`, and scales them to the
+/// 0-255 range.
+fn extract_channel(pixel: u32, mask: u32) -> u8 {
+    if mask == 0 {
+        return 0;
+    }
+    let value = u64::from((pixel & mask) >> mask.trailing_zeros());
+    let max = (1u64 << mask.count_ones()).saturating_sub(1);
+    cast::u8(value.saturating_mul(255).checked_div(max).unwrap_or(0)).unwrap_or(u8::MAX)
+}
@@ -0,0 +1,580 @@
+//! Parsing of CFF (Compact Font Format) outlines, which OpenType fonts with a
+//! `CFF ` table use instead of TrueType outlines.
+//!
+//! Each glyph is a small program (a "Type 2 charstring") which draws its
+//! outline with cubic curves, and may call shared subroutines. Hints are
+//! skipped, as the rasterizer anti-aliases instead.
+
+use core::{array, iter, mem};
+use std::collections::HashMap;
+
+use anyhow::Error;
+
+use crate::{raster::{Point, Segment}, reader::ByteReader};
+
+/// The context used in error messages while decoding a font.
+const DECODE_CONTEXT: &str = "TrueTypeFont::decode";
+
+/// The context used in error messages while drawing a glyph.
+const GLYPH_CONTEXT: &str = "TrueTypeFont::get_glyph";
+
+/// The most values a charstring may push before using them.
+const MAX_STACK: usize = 48;
+
+/// The deepest subroutines may call each other.
+const MAX_SUBROUTINE_DEPTH: usize = 10;
+
+/// The most operators and operands a glyph's charstring may run, counting
+/// those in subroutines, so that subroutines which call each other many
+/// times over cannot take forever to run.
+const MAX_OPERATIONS: usize = 0x1_0000;
+
+/// The outlines of every glyph in a CFF table.
+pub struct CffOutlines {
+    /// The charstring of every glyph.
+    char_strings: Index,
+    /// The subroutines shared by every glyph.
+    global_subroutines: Index,
+    /// The local subroutines of each font dict. Fonts which are not CID-keyed
+    /// have a single font dict.
+    local_subroutines: Vec<Index>,
+    /// The font dict used by each glyph. Empty if the font is not CID-keyed,
+    /// in which case every glyph uses the first.
+    font_dict_select: Vec<u8>,
+}
+
+impl CffOutlines {
+    /// Parses the contents of a `CFF ` table.
+    #[expect(clippy::single_call_fn, reason = "kept separate from TrueTypeFont::decode for readability")]
+    pub fn parse(cff: &[u8]) -> Result<CffOutlines, Error> {
+        let mut reader = ByteReader::new(cff, DECODE_CONTEXT);
+        reader.skip(2)?; // version
+        let header_size = usize::from(reader.u8()?);
+        reader.seek(header_size)?;
+        read_index(&mut reader)?; // font names
+        let top_dicts = read_index(&mut reader)?;
+        read_index(&mut reader)?; // strings
+        let global_subroutines = read_index(&mut reader)?;
+        let top_dict = parse_dict(top_dicts.get(cff, 0)
+            .ok_or_else(|| Error::msg("Pixl: TrueTypeFont::decode: CFF table has no fonts"))?)?;
+
+        let char_strings_offset = dict_offset(&top_dict, operator::CHAR_STRINGS)
+            .ok_or_else(|| Error::msg("Pixl: TrueTypeFont::decode: CFF font has no glyphs"))?;
+        reader.seek(char_strings_offset)?;
+        let char_strings = read_index(&mut reader)?;
+
+        let (local_subroutines, font_dict_select) = match dict_offset(&top_dict, operator::FD_ARRAY) {
+            Some(font_dicts_offset) => {
+                reader.seek(font_dicts_offset)?;
+                let font_dicts = read_index(&mut reader)?;
+                let local_subroutines = (0..font_dicts.len()).map(|index| {
+                    let font_dict = parse_dict(font_dicts.get(cff, index).unwrap_or_default())?;
+                    read_local_subroutines(cff, &font_dict)
+                }).collect::<Result<Vec<Index>, Error>>()?;
+                let select_offset = dict_offset(&top_dict, operator::FD_SELECT)
+                    .ok_or_else(|| Error::msg("Pixl: TrueTypeFont::decode: CID-keyed CFF font has no FDSelect"))?;
+                reader.seek(select_offset)?;
+                (local_subroutines, read_font_dict_select(&mut reader, char_strings.len())?)
+            },
+            None => (vec![read_local_subroutines(cff, &top_dict)?], Vec::new()),
+        };
+
+        Ok(CffOutlines { char_strings, global_subroutines, local_subroutines, font_dict_select })
+    }
+
+    /// Gets the outline of a glyph in font units, with y pointing up.
+    /// # Errors
+    /// Errors if the glyph does not exist or its charstring is invalid.
+    pub fn outline(&self, cff: &[u8], glyph: u16) -> Result<Vec<Segment>, Error> {
+        let index = usize::from(glyph);
+        let program = self.char_strings.get(cff, index)
+            .ok_or_else(|| Error::msg("Pixl: TrueTypeFont::get_glyph: glyph does not exist"))?;
+        let font_dict = self.font_dict_select.get(index).copied().unwrap_or(0);
+        let local_subroutines = self.local_subroutines.get(usize::from(font_dict))
+            .ok_or_else(|| Error::msg("Pixl: TrueTypeFont::get_glyph: glyph uses a missing font dict"))?;
+        let mut interpreter = Interpreter {
+            cff,
+            global_subroutines: &self.global_subroutines,
+            local_subroutines,
+            stack: Vec::new(),
+            segments: Vec::new(),
+            position: (0.0f64, 0.0f64),
+            contour_start: None,
+            stem_count: 0,
+            seen_width: false,
+            operations_left: MAX_OPERATIONS,
+        };
+        interpreter.run(program, 0)?;
+        interpreter.close_contour();
+        Ok(interpreter.segments)
+    }
+}
+
+/// The DICT operators which Pixl reads.
+mod operator {
+    /// The offset of the charstrings INDEX.
+    pub const CHAR_STRINGS: u16 = 17;
+    /// The size and offset of the Private DICT.
+    pub const PRIVATE: u16 = 18;
+    /// The offset of the local subroutines INDEX, from the Private DICT.
+    pub const SUBROUTINES: u16 = 19;
+    /// The offset of the font dicts INDEX of a CID-keyed font.
+    pub const FD_ARRAY: u16 = 1236;
+    /// The offset of the table choosing each glyph's font dict.
+    pub const FD_SELECT: u16 = 1237;
+}
+
+/// A CFF INDEX: a list of byte strings, stored as their ranges in the table.
+#[derive(Default)]
+struct Index {
+    /// The (start, end) of every item.
+    ranges: Vec<(usize, usize)>,
+}
+
+impl Index {
+    /// Gets the number of items.
+    const fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Gets the bytes of an item.
+    fn get<'data>(&self, cff: &'data [u8], index: usize) -> Option<&'data [u8]> {
+        let &(start, end) = self.ranges.get(index)?;
+        cff.get(start..end)
+    }
+
+    /// Gets the bytes of a subroutine from the number a charstring called it
+    /// with, which is offset so that small charstrings can use small numbers.
+    fn get_subroutine<'data>(&self, cff: &'data [u8], number: f64) -> Option<&'data [u8]> {
+        let bias = match self.len() {
+            0..1240 => 107.0f64,
+            1240..33900 => 1131.0f64,
+            _ => 32768.0f64,
+        };
+        self.get(cff, cast::usize(number + bias).ok()?)
+    }
+}
+
+/// Reads an INDEX, leaving the reader just after it.
+fn read_index(reader: &mut ByteReader) -> Result<Index, Error> {
+    let count = usize::from(reader.u16_be()?);
+    if count == 0 {
+        return Ok(Index::default());
+    }
+    let offset_size = usize::from(reader.u8()?);
+    if !(1..=4).contains(&offset_size) {
+        return Err(Error::msg("Pixl: TrueTypeFont::decode: CFF INDEX has an invalid offset size"));
+    }
+    let offsets = iter::repeat_with(|| Ok(reader.bytes(offset_size)?.iter().fold(0usize, |value, &byte| value.saturating_mul(256).saturating_add(usize::from(byte)))))
+        .take(count.saturating_add(1))
+        .collect::<Result<Vec<usize>, Error>>()?;
+    // offsets count from 1, starting at the byte before the data
+    let base = reader.position().saturating_sub(1);
+    let ranges = offsets.windows(2).map(|pair| match *pair {
+        [start, end] if start >= 1 && start <= end => Ok((base.saturating_add(start), base.saturating_add(end))),
+        _ => Err(Error::msg("Pixl: TrueTypeFont::decode: CFF INDEX has invalid offsets")),
+    }).collect::<Result<Vec<(usize, usize)>, Error>>()?;
+    reader.seek(ranges.last().map_or(base, |&(_, end)| end))?;
+    Ok(Index { ranges })
+}
+
+/// Reads the local subroutines of a top or font dict, from its Private DICT.
+fn read_local_subroutines(cff: &[u8], dict: &HashMap<u16, Vec<f64>>) -> Result<Index, Error> {
+    let Some(&[size, offset]) = dict.get(&operator::PRIVATE).map(Vec::as_slice) else {
+        return Ok(Index::default());
+    };
+    let (Ok(private_size), Ok(private_offset)) = (cast::usize(size), cast::usize(offset)) else {
+        return Err(Error::msg("Pixl: TrueTypeFont::decode: CFF Private DICT has an invalid location"));
+    };
+    let private_dict = parse_dict(cff.get(private_offset..private_offset.saturating_add(private_size))
+        .ok_or_else(|| Error::msg("Pixl: TrueTypeFont::decode: CFF Private DICT is out of bounds"))?)?;
+    let Some(subroutines_offset) = dict_offset(&private_dict, operator::SUBROUTINES) else {
+        return Ok(Index::default());
+    };
+    let mut reader = ByteReader::new(cff, DECODE_CONTEXT);
+    reader.seek(private_offset.saturating_add(subroutines_offset))?;
+    read_index(&mut reader)
+}
+
+/// Reads which font dict each glyph of a CID-keyed font uses.
+#[expect(clippy::single_call_fn, reason = "kept separate from CffOutlines::parse for readability")]
+fn read_font_dict_select(reader: &mut ByteReader, glyph_count: usize) -> Result<Vec<u8>, Error> {
+    match reader.u8()? {
+        0 => Ok(reader.bytes(glyph_count)?.to_vec()),
+        3 => {
+            let range_count = reader.u16_be()?;
+            let mut select = Vec::with_capacity(glyph_count);
+            let mut first = usize::from(reader.u16_be()?);
+            for _ in 0..range_count {
+                let font_dict = reader.u8()?;
+                let next = usize::from(reader.u16_be()?);
+                select.resize(next.max(first).min(glyph_count), font_dict);
+                first = next;
+            }
+            Ok(select)
+        },
+        _ => Err(Error::msg("Pixl: TrueTypeFont::decode: unsupported CFF FDSelect format")),
+    }
+}
+
+/// Gets a DICT value which holds a single offset.
+fn dict_offset(dict: &HashMap<u16, Vec<f64>>, operator: u16) -> Option<usize> {
+    cast::usize(*dict.get(&operator)?.first()?).ok()
+}
+
+/// Parses a DICT into the operands of each of its operators. Two-byte
+/// operators are stored as 1200 plus their second byte.
+fn parse_dict(bytes: &[u8]) -> Result<HashMap<u16, Vec<f64>>, Error> {
+    let mut reader = ByteReader::new(bytes, DECODE_CONTEXT);
+    let mut dict = HashMap::new();
+    let mut operands = Vec::new();
+    while reader.position() < bytes.len() {
+        let first = reader.u8()?;
+        match first {
+            0..=21 => {
+                let operator = if first == 12 { 1200u16.saturating_add(u16::from(reader.u8()?)) } else { u16::from(first) };
+                dict.insert(operator, mem::take(&mut operands));
+            },
+            28 => operands.push(f64::from(reader.i16_be()?)),
+            29 => operands.push(f64::from(reader.i32_be()?)),
+            30 => operands.push(read_real(&mut reader)?),
+            32..=254 => operands.push(read_small_number(&mut reader, first)?),
+            _ => return Err(Error::msg("Pixl: TrueTypeFont::decode: CFF DICT is invalid")),
+        }
+    }
+    Ok(dict)
+}
+
+/// Reads a number stored in one or two bytes, in both DICTs and charstrings,
+/// given its first byte from 32 to 254.
+fn read_small_number(reader: &mut ByteReader, first: u8) -> Result<f64, Error> {
+    let first_value = f64::from(first);
+    Ok(match first {
+        32..=246 => first_value - 139.0,
+        247..=250 => (first_value - 247.0).mul_add(256.0, f64::from(reader.u8()?) + 108.0),
+        _ => (251.0 - first_value).mul_add(256.0, -f64::from(reader.u8()?) - 108.0),
+    })
+}
+
+/// Reads a real number from a DICT, stored as a string of 4-bit codes.
+#[expect(clippy::single_call_fn, reason = "kept separate from parse_dict for readability")]
+fn read_real(reader: &mut ByteReader) -> Result<f64, Error> {
+    let mut text = String::new();
+    loop {
+        let byte = reader.u8()?;
+        for nibble in [byte >> 4u8, byte & 0x0F] {
+            match nibble {
+                0..=9 => text.push(char::from(b'0'.saturating_add(nibble))),
+                0xA => text.push('.'),
+                0xB => text.push('E'),
+                0xC => text.push_str("E-"),
+                0xE => text.push('-'),
+                0xF => return Ok(text.parse().unwrap_or(0.0)),
+                _ => {},
+            }
+        }
+    }
+}
+
+/// What a charstring does after one of its operators.
+enum Flow {
+    /// Keep running the current charstring or subroutine.
+    Continue,
+    /// Return from the current subroutine.
+    Return,
+    /// The glyph is finished.
+    End,
+}
+
+/// Runs the charstring of a single glyph, collecting its outline.
+struct Interpreter<'font> {
+    /// The contents of the CFF table.
+    cff: &'font [u8],
+    /// The subroutines shared by every glyph.
+    global_subroutines: &'font Index,
+    /// The subroutines of the glyph's font dict.
+    local_subroutines: &'font Index,
+    /// The values waiting to be used by the next operator.
+    stack: Vec<f64>,
+    /// The outline drawn so far.
+    segments: Vec<Segment>,
+    /// The current point.
+    position: Point,
+    /// The first point of the contour being drawn, if there is one.
+    contour_start: Option<Point>,
+    /// The number of stem hints declared, which sets the size of hint masks.
+    stem_count: usize,
+    /// Whether the optional advance width at the start of the charstring has
+    /// been checked for.
+    seen_width: bool,
+    /// How many more operators and operands may be run.
+    operations_left: usize,
+}
+
+impl Interpreter<'_> {
+    /// Runs a charstring or subroutine.
+    fn run(&mut self, program: &[u8], depth: usize) -> Result<Flow, Error> {
+        if depth > MAX_SUBROUTINE_DEPTH {
+            return Err(Error::msg("Pixl: TrueTypeFont::get_glyph: CFF subroutines are nested too deeply"));
+        }
+        let mut reader = ByteReader::new(program, GLYPH_CONTEXT);
+        while reader.position() < program.len() {
+            self.operations_left = self.operations_left.checked_sub(1)
+                .ok_or_else(|| Error::msg("Pixl: TrueTypeFont::get_glyph: CFF charstring ran for too long"))?;
+            let first = reader.u8()?;
+            let flow = match first {
+                28 => self.push(f64::from(reader.i16_be()?)),
+                32..=254 => self.push(read_small_number(&mut reader, first)?),
+                255 => self.push(f64::from(reader.i32_be()?) / 65536.0),
+                10 | 29 => {
+                    let subroutines = if first == 10 { self.local_subroutines } else { self.global_subroutines };
+                    let number = self.stack.pop().unwrap_or_default();
+                    let subroutine = subroutines.get_subroutine(self.cff, number)
+                        .ok_or_else(|| Error::msg("Pixl: TrueTypeFont::get_glyph: CFF charstring called a missing subroutine"))?;
+                    match self.run(subroutine, depth.saturating_add(1))? {
+                        Flow::End => Flow::End,
+                        Flow::Continue | Flow::Return => Flow::Continue,
+                    }
+                },
+                11 => Flow::Return,
+                12 => {
+                    let second = reader.u8()?;
+                    self.flex(second)?
+                },
+                19 | 20 => {
+                    // stems may be declared just before the first hint mask
+                    self.stems();
+                    reader.skip(self.stem_count.div_ceil(8))?;
+                    Flow::Continue
+                },
+                _ => self.operator(first)?,
+            };
+            match flow {
+                Flow::Continue => {},
+                Flow::Return | Flow::End => return Ok(flow),
+            }
+        }
+        Ok(Flow::Continue)
+    }
+
+    /// Runs an operator which takes its values from the stack.
+    fn operator(&mut self, operator: u8) -> Result<Flow, Error> {
+        match operator {
+            1 | 3 | 18 | 23 => self.stems(),
+            4 | 21 | 22 => {
+                let operand_count = if operator == 21 { 2 } else { 1 };
+                self.take_width(self.stack.len() > operand_count);
+                self.close_contour();
+                let (x, y) = self.position;
+                self.position = match operator {
+                    4 => (x, y + self.get(0)),
+                    21 => (x + self.get(0), y + self.get(1)),
+                    _ => (x + self.get(0), y),
+                };
+            },
+            5 => self.lines(0),
+            6 | 7 => self.alternating_lines(operator == 7),
+            8 => self.curves(0),
+            24 => {
+                let curves_end = self.stack.len().saturating_sub(2);
+                self.curves_between(0, curves_end);
+                self.lines(curves_end);
+            },
+            25 => {
+                let lines_end = self.stack.len().saturating_sub(6);
+                self.lines_between(0, lines_end);
+                self.curves(lines_end);
+            },
+            26 | 27 => self.parallel_curves(operator == 26),
+            30 | 31 => self.alternating_curves(operator == 30),
+            14 => {
+                self.take_width(self.stack.len() % 2 == 1);
+                self.close_contour();
+                return Ok(Flow::End);
+            },
+            _ => return Err(Error::msg(format!("Pixl: TrueTypeFont::get_glyph: unsupported CFF operator {operator}"))),
+        }
+        self.stack.clear();
+        Ok(Flow::Continue)
+    }
+
+    /// Pushes a value onto the stack.
+    fn push(&mut self, value: f64) -> Flow {
+        if self.stack.len() < MAX_STACK {
+            self.stack.push(value);
+        }
+        Flow::Continue
+    }
+
+    /// Gets a value from the stack, or 0.0 if there are not enough.
+    fn get(&self, index: usize) -> f64 {
+        self.stack.get(index).copied().unwrap_or_default()
+    }
+
+    /// Removes the advance width from the bottom of the stack, if the first
+    /// operator of the charstring has one.
+    fn take_width(&mut self, has_width: bool) {
+        if !self.seen_width {
+            self.seen_width = true;
+            if has_width && !self.stack.is_empty() {
+                self.stack.remove(0);
+            }
+        }
+    }
+
+    /// Counts the stem hints on the stack, which come in pairs.
+    fn stems(&mut self) {
+        self.take_width(self.stack.len() % 2 == 1);
+        self.stem_count = self.stem_count.saturating_add(self.stack.len() / 2);
+        self.stack.clear();
+    }
+
+    /// Draws lines to points given by pairs of stack values, from `start`.
+    fn lines(&mut self, start: usize) {
+        self.lines_between(start, self.stack.len());
+    }
+
+    /// Draws lines to points given by pairs of stack values, from `start` up
+    /// to `end`.
+    fn lines_between(&mut self, start: usize, end: usize) {
+        for index in (start..end.saturating_sub(1)).step_by(2) {
+            let (x, y) = self.position;
+            self.line_to((x + self.get(index), y + self.get(index.saturating_add(1))));
+        }
+    }
+
+    /// Draws lines which alternate between horizontal and vertical.
+    fn alternating_lines(&mut self, vertical_first: bool) {
+        let mut vertical = vertical_first;
+        for index in 0..self.stack.len() {
+            let (x, y) = self.position;
+            let distance = self.get(index);
+            self.line_to(if vertical { (x, y + distance) } else { (x + distance, y) });
+            vertical = !vertical;
+        }
+    }
+
+    /// Draws curves given by groups of six stack values, from `start`.
+    fn curves(&mut self, start: usize) {
+        self.curves_between(start, self.stack.len());
+    }
+
+    /// Draws curves given by groups of six stack values, from `start` up to
+    /// `end`.
+    fn curves_between(&mut self, start: usize, end: usize) {
+        for index in (start..end.saturating_sub(5)).step_by(6) {
+            let values: [f64; 6] = array::from_fn(|offset| self.get(index.saturating_add(offset)));
+            self.relative_curve(values);
+        }
+    }
+
+    /// Draws curves which start and end in the same direction: vertical if
+    /// `vertical` is true, otherwise horizontal. An odd value at the start
+    /// moves the first control point sideways.
+    fn parallel_curves(&mut self, vertical: bool) {
+        let mut index = self.stack.len() % 2;
+        let mut sideways = if index == 1 { self.get(0) } else { 0.0f64 };
+        while index.saturating_add(4) <= self.stack.len() {
+            let [along_1, x_2, y_2, along_3] = array::from_fn(|offset| self.get(index.saturating_add(offset)));
+            self.relative_curve(if vertical {
+                [sideways, along_1, x_2, y_2, 0.0f64, along_3]
+            } else {
+                [along_1, sideways, x_2, y_2, along_3, 0.0f64]
+            });
+            sideways = 0.0f64;
+            index = index.saturating_add(4);
+        }
+    }
+
+    /// Draws curves which alternate between starting vertically and ending
+    /// horizontally, and the other way around. A fifth value on the last
+    /// curve moves its end point.
+    fn alternating_curves(&mut self, vertical_first: bool) {
+        let mut vertical = vertical_first;
+        let mut index = 0usize;
+        while index.saturating_add(4) <= self.stack.len() {
+            let [start, x_2, y_2, end] = array::from_fn(|offset| self.get(index.saturating_add(offset)));
+            let last = if self.stack.len().saturating_sub(index) == 5 { self.get(index.saturating_add(4)) } else { 0.0f64 };
+            self.relative_curve(if vertical {
+                [0.0f64, start, x_2, y_2, end, last]
+            } else {
+                [start, 0.0f64, x_2, y_2, last, end]
+            });
+            vertical = !vertical;
+            index = index.saturating_add(4);
+        }
+    }
+
+    /// Runs one of the flex operators, which draw two curves that may be
+    /// flattened at small sizes.
+    fn flex(&mut self, operator: u8) -> Result<Flow, Error> {
+        let values = self.stack.clone();
+        let at = |index: usize| values.get(index).copied().unwrap_or_default();
+        let start = self.position;
+        match operator {
+            35 => {
+                self.relative_curve([at(0), at(1), at(2), at(3), at(4), at(5)]);
+                self.relative_curve([at(6), at(7), at(8), at(9), at(10), at(11)]);
+            },
+            34 => {
+                self.relative_curve([at(0), 0.0f64, at(1), at(2), at(3), 0.0f64]);
+                self.relative_curve([at(4), 0.0f64, at(5), -at(2), at(6), 0.0f64]);
+            },
+            36 => {
+                self.relative_curve([at(0), at(1), at(2), at(3), at(4), 0.0f64]);
+                // the second curve ends level with where the first started
+                let end_y = start.1 - (self.position.1 + at(7));
+                self.relative_curve([at(5), 0.0f64, at(6), at(7), at(8), end_y]);
+            },
+            37 => {
+                let total_x = at(0) + at(2) + at(4) + at(6) + at(8);
+                let total_y = at(1) + at(3) + at(5) + at(7) + at(9);
+                self.relative_curve([at(0), at(1), at(2), at(3), at(4), at(5)]);
+                // the last value moves along whichever direction the flex
+                // travels furthest, and the other returns to the start
+                let (last_x, last_y) = if total_x.abs() > total_y.abs() {
+                    (at(10), start.1 - (self.position.1 + at(7) + at(9)))
+                } else {
+                    (start.0 - (self.position.0 + at(6) + at(8)), at(10))
+                };
+                self.relative_curve([at(6), at(7), at(8), at(9), last_x, last_y]);
+            },
+            _ => return Err(Error::msg(format!("Pixl: TrueTypeFont::get_glyph: unsupported CFF operator 12 {operator}"))),
+        }
+        self.stack.clear();
+        Ok(Flow::Continue)
+    }
+
+    /// Draws a cubic curve where each point is relative to the one before.
+    fn relative_curve(&mut self, [x_1, y_1, x_2, y_2, x_3, y_3]: [f64; 6]) {
+        let start = self.position;
+        let control_1 = (start.0 + x_1, start.1 + y_1);
+        let control_2 = (control_1.0 + x_2, control_1.1 + y_2);
+        let end = (control_2.0 + x_3, control_2.1 + y_3);
+        self.begin_contour();
+        self.segments.push(Segment::Cubic(start, control_1, control_2, end));
+        self.position = end;
+    }
+
+    /// Draws a straight line from the current point.
+    fn line_to(&mut self, point: Point) {
+        self.begin_contour();
+        self.segments.push(Segment::Line(self.position, point));
+        self.position = point;
+    }
+
+    /// Starts a new contour at the current point, if one has not started.
+    const fn begin_contour(&mut self) {
+        if self.contour_start.is_none() {
+            self.contour_start = Some(self.position);
+        }
+    }
+
+    /// Finishes the current contour by joining it back up to where it
+    /// started, as charstrings leave contours open.
+    fn close_contour(&mut self) {
+        if let Some(start) = self.contour_start.take() && start != self.position {
+            self.segments.push(Segment::Line(self.position, start));
+        }
+    }
+}
@@ -1,16 +1,26 @@
 //! The `CircleNode` struct, storing components to represent a filled circle on
 //! a texture.
 
-use crate::{Color, component::{DrawComponent, FillColorComponent, PositionComponent}};
+use crate::{BlendMode, Color, component::{BlendModeComponent, DrawComponent, FillColorComponent, PositionComponent, StrokeColorComponent, StrokeWidthComponent}, ellipse::EllipseNode, stroke::{DashPattern, StrokeAlignment}};
 
 /// A node representing a circle shape to be drawn on a texture.
 /// It has a position (top left), size (width and height, must be equal and must
 /// be twice the radius) and fill color.
+/// It can be given an outline by setting `stroke_width`, and drawn as a ring
+/// by setting `filled` to false.
+/// Integer coordinates are the centers of pixels, as with `LineNode`, and the
+/// center and radius do not need to be whole numbers. Setting `anti_aliased`
+/// gives the circle smooth edges.
+/// Circles can lie partly or wholly off the texture, in which case only the
+/// pixels on the texture are drawn.
 /// ## Implemented components:
 /// - `DrawComponent`
 /// - `PositionComponent`
 /// - `SizeComponent`
 /// - `FillColorComponent`
+/// - `StrokeColorComponent`
+/// - `StrokeWidthComponent`
+/// - `BlendModeComponent`
 #[non_exhaustive]
 #[expect(clippy::module_name_repetitions, reason = "struct should be called 'CircleNode' as it is standard.")]
 pub struct CircleNode {
@@ -26,8 +36,38 @@ pub struct CircleNode {
     /// The radius of the circle (distance from the edge to the center).
     pub radius: f64,
 
-    /// The color of the circle. It will be filled.
+    /// The color of the circle. It will be filled, and its alpha channel is
+    /// blended over the existing pixels.
     pub fill_color: Color,
+
+    /// Whether the inside of the circle is filled with `fill_color`.
+    /// Defaults to true.
+    pub filled: bool,
+
+    /// The color of the circle's outline.
+    /// Defaults to the same color as `fill_color`.
+    pub stroke_color: Color,
+
+    /// The width of the circle's outline, in pixels. Defaults to 0, which
+    /// draws no outline.
+    pub stroke_width: usize,
+
+    /// Where the outline is drawn relative to the edge of the circle.
+    /// Defaults to `StrokeAlignment::Inside`.
+    pub stroke_alignment: StrokeAlignment,
+
+    /// The pattern of dashes the outline is drawn with, or `None` to draw it
+    /// solid. Defaults to `None`.
+    pub dash_pattern: Option<DashPattern>,
+
+    /// Whether the circle is drawn with smooth, anti-aliased edges, by
+    /// blending each pixel by how much of it the circle covers.
+    /// Defaults to false.
+    pub anti_aliased: bool,
+
+    /// How the fill and stroke colors are combined with the existing pixels
+    /// when drawn. Defaults to `BlendMode::SourceOver`.
+    pub blend_mode: BlendMode,
 }
 
 
@@ -43,38 +83,37 @@ impl CircleNode {
     /// A `CircleNode` with the specified properties.
     #[must_use]
     pub const fn new(x: f64, y: f64, radius: f64, fill_color: Color) -> CircleNode {
-        CircleNode { x, y, radius, fill_color }
+        CircleNode {
+            x, y, radius, fill_color,
+            filled: true,
+            stroke_color: fill_color,
+            stroke_width: 0,
+            stroke_alignment: StrokeAlignment::Inside,
+            dash_pattern: None,
+            anti_aliased: false,
+            blend_mode: BlendMode::SourceOver,
+        }
     }
 }
 
 
 impl DrawComponent for CircleNode {
     fn draw(&self, texture: &mut crate::Texture) {
-        // This code is likely incredibly suboptimal. I chose to go with a
-        // mathematical representation of a circle here, similarly to how a
-        // raytracer would work, instead of a triangle-based rasterisation
-        // approach, but there are likely many things that could be improved
-        // here: both performance-wise and related to code readability.
-
-        let radius_squared = cast::isize(self.radius.powi(2)).unwrap_or(isize::MAX);
-        let center_x = cast::isize(self.x).unwrap_or(isize::MAX);
-        let center_y = cast::isize(self.y).unwrap_or(isize::MAX);
-        let left_x = cast::isize(self.x - self.radius).unwrap();
-        let right_x = cast::isize(self.x + self.radius).unwrap();
-        let top_y = cast::isize(self.y - self.radius).unwrap();
-        let bottom_y = cast::isize(self.y + self.radius).unwrap();
-        for y in top_y..=bottom_y {
-            for x in left_x..=right_x {
-                let dx = x.checked_sub(center_x).expect("pixl: under/overflow in circle drawing");
-                let dy = y.checked_sub(center_y).expect("pixl: under/overflow in circle drawing");
-                #[expect(clippy::arithmetic_side_effects, reason = "else unreadable")]
-                if dx * dx + dy * dy <= radius_squared {
-                    #[expect(clippy::as_conversions, clippy::cast_sign_loss, reason = "bounds are checked above")]
-                    texture.set_pixel(x as usize, y as usize, self.fill_color)
-                        .expect("pixl: failed to set pixel in circle drawing");
-                }
-            }
-        }
+        // a circle is an ellipse with both radii the same
+        EllipseNode {
+            x: self.x,
+            y: self.y,
+            radius_x: self.radius,
+            radius_y: self.radius,
+            fill_color: self.fill_color,
+            filled: self.filled,
+            stroke_color: self.stroke_color,
+            stroke_width: self.stroke_width,
+            stroke_alignment: self.stroke_alignment,
+            dash_pattern: self.dash_pattern.clone(),
+            anti_aliased: self.anti_aliased,
+            blend_mode: self.blend_mode,
+        }.draw(texture);
     }
 }
 
@@ -88,6 +127,26 @@ impl FillColorComponent for CircleNode {
     }
 }
 
+impl StrokeColorComponent for CircleNode {
+    fn get_stroke_color(&self) -> &Color {
+        &self.stroke_color
+    }
+
+    fn set_stroke_color(&mut self, color: Color) {
+        self.stroke_color = color;
+    }
+}
+
+impl StrokeWidthComponent for CircleNode {
+    fn get_stroke_width(&self) -> usize {
+        self.stroke_width
+    }
+
+    fn set_stroke_width(&mut self, width: usize) {
+        self.stroke_width = width;
+    }
+}
+
 impl PositionComponent for CircleNode {
     fn get_x(&self) -> f64 {
         self.x - self.radius
@@ -106,3 +165,13 @@ impl PositionComponent for CircleNode {
     }
 }
 
+
+impl BlendModeComponent for CircleNode {
+    fn get_blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+}
@@ -6,7 +6,7 @@
 /// Each component is an 8-bit unsigned integer (0-255).
 /// This is used by the Texture struct to define the color of each pixel.
 /// Currently, only the RGBA format is properly supported.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[non_exhaustive]
 pub struct Color {
     /// The red component of the color, ranging from 0 to 255.
@@ -103,6 +103,85 @@ impl Color {
         self.r as u32 * 0x10000 + self.g as u32 * 0x100 + self.b as u32
     }
 
+    /// Composites this color *over* a `background` color using the standard
+    /// "source-over" alpha blending operation.
+    /// The alpha channel of `self` decides how much of the background shows
+    /// through: an alpha of 255 fully replaces the background, and an alpha of
+    /// 0 leaves the background untouched.
+    /// # Arguments
+    /// * `self` - The color being drawn (the source).
+    /// * `background` - The color already present (the destination).
+    /// # Returns
+    /// The blended `Color`, including the resulting alpha.
+    /// # Example
+    /// ```rust
+    /// let half_red = Color::rgba(255, 0, 0, 128);
+    /// let blended = half_red.blend_over(Color::BLUE); // roughly purple
+    /// ```
+    #[must_use]
+    pub fn blend_over(self, background: Color) -> Color {
+        if self.a == u8::MAX || background.a == 0 {
+            return self;
+        }
+        if self.a == 0 {
+            return background;
+        }
+        let src_alpha = f64::from(self.a) / 255.0f64;
+        let dst_alpha = f64::from(background.a) / 255.0f64 * (1.0f64 - src_alpha);
+        let out_alpha = src_alpha + dst_alpha;
+        let mix = |src: u8, dst: u8| {
+            channel_from_f64(f64::from(src).mul_add(src_alpha, f64::from(dst) * dst_alpha) / out_alpha)
+        };
+        Color {
+            r: mix(self.r, background.r),
+            g: mix(self.g, background.g),
+            b: mix(self.b, background.b),
+            a: channel_from_f64(out_alpha * 255.0),
+        }
+    }
+
+    /// Multiplies each channel of this color by the matching channel of a
+    /// `tint` color, as if the color was viewed through colored glass.
+    /// Tinting by `Color::WHITE` leaves the color unchanged, and tinting by a
+    /// translucent color also makes the result translucent.
+    /// # Arguments
+    /// * `tint` - The color to multiply by.
+    /// # Returns
+    /// The tinted `Color`.
+    /// # Example
+    /// ```rust
+    /// let hurt = Color::rgb(200, 180, 160).tinted(Color::RED); // only red remains
+    /// ```
+    #[must_use]
+    pub fn tinted(self, tint: Color) -> Color {
+        let channel = |value: u8, factor: u8| {
+            cast::u8(u16::from(value).saturating_mul(u16::from(factor)).saturating_add(127) / 255).unwrap_or(u8::MAX)
+        };
+        Color {
+            r: channel(self.r, tint.r),
+            g: channel(self.g, tint.g),
+            b: channel(self.b, tint.b),
+            a: channel(self.a, tint.a),
+        }
+    }
+
+    /// Multiplies the alpha channel of this color by a factor, making it more
+    /// transparent. This is used to draw partially covered pixels at the
+    /// edges of anti-aliased shapes.
+    /// # Arguments
+    /// * `factor` - How much of the alpha channel to keep, from 0.0 (fully
+    ///   transparent) to 1.0 (unchanged).
+    /// # Returns
+    /// The color with its alpha channel scaled.
+    /// # Example
+    /// ```rust
+    /// let ghost = Color::WHITE.multiply_alpha(0.25);
+    /// ```
+    #[must_use]
+    pub fn multiply_alpha(self, factor: f64) -> Color {
+        Color { a: channel_from_f64(f64::from(self.a) * factor.clamp(0.0, 1.0)), ..self }
+    }
+
     /// A constant for the fully opaque color black (RGB: 0, 0, 0).
     pub const BLACK: Color = Color::rgb(0, 0, 0);
     /// A constant for the fully opaque color white (RGB: 255, 255, 255).
@@ -115,3 +194,10 @@ impl Color {
     pub const BLUE: Color = Color::rgb(0, 0, 255);
 
 }
+
+/// Converts a floating point channel value into a `u8` channel, rounding to the
+/// nearest integer and clamping it to the 0-255 range.
+#[expect(clippy::pub_with_shorthand, reason = "shared with the blend and svg modules, but not part of the public API")]
+pub(crate) fn channel_from_f64(value: f64) -> u8 {
+    cast::u8(value.round().clamp(0.0f64, 255.0f64)).unwrap_or(u8::MAX)
+}
@@ -15,7 +15,7 @@
 
 #![expect(clippy::module_name_repetitions, reason = "components should be explicitly defined as Components to avoid name conflicts")]
 
-use crate::{Color, Texture};
+use crate::{BlendMode, Color, Texture};
 
 
 /// This node provides functions for drawing a node to the screen.
@@ -185,3 +185,20 @@ pub trait StrokeWidthComponent {
     fn set_stroke_width(&mut self, width: usize);
 }
 
+
+/// This trait provides functions for getting and setting the blend mode of a
+/// node. The blend mode decides how the node's colors are combined with the
+/// pixels already on the texture it is drawn to.
+/// This should be implemented by any node that can be drawn with a blend mode.
+pub trait BlendModeComponent {
+
+    /// Get the blend mode of the node.
+    /// # Returns
+    /// * `BlendMode` - The blend mode used when drawing the node.
+    fn get_blend_mode(&self) -> BlendMode;
+
+    /// Set the blend mode of the node.
+    /// # Arguments
+    /// * `blend_mode: BlendMode` - The new blend mode of the node.
+    fn set_blend_mode(&mut self, blend_mode: BlendMode);
+}
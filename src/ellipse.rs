@@ -0,0 +1,360 @@
+//! `EllipseNode` struct - represents a node for an ellipse shape in a texture,
+//! such as a shadow under a character or a planet's orbit.
+
+use core::{f64::consts::{PI, TAU}, ops::Range};
+
+use crate::{BlendMode, Color, Texture, color::channel_from_f64, component::{BlendModeComponent, DrawComponent, FillColorComponent, PositionComponent, SizeComponent, StrokeColorComponent, StrokeWidthComponent}, stroke::{DashPattern, StrokeAlignment}};
+
+/// How many straight lines an ellipse is split into when measuring the
+/// distance around it for dashes.
+const ARC_STEPS: usize = 256;
+
+/// A node representing an ellipse shape to be drawn on a texture, with
+/// separate horizontal and vertical radii.
+/// Integer coordinates are the centers of pixels, as with `LineNode`, and the
+/// center and radii do not need to be whole numbers.
+/// It can be given an outline by setting `stroke_width`, drawn as a ring by
+/// setting `filled` to false, and given smooth edges by setting
+/// `anti_aliased`.
+/// Ellipses can lie partly or wholly off the texture, in which case only the
+/// pixels on the texture are drawn.
+/// ## Implemented components:
+/// - `DrawComponent`
+/// - `PositionComponent`
+/// - `SizeComponent`
+/// - `FillColorComponent`
+/// - `StrokeColorComponent`
+/// - `StrokeWidthComponent`
+/// - `BlendModeComponent`
+/// # Example
+/// ```rust
+/// let mut shadow = EllipseNode::new(player_x, player_y + 14.0, 10.0, 3.5, Color::rgba(0, 0, 0, 96));
+/// shadow.anti_aliased = true;
+/// texture.add(&shadow);
+/// ```
+#[non_exhaustive]
+#[expect(clippy::module_name_repetitions, reason = "struct should be called 'EllipseNode' as it is standard.")]
+pub struct EllipseNode {
+
+    /// The X coordinate of the **center** of the ellipse.
+    /// This is not the same value as is returned by the `get_x` method.
+    pub x: f64,
+
+    /// The Y coordinate of the **center** of the ellipse.
+    /// This is not the same value as is returned by the `get_y` method.
+    pub y: f64,
+
+    /// The distance from the center of the ellipse to its left and right
+    /// edges.
+    pub radius_x: f64,
+
+    /// The distance from the center of the ellipse to its top and bottom
+    /// edges.
+    pub radius_y: f64,
+
+    /// The color of the inside of the ellipse. Its alpha channel is blended
+    /// over the existing pixels.
+    pub fill_color: Color,
+
+    /// Whether the inside of the ellipse is filled with `fill_color`.
+    /// Defaults to true.
+    pub filled: bool,
+
+    /// The color of the ellipse's outline.
+    /// Defaults to the same color as `fill_color`.
+    pub stroke_color: Color,
+
+    /// The width of the ellipse's outline, in pixels. Defaults to 0, which
+    /// draws no outline.
+    pub stroke_width: usize,
+
+    /// Where the outline is drawn relative to the edge of the ellipse.
+    /// Defaults to `StrokeAlignment::Inside`.
+    pub stroke_alignment: StrokeAlignment,
+
+    /// The pattern of dashes the outline is drawn with, or `None` to draw it
+    /// solid. Dashes are measured along the middle of the outline, clockwise
+    /// from the right of the ellipse. Defaults to `None`.
+    pub dash_pattern: Option<DashPattern>,
+
+    /// Whether the ellipse is drawn with smooth, anti-aliased edges, by
+    /// blending each pixel by how much of it the ellipse covers.
+    /// Defaults to false.
+    pub anti_aliased: bool,
+
+    /// How the fill and stroke colors are combined with the existing pixels
+    /// when drawn. Defaults to `BlendMode::SourceOver`.
+    pub blend_mode: BlendMode,
+}
+
+impl EllipseNode {
+    /// Create a new `EllipseNode` with the specified center, radii and fill
+    /// color.
+    /// # Parameters
+    /// - `x`: The X coordinate of the center of the ellipse.
+    /// - `y`: The Y coordinate of the center of the ellipse.
+    /// - `radius_x`: The distance from the center to the left and right edges.
+    /// - `radius_y`: The distance from the center to the top and bottom edges.
+    /// - `fill_color`: The color to fill the ellipse with.
+    /// # Returns
+    /// A filled `EllipseNode` with no outline.
+    #[must_use]
+    pub const fn new(x: f64, y: f64, radius_x: f64, radius_y: f64, fill_color: Color) -> EllipseNode {
+        EllipseNode {
+            x, y, radius_x, radius_y, fill_color,
+            filled: true,
+            stroke_color: fill_color,
+            stroke_width: 0,
+            stroke_alignment: StrokeAlignment::Inside,
+            dash_pattern: None,
+            anti_aliased: false,
+            blend_mode: BlendMode::SourceOver,
+        }
+    }
+}
+
+impl DrawComponent for EllipseNode {
+    fn draw(&self, texture: &mut Texture) {
+        let (inside, outside) = self.stroke_alignment.split(self.stroke_width);
+        let outer_radii = (self.radius_x + cast::f64(outside), self.radius_y + cast::f64(outside));
+        let inner_radii = (self.radius_x - cast::f64(inside), self.radius_y - cast::f64(inside));
+        // dashes are measured along the middle of the stroke
+        let middle_radii = (f64::midpoint(inner_radii.0, outer_radii.0), f64::midpoint(inner_radii.1, outer_radii.1));
+        let arc_lengths = self.dash_pattern.as_ref().map(|_| arc_lengths(middle_radii));
+        // anti-aliased edges partly cover pixels just outside the ellipse
+        let margin = if self.anti_aliased { 1.0f64 } else { 0.0f64 };
+        // how far a pixel must be from an edge to be certain which side of
+        // it the whole pixel is on
+        let solid = if self.anti_aliased { 1.5f64 } else { 1.0f64 };
+        let (span_x, span_y) = (outer_radii.0 + margin, outer_radii.1 + margin);
+        let width = texture.get_width();
+        // only the rows on the texture are visited, and only the part of
+        // each row which is both on the texture and inside the ellipse
+        for y in pixel_span(self.y - span_y, self.y + span_y, texture.get_height()) {
+            let dy = cast::f64(y) - self.y;
+            let half_width = span_x * (dy / span_y).mul_add(-(dy / span_y), 1.0f64).max(0.0).sqrt();
+            let columns = pixel_span(self.x - half_width, self.x + half_width, width);
+            // the pixels on this row inside an ellipse grown by `grow`, or
+            // none if it does not reach the row
+            let row_span = |(radius_x, radius_y): (f64, f64), grow: f64| {
+                let (grown_x, grown_y) = (radius_x + grow, radius_y + grow);
+                if grown_x < 0.0f64 || grown_y < 0.0f64 {
+                    return 0..0;
+                }
+                let half = grown_x * (dy / grown_y).mul_add(-(dy / grown_y), 1.0f64).sqrt();
+                pixel_span(self.x - half, self.x + half, width)
+            };
+            // pixels near an edge are worked out one by one
+            let edge = |target: &mut Texture, x: usize| {
+                let dx = cast::f64(x) - self.x;
+                let outer = coverage(dx, dy, outer_radii, self.anti_aliased);
+                if outer <= 0.0f64 {
+                    return;
+                }
+                // the ring between the inner and outer edges is stroke
+                let inner = coverage(dx, dy, inner_radii, self.anti_aliased);
+                let dashed_out = self.dash_pattern.as_ref().zip(arc_lengths.as_deref()).is_some_and(|(pattern, lengths)| {
+                    let angle = (dy / middle_radii.1).atan2(dx / middle_radii.0).rem_euclid(TAU);
+                    !pattern.is_dash(arc_length(lengths, angle))
+                });
+                let fill = if self.filled { inner } else { 0.0f64 };
+                let stroke = if dashed_out { 0.0f64 } else { outer - inner };
+                if let Some(color) = mix(self.fill_color, fill, self.stroke_color, stroke) {
+                    target.draw_pixel(x, y, color, self.blend_mode).unwrap_or(());
+                }
+            };
+            // while everything away from the edges is drawn in whole spans:
+            // the fill well inside the inner edge, and the stroke on either
+            // side of it, unless it is dashed
+            let fill = if self.filled { row_span(inner_radii, -solid) } else { 0..0 };
+            let ring = if self.dash_pattern.is_none() { row_span(outer_radii, -solid) } else { 0..0 };
+            let hole = row_span(inner_radii, solid);
+            let (left_ring, right_ring) = if hole.is_empty() || ring.is_empty() { (ring, 0..0) } else { (ring.start..hole.start, hole.end..ring.end) };
+            let mut x = columns.start;
+            for (span, color) in [(left_ring, self.stroke_color), (fill, self.fill_color), (right_ring, self.stroke_color)] {
+                if span.is_empty() {
+                    continue;
+                }
+                let start = span.start.clamp(x, columns.end);
+                let end = span.end.clamp(start, columns.end);
+                for edge_x in x..start {
+                    edge(texture, edge_x);
+                }
+                texture.fill_span(start, y, end.saturating_sub(start), color, self.blend_mode);
+                x = end;
+            }
+            for edge_x in x..columns.end {
+                edge(texture, edge_x);
+            }
+        }
+    }
+}
+
+/// Gets the pixels from `start` to `end`, including both, which are within a
+/// texture `size` pixels wide or high.
+fn pixel_span(start: f64, end: f64, size: usize) -> Range<usize> {
+    let limit = cast::f64(size);
+    cast::usize(start.ceil().clamp(0.0, limit)).unwrap_or(0)..cast::usize((end.floor() + 1.0).clamp(0.0, limit)).unwrap_or(0)
+}
+
+/// Gets how much of the pixel `(dx, dy)` from the center of an ellipse is
+/// covered by it, from 0.0 to 1.0. Aliased ellipses cover a pixel fully if its
+/// center is inside them, and not at all otherwise.
+fn coverage(dx: f64, dy: f64, (radius_x, radius_y): (f64, f64), anti_aliased: bool) -> f64 {
+    if radius_x.is_nan() || radius_y.is_nan() || radius_x < 0.0f64 || radius_y < 0.0f64 {
+        return 0.0;
+    }
+    // an estimate of the distance from the center of the pixel to the edge,
+    // which is exact for circles: the value of the ellipse's equation at the
+    // pixel, divided by how quickly it changes there
+    let scaled = (dx / radius_x).hypot(dy / radius_y);
+    let gradient = (dx / radius_x.powi(2)).hypot(dy / radius_y.powi(2));
+    let distance = if gradient > 0.0f64 { scaled * (scaled - 1.0f64) / gradient } else { -radius_x.min(radius_y) };
+    if !anti_aliased {
+        return if distance <= 0.0 { 1.0 } else { 0.0 };
+    }
+    // an ellipse smaller than a pixel cannot cover more of it than its area
+    (0.5 - distance).clamp(0.0, 1.0).min(PI * radius_x * radius_y)
+}
+
+/// Measures the distance around an ellipse, starting from its right edge.
+/// # Returns
+/// The distance to each of `ARC_STEPS + 1` evenly spaced angles, from 0 to a
+/// full turn.
+#[expect(clippy::single_call_fn, reason = "kept separate from EllipseNode::draw for readability")]
+fn arc_lengths((radius_x, radius_y): (f64, f64)) -> Vec<f64> {
+    let mut lengths = Vec::with_capacity(ARC_STEPS.saturating_add(1));
+    let mut total = 0.0f64;
+    let mut previous = (radius_x, 0.0f64);
+    lengths.push(total);
+    for step in 1..=ARC_STEPS {
+        let angle = cast::f64(step) * TAU / cast::f64(ARC_STEPS);
+        let point = (radius_x * angle.cos(), radius_y * angle.sin());
+        total += (point.0 - previous.0).hypot(point.1 - previous.1);
+        lengths.push(total);
+        previous = point;
+    }
+    lengths
+}
+
+/// Gets the distance around an ellipse to an angle, from 0 to a full turn,
+/// using the table made by `arc_lengths`.
+#[expect(clippy::single_call_fn, reason = "kept separate from EllipseNode::draw for readability")]
+fn arc_length(lengths: &[f64], angle: f64) -> f64 {
+    let position = angle / TAU * cast::f64(ARC_STEPS);
+    let step = position.floor();
+    let index = cast::usize(step).unwrap_or(0);
+    let start = lengths.get(index).copied().unwrap_or(0.0f64);
+    let end = lengths.get(index.saturating_add(1)).copied().unwrap_or(start);
+    (end - start).mul_add(position - step, start)
+}
+
+/// Combines the fill and stroke colors drawn on one pixel, by how much of the
+/// pixel each covers.
+/// # Returns
+/// The color to draw, with the coverage included in its alpha channel, or
+/// `None` if neither covers the pixel.
+#[expect(clippy::single_call_fn, reason = "kept separate from EllipseNode::draw for readability")]
+fn mix(fill: Color, fill_coverage: f64, stroke: Color, stroke_coverage: f64) -> Option<Color> {
+    let coverage = fill_coverage + stroke_coverage;
+    if coverage <= 0.0f64 {
+        return None;
+    }
+    let fill_weight = fill_coverage * f64::from(fill.a);
+    let stroke_weight = stroke_coverage * f64::from(stroke.a);
+    let weight = fill_weight + stroke_weight;
+    // fully transparent colors still matter for blend modes such as `Source`
+    let (fill_share, stroke_share) = if weight > 0.0f64 {
+        (fill_weight / weight, stroke_weight / weight)
+    } else {
+        (fill_coverage / coverage, stroke_coverage / coverage)
+    };
+    let channel = |fill_channel: u8, stroke_channel: u8| {
+        channel_from_f64(f64::from(fill_channel).mul_add(fill_share, f64::from(stroke_channel) * stroke_share))
+    };
+    Some(Color {
+        r: channel(fill.r, stroke.r),
+        g: channel(fill.g, stroke.g),
+        b: channel(fill.b, stroke.b),
+        a: channel_from_f64(weight),
+    })
+}
+
+impl FillColorComponent for EllipseNode {
+    fn get_fill_color(&self) -> &Color {
+        &self.fill_color
+    }
+
+    fn set_fill_color(&mut self, color: Color) {
+        self.fill_color = color;
+    }
+}
+
+impl StrokeColorComponent for EllipseNode {
+    fn get_stroke_color(&self) -> &Color {
+        &self.stroke_color
+    }
+
+    fn set_stroke_color(&mut self, color: Color) {
+        self.stroke_color = color;
+    }
+}
+
+impl StrokeWidthComponent for EllipseNode {
+    fn get_stroke_width(&self) -> usize {
+        self.stroke_width
+    }
+
+    fn set_stroke_width(&mut self, width: usize) {
+        self.stroke_width = width;
+    }
+}
+
+impl PositionComponent for EllipseNode {
+    fn get_x(&self) -> f64 {
+        self.x - self.radius_x
+    }
+
+    fn get_y(&self) -> f64 {
+        self.y - self.radius_y
+    }
+
+    fn set_x(&mut self, x: f64) {
+        self.x = x + self.radius_x;
+    }
+
+    fn set_y(&mut self, y: f64) {
+        self.y = y + self.radius_y;
+    }
+}
+
+impl SizeComponent for EllipseNode {
+    fn get_width(&self) -> f64 {
+        self.radius_x * 2.0
+    }
+
+    fn get_height(&self) -> f64 {
+        self.radius_y * 2.0
+    }
+
+    // the top-left corner stays where it is, as with the other sized nodes
+    fn set_width(&mut self, width: f64) {
+        self.x += width / 2.0f64 - self.radius_x;
+        self.radius_x = width / 2.0f64;
+    }
+
+    fn set_height(&mut self, height: f64) {
+        self.y += height / 2.0f64 - self.radius_y;
+        self.radius_y = height / 2.0f64;
+    }
+}
+
+impl BlendModeComponent for EllipseNode {
+    fn get_blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+}
@@ -0,0 +1,248 @@
+//! The `Font` trait, which lets `TextNode` draw text with any kind of font,
+//! and `BuiltinFont`, a simple 8x8 pixel font which is always available.
+
+use crate::{BlendMode, Color, Texture};
+
+/// A font which can draw individual characters onto a texture.
+/// `TextNode` uses this trait to lay out and draw text, so it works with any
+/// font: implement it to add your own.
+pub trait Font {
+
+    /// Get the distance between the tops of two lines of text.
+    /// # Returns
+    /// The line height in pixels.
+    fn get_line_height(&self) -> f64;
+
+    /// Get how far to move right after drawing a character, before drawing
+    /// the next one.
+    /// # Returns
+    /// The advance width of `character` in pixels.
+    fn get_advance(&self, character: char) -> f64;
+
+    /// Get an extra adjustment to the space between two specific characters,
+    /// such as moving "V" closer to "A". This is added to the advance of
+    /// `left`. Fonts without kerning do not need to implement this.
+    /// # Returns
+    /// The adjustment in pixels: negative values move the characters closer.
+    fn get_kerning(&self, _left: char, _right: char) -> f64 {
+        0.0
+    }
+
+    /// Draw a single character onto a texture.
+    /// (`x`, `y`) is the top-left corner of the character's line: characters
+    /// drawn on the same line share the same `y`, even if some reach lower
+    /// than others.
+    fn draw_char(&self, texture: &mut Texture, character: char, x: f64, y: f64, color: Color, blend_mode: BlendMode);
+}
+
+/// A simple 8x8 pixel font covering the printable ASCII characters, built
+/// into Pixl so text can be drawn without loading any assets.
+/// Characters outside of printable ASCII are drawn as `?`.
+/// # Example
+/// ```rust
+/// let score = TextNode::new("Score: 100", 2.0, 2.0, Color::WHITE);
+/// texture.add(&score); // uses BuiltinFont::DEFAULT
+///
+/// let title_font = BuiltinFont::new(3); // 24x24 characters
+/// let mut title = TextNode::new("GAME OVER", 10.0, 20.0, Color::RED);
+/// title.font = &title_font;
+/// texture.add(&title);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+#[expect(clippy::module_name_repetitions, reason = "the name 'Builtin' alone would be unclear")]
+pub struct BuiltinFont {
+    /// How many texture pixels wide and tall each pixel of the font is drawn.
+    /// A scale of 1 draws 8x8 characters, 2 draws 16x16 characters, and so
+    /// on.
+    pub scale: usize,
+}
+
+impl BuiltinFont {
+    /// The built-in font at its original 8x8 size.
+    pub const DEFAULT: BuiltinFont = BuiltinFont::new(1);
+
+    /// The width and height of every character at a scale of 1, in pixels.
+    pub const GLYPH_SIZE: usize = 8;
+
+    /// Create a new `BuiltinFont` drawn at a whole-number scale.
+    /// # Parameters
+    /// - `scale`: How many texture pixels wide and tall each pixel of the font
+    ///   is drawn.
+    /// # Returns
+    /// A new `BuiltinFont` with the specified scale.
+    #[must_use]
+    pub const fn new(scale: usize) -> BuiltinFont {
+        BuiltinFont { scale }
+    }
+
+    /// Get the rows of pixels making up a character, from top to bottom.
+    /// Bit 0 of each row is its leftmost pixel.
+    /// # Returns
+    /// The 8 rows of the character's glyph, or of `?` if the font does not
+    /// contain the character.
+    #[must_use]
+    pub fn get_glyph(character: char) -> [u8; 8] {
+        let index = u32::from(character).checked_sub(u32::from(' '))
+            .and_then(|index| GLYPHS.get(cast::usize(index)))
+            .or_else(|| GLYPHS.get(FALLBACK_GLYPH));
+        index.copied().unwrap_or_default()
+    }
+}
+
+impl Default for BuiltinFont {
+    fn default() -> BuiltinFont {
+        BuiltinFont::DEFAULT
+    }
+}
+
+impl Font for BuiltinFont {
+    fn get_line_height(&self) -> f64 {
+        cast::f64(BuiltinFont::GLYPH_SIZE.saturating_mul(self.scale))
+    }
+
+    fn get_advance(&self, _character: char) -> f64 {
+        cast::f64(BuiltinFont::GLYPH_SIZE.saturating_mul(self.scale))
+    }
+
+    fn draw_char(&self, texture: &mut Texture, character: char, x: f64, y: f64, color: Color, blend_mode: BlendMode) {
+        let glyph = BuiltinFont::get_glyph(character);
+        let size = (BuiltinFont::GLYPH_SIZE, BuiltinFont::GLYPH_SIZE);
+        draw_bitmap_glyph(texture, (x, y), size, self.scale, color, blend_mode, |column, row| {
+            glyph.get(row).is_some_and(|bits| (bits >> column) & 1 == 1)
+        });
+    }
+}
+
+/// Draws a one-bit glyph, such as a character of a pixel font, with its
+/// top-left corner at `origin`. `is_set(column, row)` says whether each pixel
+/// of the `size` (width, height) glyph is drawn, and every drawn pixel becomes
+/// a `scale` by `scale` block of `color`. The glyph is snapped to whole
+/// pixels to keep it crisp.
+#[expect(clippy::pub_with_shorthand, reason = "shared by the pixel fonts, but not part of the public API")]
+pub(crate) fn draw_bitmap_glyph<F>(texture: &mut Texture, origin: (f64, f64), size: (usize, usize), scale: usize, color: Color, blend_mode: BlendMode, is_set: F)
+where F: Fn(usize, usize) -> bool {
+    let left = origin.0.round();
+    let top = origin.1.round();
+    let block_size = cast::f64(scale);
+    for row in 0..size.1 {
+        for column in (0..size.0).filter(|&column| is_set(column, row)) {
+            let block_x = cast::f64(column).mul_add(block_size, left);
+            let block_y = cast::f64(row).mul_add(block_size, top);
+            for offset_y in 0..scale {
+                for offset_x in 0..scale {
+                    let (Ok(pixel_x), Ok(pixel_y)) = (
+                        cast::usize(block_x + cast::f64(offset_x)),
+                        cast::usize(block_y + cast::f64(offset_y)),
+                    ) else {
+                        continue;
+                    };
+                    texture.draw_pixel(pixel_x, pixel_y, color, blend_mode).unwrap_or(());
+                }
+            }
+        }
+    }
+}
+
+/// The index in `GLYPHS` of `?`, drawn for characters the font lacks.
+const FALLBACK_GLYPH: usize = 31;
+
+/// The glyphs of every printable ASCII character, from `' '` to `'~'`.
+/// Based on the public domain font8x8 by Daniel Hepper.
+const GLYPHS: [[u8; 8]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x18, 0x3C, 0x3C, 0x18, 0x18, 0x00, 0x18, 0x00], // '!'
+    [0x36, 0x36, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // '"'
+    [0x36, 0x36, 0x7F, 0x36, 0x7F, 0x36, 0x36, 0x00], // '#'
+    [0x0C, 0x3E, 0x03, 0x1E, 0x30, 0x1F, 0x0C, 0x00], // '$'
+    [0x00, 0x63, 0x33, 0x18, 0x0C, 0x66, 0x63, 0x00], // '%'
+    [0x1C, 0x36, 0x1C, 0x6E, 0x3B, 0x33, 0x6E, 0x00], // '&'
+    [0x06, 0x06, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00], // '\''
+    [0x18, 0x0C, 0x06, 0x06, 0x06, 0x0C, 0x18, 0x00], // '('
+    [0x06, 0x0C, 0x18, 0x18, 0x18, 0x0C, 0x06, 0x00], // ')'
+    [0x00, 0x66, 0x3C, 0xFF, 0x3C, 0x66, 0x00, 0x00], // '*'
+    [0x00, 0x0C, 0x0C, 0x3F, 0x0C, 0x0C, 0x00, 0x00], // '+'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C, 0x06], // ','
+    [0x00, 0x00, 0x00, 0x3F, 0x00, 0x00, 0x00, 0x00], // '-'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C, 0x00], // '.'
+    [0x60, 0x30, 0x18, 0x0C, 0x06, 0x03, 0x01, 0x00], // '/'
+    [0x3E, 0x63, 0x73, 0x7B, 0x6F, 0x67, 0x3E, 0x00], // '0'
+    [0x0C, 0x0E, 0x0C, 0x0C, 0x0C, 0x0C, 0x3F, 0x00], // '1'
+    [0x1E, 0x33, 0x30, 0x1C, 0x06, 0x33, 0x3F, 0x00], // '2'
+    [0x1E, 0x33, 0x30, 0x1C, 0x30, 0x33, 0x1E, 0x00], // '3'
+    [0x38, 0x3C, 0x36, 0x33, 0x7F, 0x30, 0x78, 0x00], // '4'
+    [0x3F, 0x03, 0x1F, 0x30, 0x30, 0x33, 0x1E, 0x00], // '5'
+    [0x1C, 0x06, 0x03, 0x1F, 0x33, 0x33, 0x1E, 0x00], // '6'
+    [0x3F, 0x33, 0x30, 0x18, 0x0C, 0x0C, 0x0C, 0x00], // '7'
+    [0x1E, 0x33, 0x33, 0x1E, 0x33, 0x33, 0x1E, 0x00], // '8'
+    [0x1E, 0x33, 0x33, 0x3E, 0x30, 0x18, 0x0E, 0x00], // '9'
+    [0x00, 0x0C, 0x0C, 0x00, 0x00, 0x0C, 0x0C, 0x00], // ':'
+    [0x00, 0x0C, 0x0C, 0x00, 0x00, 0x0C, 0x0C, 0x06], // ';'
+    [0x18, 0x0C, 0x06, 0x03, 0x06, 0x0C, 0x18, 0x00], // '<'
+    [0x00, 0x00, 0x3F, 0x00, 0x00, 0x3F, 0x00, 0x00], // '='
+    [0x06, 0x0C, 0x18, 0x30, 0x18, 0x0C, 0x06, 0x00], // '>'
+    [0x1E, 0x33, 0x30, 0x18, 0x0C, 0x00, 0x0C, 0x00], // '?'
+    [0x3E, 0x63, 0x7B, 0x7B, 0x7B, 0x03, 0x1E, 0x00], // '@'
+    [0x0C, 0x1E, 0x33, 0x33, 0x3F, 0x33, 0x33, 0x00], // 'A'
+    [0x3F, 0x66, 0x66, 0x3E, 0x66, 0x66, 0x3F, 0x00], // 'B'
+    [0x3C, 0x66, 0x03, 0x03, 0x03, 0x66, 0x3C, 0x00], // 'C'
+    [0x1F, 0x36, 0x66, 0x66, 0x66, 0x36, 0x1F, 0x00], // 'D'
+    [0x7F, 0x46, 0x16, 0x1E, 0x16, 0x46, 0x7F, 0x00], // 'E'
+    [0x7F, 0x46, 0x16, 0x1E, 0x16, 0x06, 0x0F, 0x00], // 'F'
+    [0x3C, 0x66, 0x03, 0x03, 0x73, 0x66, 0x7C, 0x00], // 'G'
+    [0x33, 0x33, 0x33, 0x3F, 0x33, 0x33, 0x33, 0x00], // 'H'
+    [0x1E, 0x0C, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // 'I'
+    [0x78, 0x30, 0x30, 0x30, 0x33, 0x33, 0x1E, 0x00], // 'J'
+    [0x67, 0x66, 0x36, 0x1E, 0x36, 0x66, 0x67, 0x00], // 'K'
+    [0x0F, 0x06, 0x06, 0x06, 0x46, 0x66, 0x7F, 0x00], // 'L'
+    [0x63, 0x77, 0x7F, 0x7F, 0x6B, 0x63, 0x63, 0x00], // 'M'
+    [0x63, 0x67, 0x6F, 0x7B, 0x73, 0x63, 0x63, 0x00], // 'N'
+    [0x1C, 0x36, 0x63, 0x63, 0x63, 0x36, 0x1C, 0x00], // 'O'
+    [0x3F, 0x66, 0x66, 0x3E, 0x06, 0x06, 0x0F, 0x00], // 'P'
+    [0x1E, 0x33, 0x33, 0x33, 0x3B, 0x1E, 0x38, 0x00], // 'Q'
+    [0x3F, 0x66, 0x66, 0x3E, 0x36, 0x66, 0x67, 0x00], // 'R'
+    [0x1E, 0x33, 0x07, 0x0E, 0x38, 0x33, 0x1E, 0x00], // 'S'
+    [0x3F, 0x2D, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // 'T'
+    [0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x3F, 0x00], // 'U'
+    [0x33, 0x33, 0x33, 0x33, 0x33, 0x1E, 0x0C, 0x00], // 'V'
+    [0x63, 0x63, 0x63, 0x6B, 0x7F, 0x77, 0x63, 0x00], // 'W'
+    [0x63, 0x63, 0x36, 0x1C, 0x1C, 0x36, 0x63, 0x00], // 'X'
+    [0x33, 0x33, 0x33, 0x1E, 0x0C, 0x0C, 0x1E, 0x00], // 'Y'
+    [0x7F, 0x63, 0x31, 0x18, 0x4C, 0x66, 0x7F, 0x00], // 'Z'
+    [0x1E, 0x06, 0x06, 0x06, 0x06, 0x06, 0x1E, 0x00], // '['
+    [0x03, 0x06, 0x0C, 0x18, 0x30, 0x60, 0x40, 0x00], // '\\'
+    [0x1E, 0x18, 0x18, 0x18, 0x18, 0x18, 0x1E, 0x00], // ']'
+    [0x08, 0x1C, 0x36, 0x63, 0x00, 0x00, 0x00, 0x00], // '^'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF], // '_'
+    [0x0C, 0x0C, 0x18, 0x00, 0x00, 0x00, 0x00, 0x00], // '`'
+    [0x00, 0x00, 0x1E, 0x30, 0x3E, 0x33, 0x6E, 0x00], // 'a'
+    [0x07, 0x06, 0x06, 0x3E, 0x66, 0x66, 0x3B, 0x00], // 'b'
+    [0x00, 0x00, 0x1E, 0x33, 0x03, 0x33, 0x1E, 0x00], // 'c'
+    [0x38, 0x30, 0x30, 0x3E, 0x33, 0x33, 0x6E, 0x00], // 'd'
+    [0x00, 0x00, 0x1E, 0x33, 0x3F, 0x03, 0x1E, 0x00], // 'e'
+    [0x1C, 0x36, 0x06, 0x0F, 0x06, 0x06, 0x0F, 0x00], // 'f'
+    [0x00, 0x00, 0x6E, 0x33, 0x33, 0x3E, 0x30, 0x1F], // 'g'
+    [0x07, 0x06, 0x36, 0x6E, 0x66, 0x66, 0x67, 0x00], // 'h'
+    [0x0C, 0x00, 0x0E, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // 'i'
+    [0x30, 0x00, 0x30, 0x30, 0x30, 0x33, 0x33, 0x1E], // 'j'
+    [0x07, 0x06, 0x66, 0x36, 0x1E, 0x36, 0x67, 0x00], // 'k'
+    [0x0E, 0x0C, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // 'l'
+    [0x00, 0x00, 0x33, 0x7F, 0x7F, 0x6B, 0x63, 0x00], // 'm'
+    [0x00, 0x00, 0x1F, 0x33, 0x33, 0x33, 0x33, 0x00], // 'n'
+    [0x00, 0x00, 0x1E, 0x33, 0x33, 0x33, 0x1E, 0x00], // 'o'
+    [0x00, 0x00, 0x3B, 0x66, 0x66, 0x3E, 0x06, 0x0F], // 'p'
+    [0x00, 0x00, 0x6E, 0x33, 0x33, 0x3E, 0x30, 0x78], // 'q'
+    [0x00, 0x00, 0x3B, 0x6E, 0x66, 0x06, 0x0F, 0x00], // 'r'
+    [0x00, 0x00, 0x3E, 0x03, 0x1E, 0x30, 0x1F, 0x00], // 's'
+    [0x08, 0x0C, 0x3E, 0x0C, 0x0C, 0x2C, 0x18, 0x00], // 't'
+    [0x00, 0x00, 0x33, 0x33, 0x33, 0x33, 0x6E, 0x00], // 'u'
+    [0x00, 0x00, 0x33, 0x33, 0x33, 0x1E, 0x0C, 0x00], // 'v'
+    [0x00, 0x00, 0x63, 0x6B, 0x7F, 0x7F, 0x36, 0x00], // 'w'
+    [0x00, 0x00, 0x63, 0x36, 0x1C, 0x36, 0x63, 0x00], // 'x'
+    [0x00, 0x00, 0x33, 0x33, 0x33, 0x3E, 0x30, 0x1F], // 'y'
+    [0x00, 0x00, 0x3F, 0x19, 0x0C, 0x26, 0x3F, 0x00], // 'z'
+    [0x38, 0x0C, 0x0C, 0x07, 0x0C, 0x0C, 0x38, 0x00], // '{'
+    [0x18, 0x18, 0x18, 0x00, 0x18, 0x18, 0x18, 0x00], // '|'
+    [0x07, 0x0C, 0x0C, 0x38, 0x0C, 0x0C, 0x07, 0x00], // '}'
+    [0x6E, 0x3B, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // '~'
+];
@@ -0,0 +1,209 @@
+//! Text layout: measuring text, wrapping it to a maximum width and aligning
+//! its lines.
+//!
+//! `TextNode` lays out its text automatically, but `TextLayout` can also be
+//! used directly, for example to size a dialogue box around some text before
+//! drawing either of them.
+
+use core::mem;
+
+use crate::font::Font;
+
+/// How each line of text is positioned horizontally within its block.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TextAlign {
+    /// Lines start at the left edge of the block.
+    #[default]
+    Left,
+    /// Lines are centered within the block.
+    Center,
+    /// Lines end at the right edge of the block.
+    Right,
+}
+
+/// A single line of laid out text.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct TextLine {
+    /// The characters on the line.
+    pub text: String,
+    /// The distance from the left edge of the block to the start of the line.
+    pub x: f64,
+    /// The distance from the top edge of the block to the top of the line.
+    pub y: f64,
+    /// The width of the line in pixels.
+    pub width: f64,
+}
+
+/// Text split into lines and positioned, ready to be drawn or measured.
+/// Lines break at every `'\n'` and, if a maximum width is set, between words
+/// so that no line is wider than it. Words too long to fit on a line of their
+/// own are split between characters.
+/// # Example
+/// ```rust
+/// let layout = TextLayout::new(&font, "Welcome to the village!", Some(120.0), TextAlign::Center, 1.2);
+/// let (width, height) = layout.get_size();
+/// let panel = RectangleNode::new(10.0, 10.0, width + 8.0, height + 8.0, Color::BLACK);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+#[expect(clippy::module_name_repetitions, reason = "the name 'Layout' alone would be unclear")]
+pub struct TextLayout {
+    /// Every line, from top to bottom.
+    lines: Vec<TextLine>,
+    /// The width of the block the lines are aligned within.
+    width: f64,
+    /// The distance from the top of the first line to the bottom of the last.
+    height: f64,
+}
+
+impl TextLayout {
+    /// Lays out some text.
+    /// # Parameters
+    /// - `font`: The font the text will be drawn with.
+    /// - `text`: The text to lay out.
+    /// - `max_width`: The width to wrap lines at, or `None` to only break
+    ///   lines at `'\n'`.
+    /// - `align`: How each line is positioned within the block.
+    /// - `line_spacing`: The distance between the tops of lines, as a multiple
+    ///   of the font's line height. 1.0 is the font's normal spacing.
+    /// # Returns
+    /// The laid out text. Its block is `max_width` wide if set, or as wide as
+    /// its widest line otherwise.
+    #[must_use]
+    pub fn new(font: &dyn Font, text: &str, max_width: Option<f64>, align: TextAlign, line_spacing: f64) -> TextLayout {
+        let mut lines: Vec<TextLine> = text.split('\n')
+            .flat_map(|paragraph| wrap(font, paragraph, max_width))
+            .map(|line| TextLine { width: measure_line(font, &line), text: line, x: 0.0, y: 0.0 })
+            .collect();
+        let widest = lines.iter().map(|line| line.width).fold(0.0f64, f64::max);
+        let width = max_width.unwrap_or(widest);
+        let line_step = font.get_line_height() * line_spacing;
+        let mut y = 0.0f64;
+        for line in &mut lines {
+            line.x = match align {
+                TextAlign::Left => 0.0f64,
+                TextAlign::Center => (width - line.width) / 2.0f64,
+                TextAlign::Right => width - line.width,
+            };
+            line.y = y;
+            y += line_step;
+        }
+        let height = lines.last().map_or(0.0f64, |last| last.y + font.get_line_height());
+        TextLayout { lines, width, height }
+    }
+
+    /// Measures some text without wrapping it.
+    /// # Parameters
+    /// - `font`: The font the text will be drawn with.
+    /// - `text`: The text to measure. A `'\n'` starts a new line.
+    /// # Returns
+    /// The (width, height) of the text in pixels.
+    /// # Example
+    /// ```rust
+    /// let (width, _) = TextLayout::measure(&BuiltinFont::DEFAULT, "PAUSED");
+    /// let title = TextNode::new("PAUSED", (screen_width - width) / 2.0, 20.0, Color::WHITE);
+    /// ```
+    #[must_use]
+    pub fn measure(font: &dyn Font, text: &str) -> (f64, f64) {
+        TextLayout::new(font, text, None, TextAlign::Left, 1.0).get_size()
+    }
+
+    /// Gets every line of the laid out text.
+    /// # Returns
+    /// The lines, from top to bottom.
+    #[must_use]
+    pub fn get_lines(&self) -> &[TextLine] {
+        &self.lines
+    }
+
+    /// Gets the size of the block containing the text.
+    /// # Returns
+    /// The (width, height) of the block in pixels.
+    #[must_use]
+    pub const fn get_size(&self) -> (f64, f64) {
+        (self.width, self.height)
+    }
+
+    /// Gets the smallest box containing every line, which may be narrower
+    /// than the block if a maximum width was set.
+    /// # Returns
+    /// The (x, y, width, height) of the box, relative to the top-left corner
+    /// of the block.
+    #[must_use]
+    pub fn get_bounds(&self) -> (f64, f64, f64, f64) {
+        let left = self.lines.iter().map(|line| line.x).fold(f64::INFINITY, f64::min);
+        let right = self.lines.iter().map(|line| line.x + line.width).fold(f64::NEG_INFINITY, f64::max);
+        if left > right {
+            return (0.0, 0.0, 0.0, 0.0);
+        }
+        (left, 0.0, right - left, self.height)
+    }
+}
+
+/// Measures a single line of text, including kerning.
+fn measure_line(font: &dyn Font, line: &str) -> f64 {
+    let mut width = 0.0f64;
+    let mut previous = None;
+    for character in line.chars() {
+        if let Some(left) = previous {
+            width += font.get_kerning(left, character);
+        }
+        width += font.get_advance(character);
+        previous = Some(character);
+    }
+    width
+}
+
+/// Splits a paragraph into lines no wider than `max_width`, breaking at
+/// spaces where possible. Spaces at the start of the paragraph are kept, but
+/// spaces where a line wraps are dropped.
+#[expect(clippy::single_call_fn, reason = "kept separate from TextLayout::new for readability")]
+fn wrap(font: &dyn Font, paragraph: &str, max_width: Option<f64>) -> Vec<String> {
+    let Some(width) = max_width else {
+        return vec![paragraph.to_owned()];
+    };
+    let fits = |text: &str| measure_line(font, text) <= width;
+    // spaces before the first word are kept as indentation
+    let content = paragraph.trim_start();
+    let mut line = paragraph.get(..paragraph.len().saturating_sub(content.len())).unwrap_or_default().to_owned();
+    let mut lines = Vec::new();
+    let mut spaces = "";
+    let mut rest = content;
+    while !rest.is_empty() {
+        let (word, after_word) = rest.split_at(rest.find(char::is_whitespace).unwrap_or(rest.len()));
+        let next_word = after_word.trim_start();
+        let candidate = format!("{line}{spaces}{word}");
+        if fits(&candidate) {
+            line = candidate;
+        } else {
+            if !line.trim_start().is_empty() {
+                lines.push(mem::take(&mut line));
+            }
+            line = split_word(word, &fits, &mut lines);
+        }
+        spaces = after_word.get(..after_word.len().saturating_sub(next_word.len())).unwrap_or_default();
+        rest = next_word;
+    }
+    lines.push(line);
+    lines
+}
+
+/// Splits a word too long for a line of its own between characters, adding
+/// every full line to `lines`.
+/// # Returns
+/// The end of the word, which starts the next line.
+#[expect(clippy::single_call_fn, reason = "kept separate from wrap for readability")]
+fn split_word<F: Fn(&str) -> bool>(word: &str, fits: &F, lines: &mut Vec<String>) -> String {
+    let mut line = String::new();
+    for character in word.chars() {
+        line.push(character);
+        // a single character always goes on the line, even if it is too wide
+        if !fits(&line) && line.chars().count() > 1 {
+            line.pop();
+            lines.push(mem::replace(&mut line, character.to_string()));
+        }
+    }
+    line
+}
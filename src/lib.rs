@@ -554,7 +554,7 @@
     clippy::else_if_without_else,
     clippy::empty_drop,
     clippy::empty_enum_variants_with_brackets,
-    clippy::empty_enum,
+    clippy::empty_enums,
     clippy::empty_structs_with_brackets,
     clippy::enum_glob_use,
     clippy::equatable_if_let,
@@ -759,7 +759,7 @@
     clippy::try_err,
     clippy::tuple_array_conversions,
     clippy::type_repetition_in_bounds,
-    clippy::unchecked_duration_subtraction,
+    clippy::unchecked_time_subtraction,
     clippy::undocumented_unsafe_blocks,
     clippy::unicode_not_nfc,
     clippy::unimplemented,
@@ -823,17 +823,51 @@
 )]
 
 
+extern crate alloc;
+
 pub mod window;
 pub mod texture;
 pub mod color;
+pub mod blend;
 pub mod component;
 pub mod pixel;
 pub mod rectangle;
 pub mod line;
+pub mod polyline;
+pub mod polygon;
+pub mod triangle;
+pub mod path;
 pub mod circle;
+pub mod ellipse;
+pub mod sprite;
+pub mod spritesheet;
+pub mod animated_sprite;
+pub mod font;
+pub mod text;
+pub mod layout;
+pub mod bmfont;
+pub mod psf;
+pub mod truetype;
+pub mod rect;
+pub mod transform;
+pub mod stroke;
+#[cfg(feature = "png")]
+pub mod png;
+pub mod bmp;
+pub mod qoi;
+pub mod netpbm;
+pub mod tga;
+pub mod svg;
+#[cfg(feature = "aseprite")]
+pub mod aseprite;
+
+mod reader;
+mod raster;
+mod cff;
 
 
 pub use window::Window;
 pub use texture::Texture;
 pub use color::Color;
+pub use blend::BlendMode;
 
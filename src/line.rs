@@ -1,30 +1,59 @@
 //! `LineNode` struct - represents a node for a rectangle shape in a
 //! texture.
 
-use crate::{Color, Texture, component::DrawComponent};
+use crate::{BlendMode, Color, Texture, component::{BlendModeComponent, DrawComponent, StrokeColorComponent, StrokeWidthComponent}, polygon::FillRule, raster, stroke::{self, DashPattern, LineCap, LineJoin}};
 
 /// A node representing a line shape in a texture.
+/// Integer coordinates are the centers of pixels. By default the line is
+/// aliased, with its endpoints rounded to the nearest pixel, but setting
+/// `anti_aliased` draws a smooth line through the exact endpoints instead.
+/// Lines wider than one pixel are drawn as filled shapes, with `line_cap`
+/// deciding the shape of their ends.
+/// Lines can start and end anywhere, including off the texture, as they are
+/// clipped to it before being drawn.
 /// Implemented components:
 /// - `DrawComponent`
 /// - `StrokeColorComponent`
 /// - `StrokeWidthComponent`
+/// - `BlendModeComponent`
 #[non_exhaustive]
 #[expect(clippy::module_name_repetitions, reason = "struct should be called 'LineNode' as it is standard.")]
 pub struct LineNode {
     /// The x-coordinate of the start point of the line.
-    pub x1: usize,
+    pub x1: f64,
 
     /// The y-coordinate of the start point of the line.
-    pub y1: usize,
+    pub y1: f64,
 
     /// The x-coordinate of the end point of the line.
-    pub x2: usize,
+    pub x2: f64,
 
     /// The y-coordinate of the end point of the line.
-    pub y2: usize,
+    pub y2: f64,
 
-    /// The color of the line.
+    /// The color of the line. Its alpha channel is blended over the existing
+    /// pixels when drawn.
     pub color: Color,
+
+    /// Whether the line is drawn with smooth, anti-aliased edges, by blending
+    /// each pixel by how much of it the line covers. Defaults to false.
+    pub anti_aliased: bool,
+
+    /// The width of the line, in pixels. Defaults to 1, and a width of 0
+    /// draws nothing.
+    pub stroke_width: usize,
+
+    /// The shape drawn at the ends of the line. Lines one pixel wide always
+    /// end exactly at their endpoints. Defaults to `LineCap::Butt`.
+    pub line_cap: LineCap,
+
+    /// The pattern of dashes the line is drawn with, or `None` to draw it
+    /// solid. Every dash is given the line's caps. Defaults to `None`.
+    pub dash_pattern: Option<DashPattern>,
+
+    /// How the line's color is combined with the existing pixels when drawn.
+    /// Defaults to `BlendMode::SourceOver`.
+    pub blend_mode: BlendMode,
 }
 
 impl LineNode {
@@ -39,50 +68,167 @@ impl LineNode {
     /// A new `LineNode` instance with the specified properties.
     #[must_use]
     pub const fn new(
-        x1: usize, y1: usize, x2: usize, y2: usize, color: Color
+        x1: f64, y1: f64, x2: f64, y2: f64, color: Color
     ) -> LineNode {
-        LineNode { x1, y1, x2, y2, color }
+        LineNode {
+            x1, y1, x2, y2, color,
+            anti_aliased: false,
+            stroke_width: 1,
+            line_cap: LineCap::Butt,
+            dash_pattern: None,
+            blend_mode: BlendMode::SourceOver,
+        }
     }
 }
 
+/// Draws a line one pixel wide through `points`, leaving out the first pixel
+/// of every aliased segment after the first, so that corners are not drawn
+/// twice.
+/// # Parameters
+/// - `texture`: The texture to draw the line on.
+/// - `points`: The (x, y) coordinates of every point the line passes through.
+/// - `color`: The color of the line.
+/// - `blend_mode`: How `color` is combined with the existing pixels.
+/// - `anti_aliased`: Whether to draw the line with Wu's algorithm instead of
+///   Bresenham's.
+#[expect(clippy::pub_with_shorthand, reason = "shared with the other stroked nodes, but not part of the public API")]
+pub(crate) fn draw_thin(texture: &mut Texture, points: &[(f64, f64)], color: Color, blend_mode: BlendMode, anti_aliased: bool) {
+    let round = |value: f64| cast::isize(value.round());
+    // one pixel past every edge is kept, so that the pixels at the edges
+    // are still drawn with the right coverage
+    let bounds = (-1.0f64, -1.0f64, cast::f64(texture.get_width()), cast::f64(texture.get_height()));
+    for (index, (from, to)) in points.iter().copied().zip(points.iter().copied().skip(1)).enumerate() {
+        let Some((start, end)) = clip(from, to, bounds) else {
+            continue;
+        };
+        let (clipped_from, clipped_to) = (point_at(from, to, start), point_at(from, to, end));
+        if anti_aliased {
+            draw_line_anti_aliased(texture, clipped_from, clipped_to, color, blend_mode);
+            continue;
+        }
+        let ends = (round(clipped_from.0), round(clipped_from.1), round(clipped_to.0), round(clipped_to.1));
+        if let (Ok(x1), Ok(y1), Ok(x2), Ok(y2)) = ends {
+            // the start pixel is only the corner with the previous segment if
+            // it was not clipped off
+            draw_line(texture, (x1, y1), (x2, y2), color, blend_mode, index > 0 && start <= 0.0f64);
+        }
+    }
+}
+
+/// Draws a line one pixel wide through `points`, split into dashes if it has
+/// a pattern. Only the parts of the line near the texture are split, so the
+/// limited number of dashes is not used up by those far off its edges.
+/// # Parameters
+/// - `texture`: The texture to draw the line on.
+/// - `points`: The (x, y) coordinates of every point the line passes through.
+/// - `closed`: Whether the line joins back up to its first point.
+/// - `pattern`: The pattern of dashes, or `None` for a solid line.
+/// - `color`: The color of the line.
+/// - `blend_mode`: How `color` is combined with the existing pixels.
+/// - `anti_aliased`: Whether to draw the line with Wu's algorithm instead of
+///   Bresenham's.
+#[expect(clippy::pub_with_shorthand, reason = "shared with the other stroked nodes, but not part of the public API")]
+pub(crate) fn draw_thin_dashed(texture: &mut Texture, points: &[(f64, f64)], closed: bool, pattern: Option<&DashPattern>, color: Color, blend_mode: BlendMode, anti_aliased: bool) {
+    let Some(dashes) = pattern else {
+        for line in stroke::dashes(points, closed, None) {
+            draw_thin(texture, &line, color, blend_mode, anti_aliased);
+        }
+        return;
+    };
+    let closing = closed.then(|| points.first().copied()).flatten();
+    let path: Vec<(f64, f64)> = points.iter().copied().chain(closing).collect();
+    let bounds = (-1.0f64, -1.0f64, cast::f64(texture.get_width()), cast::f64(texture.get_height()));
+    // the visible parts of the line, each with how far along the line it
+    // starts, so that its dashes stay where they were before clipping
+    let mut visible: Vec<(f64, Vec<(f64, f64)>)> = Vec::new();
+    let mut travelled = 0.0f64;
+    let mut connected = false;
+    for (from, to) in path.iter().copied().zip(path.iter().copied().skip(1)) {
+        let length = (to.0 - from.0).hypot(to.1 - from.1);
+        let clipped = clip(from, to, bounds);
+        if let Some((start, end)) = clipped {
+            match visible.last_mut() {
+                Some(&mut (_, ref mut run)) if connected && start <= 0.0f64 => run.push(point_at(from, to, end)),
+                _ => visible.push((start.mul_add(length, travelled), vec![point_at(from, to, start), point_at(from, to, end)])),
+            }
+        }
+        connected = clipped.is_some_and(|(_, end)| end >= 1.0f64);
+        travelled += length;
+    }
+    for (offset, run) in visible {
+        let shifted = DashPattern { offset: dashes.offset + offset, ..dashes.clone() };
+        for dash in stroke::dashes(&run, false, Some(&shifted)) {
+            draw_thin(texture, &dash, color, blend_mode, anti_aliased);
+        }
+    }
+}
+
+/// Clips a line to a box using the Liang-Barsky algorithm.
+/// # Parameters
+/// - `from`: The start point of the line.
+/// - `to`: The end point of the line.
+/// - `(left, top, right, bottom)`: The edges of the box.
+/// # Returns
+/// How far along the line, from 0.0 at `from` to 1.0 at `to`, the part of it
+/// inside the box starts and ends, or `None` if none of it is inside.
+fn clip(from: (f64, f64), to: (f64, f64), (left, top, right, bottom): (f64, f64, f64, f64)) -> Option<(f64, f64)> {
+    let (delta_x, delta_y) = (to.0 - from.0, to.1 - from.1);
+    let mut start = 0.0f64;
+    let mut end = 1.0f64;
+    // each edge is a pair of how fast the line moves out of the box through
+    // it, and how far inside it the start point is
+    for (outwards, inside) in [(-delta_x, from.0 - left), (delta_x, right - from.0), (-delta_y, from.1 - top), (delta_y, bottom - from.1)] {
+        if outwards == 0.0f64 {
+            // parallel to the edge, so either always outside or never
+            if inside < 0.0f64 {
+                return None;
+            }
+            continue;
+        }
+        let crossing = inside / outwards;
+        if outwards < 0.0f64 {
+            start = start.max(crossing);
+        } else {
+            end = end.min(crossing);
+        }
+    }
+    (start <= end).then_some((start, end))
+}
+
+/// Gets the point a fraction of the way along a line.
+fn point_at(from: (f64, f64), to: (f64, f64), fraction: f64) -> (f64, f64) {
+    ((to.0 - from.0).mul_add(fraction, from.0), (to.1 - from.1).mul_add(fraction, from.1))
+}
+
 /// Draw a line on the given texture using Bresenham's line algorithm.
 /// # Parameters
 /// - `texture`: The texture to draw the line on.
-/// - `x1`: The x-coordinate of the start point of the line.
-/// - `y1`: The y-coordinate of the start point of the line.
-/// - `x2`: The x-coordinate of the end point of the line.
-/// - `y2`: The y-coordinate of the end point of the line.
+/// - `(x1, y1)`: The coordinates of the start point of the line.
+/// - `(x2, y2)`: The coordinates of the end point of the line.
 /// - `color`: The color of the line.
+/// - `blend_mode`: How `color` is combined with the existing pixels.
+/// - `skip_start`: Whether to leave out the pixel at the start point.
 #[expect(clippy::single_call_fn, reason = "due to the unchangable return type of DrawComponent::draw")]
 #[expect(clippy::arithmetic_side_effects, reason = "checked arithmetic here is completely unreadable")]
-#[expect(clippy::cast_sign_loss, reason = "cannot fail, and required in line drawing algorithm")]
-#[expect(clippy::as_conversions, reason = "cannot fail, and required in line drawing algorithm")]
-#[expect(clippy::cast_possible_wrap, reason = "cannot fail, and required in line drawing algorithm")]
-fn draw_line(texture: &mut Texture, x1: usize, y1: usize, x2: usize, y2: usize, color: Color) {
+fn draw_line(texture: &mut Texture, (x1, y1): (isize, isize), (x2, y2): (isize, isize), color: Color, blend_mode: BlendMode, skip_start: bool) {
     // Bresenham's line algorithm
     // Note: I personally hate AI-generated code, but this implementation was
     // mostly written by AI to save time. Issues will be fixed as they are
     // found, including any performance issues.
 
-    let x1i = x1 as isize;
-    let y1i = y1 as isize;
-    let x2i = x2 as isize;
-    let y2i = y2 as isize;
-    
-    let dx = (x2i - x1i).abs();
-    let dy = -(y2i - y1i).abs();
+    let dx = (x2 - x1).abs();
+    let dy = -(y2 - y1).abs();
     let sx: isize = if x1 < x2 { 1 } else { -1 };
     let sy: isize = if y1 < y2 { 1 } else { -1 };
     let mut err = dx + dy;
-    let mut x = x1 as isize;
-    let mut y = y1 as isize;
+    let mut x = x1;
+    let mut y = y1;
 
     loop {
-        if x >= 0 && y >= 0 {
-            #[expect(clippy::unwrap_used, reason = "bounds are already checked manually")]
-            texture.set_pixel(x as usize, y as usize, color).unwrap();
+        if let (Ok(pixel_x), Ok(pixel_y), false) = (cast::usize(x), cast::usize(y), skip_start && x == x1 && y == y1) {
+            texture.draw_pixel(pixel_x, pixel_y, color, blend_mode).unwrap_or(());
         }
-        if x == x2i && y == y2i {
+        if x == x2 && y == y2 {
             break;
         }
         let e2 = 2 * err;
@@ -97,9 +243,113 @@ fn draw_line(texture: &mut Texture, x1: usize, y1: usize, x2: usize, y2: usize,
     }
 }
 
+/// Draw an anti-aliased line on the given texture using Xiaolin Wu's line
+/// algorithm. Each step along the line's major axis covers two pixels, which
+/// are blended by how close the line passes to each of their centers.
+/// # Parameters
+/// - `texture`: The texture to draw the line on.
+/// - `from`: The (x, y) coordinates of the start point of the line.
+/// - `to`: The (x, y) coordinates of the end point of the line.
+/// - `color`: The color of the line.
+/// - `blend_mode`: How `color` is combined with the existing pixels.
+#[expect(clippy::single_call_fn, reason = "kept separate from draw_thin for readability")]
+fn draw_line_anti_aliased(texture: &mut Texture, from: (f64, f64), to: (f64, f64), color: Color, blend_mode: BlendMode) {
+    // steep lines are drawn along the y axis, with x and y swapped
+    let steep = (to.1 - from.1).abs() > (to.0 - from.0).abs();
+    let swap = |point: (f64, f64)| if steep { (point.1, point.0) } else { point };
+    let (start, end) = if swap(from).0 <= swap(to).0 { (swap(from), swap(to)) } else { (swap(to), swap(from)) };
+    let length = end.0 - start.0;
+    let gradient = if length == 0.0f64 { 1.0f64 } else { (end.1 - start.1) / length };
+    let mut plot = |major: f64, minor: f64, coverage: f64| {
+        let (x, y) = if steep { (minor, major) } else { (major, minor) };
+        if let (Ok(pixel_x), Ok(pixel_y)) = (cast::usize(x), cast::usize(y)) {
+            texture.draw_pixel(pixel_x, pixel_y, color.multiply_alpha(coverage), blend_mode).unwrap_or(());
+        }
+    };
+    // each endpoint pixel is only covered by the part of the line inside it
+    let first_x = start.0.round();
+    let first_y = gradient.mul_add(first_x - start.0, start.1);
+    let first_gap = 1.0f64 - fraction(start.0 + 0.5);
+    plot(first_x, first_y.floor(), (1.0f64 - fraction(first_y)) * first_gap);
+    plot(first_x, first_y.floor() + 1.0f64, fraction(first_y) * first_gap);
+    let last_x = end.0.round();
+    let last_y = gradient.mul_add(last_x - end.0, end.1);
+    let last_gap = fraction(end.0 + 0.5);
+    plot(last_x, last_y.floor(), (1.0f64 - fraction(last_y)) * last_gap);
+    plot(last_x, last_y.floor() + 1.0f64, fraction(last_y) * last_gap);
+    // the pixels between the endpoints, stepping one pixel at a time
+    let mut y = first_y;
+    for step in 1..cast::usize(last_x - first_x).unwrap_or(0) {
+        y += gradient;
+        let x = first_x + cast::f64(step);
+        plot(x, y.floor(), 1.0f64 - fraction(y));
+        plot(x, y.floor() + 1.0f64, fraction(y));
+    }
+}
+
+/// Gets the fractional part of a number, which is always positive.
+fn fraction(value: f64) -> f64 {
+    value - value.floor()
+}
+
 impl DrawComponent for LineNode {
     fn draw(&self, texture: &mut crate::Texture) {
-        draw_line(texture, self.x1, self.y1, self.x2, self.y2, self.color);
+        match self.stroke_width {
+            0 => {},
+            1 => {
+                draw_thin_dashed(texture, &[(self.x1, self.y1), (self.x2, self.y2)], false, self.dash_pattern.as_ref(), self.color, self.blend_mode, self.anti_aliased);
+            },
+            width => {
+                // the outline is filled in texture coordinates, where the
+                // centers of pixels are halfway between integers
+                let (from, to) = ((self.x1 + 0.5f64, self.y1 + 0.5f64), (self.x2 + 0.5f64, self.y2 + 0.5f64));
+                // anything more than half the width past the edges, which
+                // includes any caps, cannot be seen
+                let margin = cast::f64(width) / 2.0f64 + 1.0f64;
+                let bounds = (-margin, -margin, cast::f64(texture.get_width()) + margin, cast::f64(texture.get_height()) + margin);
+                let Some((start, end)) = clip(from, to, bounds) else {
+                    return;
+                };
+                let points = [point_at(from, to, start), point_at(from, to, end)];
+                // the dashes stay where they were before the start was clipped
+                let pattern = self.dash_pattern.clone().map(|mut pattern| {
+                    pattern.offset += start * (to.0 - from.0).hypot(to.1 - from.1);
+                    pattern
+                });
+                let outline = stroke::dashed_outline(&points, false, cast::f64(width), self.line_cap, LineJoin::Miter, pattern.as_ref());
+                raster::fill(texture, &outline, self.color, self.blend_mode, self.anti_aliased, FillRule::NonZero);
+            },
+        }
+    }
+}
+
+impl StrokeColorComponent for LineNode {
+    fn get_stroke_color(&self) -> &Color {
+        &self.color
+    }
+
+    fn set_stroke_color(&mut self, color: Color) {
+        self.color = color;
+    }
+}
+
+impl StrokeWidthComponent for LineNode {
+    fn get_stroke_width(&self) -> usize {
+        self.stroke_width
+    }
+
+    fn set_stroke_width(&mut self, width: usize) {
+        self.stroke_width = width;
     }
 }
 
+
+impl BlendModeComponent for LineNode {
+    fn get_blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+}
@@ -0,0 +1,295 @@
+//! Netpbm (PBM, PGM, PPM and PAM) loading and saving for `Texture`, with no
+//! extra dependencies.
+//!
+//! Every Netpbm variant can be loaded: plain (ASCII) and raw (binary) bitmaps,
+//! graymaps and pixmaps (`P1` to `P6`), and arbitrary maps (`P7`) with a
+//! depth of 1 to 4 channels, at 8 or 16 bits per sample.
+//! Textures can be saved as raw PPM (`P6`, without alpha) or as PAM (`P7`,
+//! with alpha).
+
+use std::{fs, path::Path};
+
+use anyhow::{Context as _, Error};
+
+use crate::{Color, Texture};
+
+#[expect(clippy::multiple_inherent_impl, reason = "each image format is kept in its own module")]
+impl Texture {
+    /// Loads a Netpbm image file (PBM, PGM, PPM or PAM) into a new `Texture`.
+    /// # Arguments
+    /// * `path` - The path of the Netpbm file to load.
+    /// # Returns
+    /// A `Texture` with the same size and pixels as the image.
+    /// # Errors
+    /// Errors if the file cannot be read or is not a valid Netpbm image.
+    /// # Example
+    /// ```rust
+    /// let heightmap = Texture::load_netpbm("assets/heightmap.pgm")
+    ///     .expect("failed to load heightmap");
+    /// ```
+    pub fn load_netpbm<P: AsRef<Path>>(path: P) -> Result<Texture, Error> {
+        let bytes = fs::read(path).context("Pixl: load_netpbm: failed to read file")?;
+        Texture::decode_netpbm(&bytes)
+    }
+
+    /// Saves the texture to a raw (binary) PPM image file. PPM images have no
+    /// alpha channel, so it is discarded - use `save_pam` to keep it.
+    /// # Arguments
+    /// * `path` - The path of the PPM file to create or overwrite.
+    /// # Errors
+    /// Errors if the file cannot be written.
+    /// # Example
+    /// ```rust
+    /// texture.save_ppm("screenshot.ppm")
+    ///     .expect("failed to save screenshot");
+    /// ```
+    pub fn save_ppm<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        fs::write(path, self.encode_ppm()).context("Pixl: save_ppm: failed to write file")
+    }
+
+    /// Saves the texture to a PAM image file, including its alpha channel.
+    /// # Arguments
+    /// * `path` - The path of the PAM file to create or overwrite.
+    /// # Errors
+    /// Errors if the file cannot be written.
+    /// # Example
+    /// ```rust
+    /// texture.save_pam("screenshot.pam")
+    ///     .expect("failed to save screenshot");
+    /// ```
+    pub fn save_pam<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        fs::write(path, self.encode_pam()).context("Pixl: save_pam: failed to write file")
+    }
+
+    /// Decodes an in-memory Netpbm image (PBM, PGM, PPM or PAM) into a new
+    /// `Texture`.
+    /// # Arguments
+    /// * `bytes` - The contents of a Netpbm file.
+    /// # Returns
+    /// A `Texture` with the same size and pixels as the image.
+    /// # Errors
+    /// Errors if `bytes` is not a valid Netpbm image.
+    /// # Example
+    /// ```rust
+    /// let mask = Texture::decode_netpbm(b"P1 2 2  1 0  0 1")
+    ///     .expect("invalid mask");
+    /// ```
+    pub fn decode_netpbm(bytes: &[u8]) -> Result<Texture, Error> {
+        let mut header = Header { bytes, position: 2 };
+        let (width, height, channels, max_value) = match bytes.get(..2) {
+            Some(b"P1" | b"P4") => (header.number()?, header.number()?, 1, 1),
+            Some(b"P2" | b"P5") => (header.number()?, header.number()?, 1, header.number()?),
+            Some(b"P3" | b"P6") => (header.number()?, header.number()?, 3, header.number()?),
+            Some(b"P7") => header.pam_fields()?,
+            _ => return Err(Error::msg("Pixl: decode_netpbm: missing Netpbm signature")),
+        };
+        if !(1..=usize::from(u16::MAX)).contains(&max_value) || !(1..=4).contains(&channels) {
+            return Err(Error::msg("Pixl: decode_netpbm: unsupported maximum value or depth"));
+        }
+        let sample_count = width.checked_mul(height)
+            .and_then(|count| count.checked_mul(channels))
+            .ok_or_else(|| Error::msg("Pixl: decode_netpbm: image is too large"))?;
+
+        let samples = match bytes.get(..2) {
+            Some(b"P1") => header.plain_bits(sample_count)?,
+            Some(b"P2" | b"P3") => header.plain_samples(sample_count, max_value)?,
+            Some(b"P4") => header.raw_bits(width, height)?,
+            _ => header.raw_samples(sample_count, max_value)?,
+        };
+        let pixels = samples.chunks_exact(channels)
+            .map(|chunk| match *chunk {
+                [gray] => Color::rgb(gray, gray, gray),
+                [gray, a] => Color::rgba(gray, gray, gray, a),
+                [r, g, b] => Color::rgb(r, g, b),
+                [r, g, b, a] => Color::rgba(r, g, b, a),
+                // the depth is checked to be between 1 and 4 above
+                _ => Color::rgba(0, 0, 0, 0),
+            })
+            .collect();
+        Texture::from_pixels(width, height, pixels)
+    }
+
+    /// Encodes the texture as an in-memory raw (binary) PPM image. PPM images
+    /// have no alpha channel, so it is discarded - use `encode_pam` to keep
+    /// it.
+    /// # Returns
+    /// The contents of the PPM file.
+    /// # Example
+    /// ```rust
+    /// let bytes = texture.encode_ppm();
+    /// ```
+    #[must_use]
+    pub fn encode_ppm(&self) -> Vec<u8> {
+        let mut bytes = format!("P6\n{} {}\n255\n", self.get_width(), self.get_height()).into_bytes();
+        for pixel in self.get_pixels() {
+            bytes.extend_from_slice(&[pixel.r, pixel.g, pixel.b]);
+        }
+        bytes
+    }
+
+    /// Encodes the texture as an in-memory PAM image, including its alpha
+    /// channel.
+    /// # Returns
+    /// The contents of the PAM file.
+    /// # Example
+    /// ```rust
+    /// let bytes = texture.encode_pam();
+    /// ```
+    #[must_use]
+    pub fn encode_pam(&self) -> Vec<u8> {
+        let mut bytes = format!(
+            "P7\nWIDTH {}\nHEIGHT {}\nDEPTH 4\nMAXVAL 255\nTUPLTYPE RGB_ALPHA\nENDHDR\n",
+            self.get_width(), self.get_height()
+        ).into_bytes();
+        for pixel in self.get_pixels() {
+            bytes.extend_from_slice(&[pixel.r, pixel.g, pixel.b, pixel.a]);
+        }
+        bytes
+    }
+}
+
+/// A cursor over the contents of a Netpbm file, which parses the whitespace
+/// separated header (and the samples of plain images).
+struct Header<'data> {
+    /// The full contents of the file.
+    bytes: &'data [u8],
+    /// The index of the next byte to be read.
+    position: usize,
+}
+
+impl<'data> Header<'data> {
+    /// Skips whitespace and `#` comments, which may appear anywhere between
+    /// header tokens.
+    fn skip_whitespace(&mut self) {
+        while let Some(&byte) = self.bytes.get(self.position) {
+            if byte == b'#' {
+                while self.bytes.get(self.position).is_some_and(|&comment| comment != b'\n') {
+                    self.position = self.position.saturating_add(1);
+                }
+            }
+            else if byte.is_ascii_whitespace() {
+                self.position = self.position.saturating_add(1);
+            }
+            else {
+                break;
+            }
+        }
+    }
+
+    /// Reads the next whitespace separated token.
+    fn token(&mut self) -> Result<&'data [u8], Error> {
+        self.skip_whitespace();
+        let start = self.position;
+        while self.bytes.get(self.position).is_some_and(|byte| !byte.is_ascii_whitespace()) {
+            self.position = self.position.saturating_add(1);
+        }
+        match self.bytes.get(start..self.position) {
+            Some(token) if !token.is_empty() => Ok(token),
+            _ => Err(Error::msg("Pixl: decode_netpbm: unexpected end of header")),
+        }
+    }
+
+    /// Reads the next token as a decimal number.
+    fn number(&mut self) -> Result<usize, Error> {
+        let token = self.token()?;
+        str::from_utf8(token).ok()
+            .and_then(|text| text.parse().ok())
+            .ok_or_else(|| Error::msg("Pixl: decode_netpbm: invalid number in header"))
+    }
+
+    /// Reads the `WIDTH`, `HEIGHT`, `DEPTH` and `MAXVAL` fields of a PAM
+    /// header, up to and including `ENDHDR`.
+    fn pam_fields(&mut self) -> Result<(usize, usize, usize, usize), Error> {
+        let mut fields = [None; 4];
+        loop {
+            let field = match self.token()? {
+                b"WIDTH" => fields.get_mut(0),
+                b"HEIGHT" => fields.get_mut(1),
+                b"DEPTH" => fields.get_mut(2),
+                b"MAXVAL" => fields.get_mut(3),
+                // the tuple type is implied by the depth
+                b"TUPLTYPE" => { self.token()?; continue; },
+                b"ENDHDR" => break,
+                _ => return Err(Error::msg("Pixl: decode_netpbm: unknown PAM header field")),
+            };
+            if let Some(slot) = field {
+                *slot = Some(self.number()?);
+            }
+        }
+        match fields {
+            [Some(width), Some(height), Some(depth), Some(max_value)] => Ok((width, height, depth, max_value)),
+            _ => Err(Error::msg("Pixl: decode_netpbm: missing PAM header field")),
+        }
+    }
+
+    /// Reads the samples of a plain PBM image, where each pixel is a `0`
+    /// (white) or `1` (black) character, optionally separated by whitespace.
+    fn plain_bits(&mut self, count: usize) -> Result<Vec<u8>, Error> {
+        let mut samples = Vec::with_capacity(count.min(self.bytes.len()));
+        while samples.len() < count {
+            self.skip_whitespace();
+            let bit = self.bytes.get(self.position)
+                .ok_or_else(|| Error::msg("Pixl: decode_netpbm: unexpected end of data"))?;
+            samples.push(if *bit == b'1' { 0 } else { u8::MAX });
+            self.position = self.position.saturating_add(1);
+        }
+        Ok(samples)
+    }
+
+    /// Reads the samples of a plain PGM or PPM image, where each sample is a
+    /// decimal number.
+    fn plain_samples(&mut self, count: usize, max_value: usize) -> Result<Vec<u8>, Error> {
+        let mut samples = Vec::with_capacity(count.min(self.bytes.len()));
+        while samples.len() < count {
+            samples.push(scale_sample(self.number()?, max_value));
+        }
+        Ok(samples)
+    }
+
+    /// Reads the samples of a raw PBM image, where each pixel is a bit (1
+    /// being black), and each row is padded to a whole byte.
+    fn raw_bits(&self, width: usize, height: usize) -> Result<Vec<u8>, Error> {
+        let row_size = width.div_ceil(8);
+        let data = self.raster(row_size.saturating_mul(height))?;
+        Ok(data.chunks_exact(row_size.max(1))
+            .flat_map(|row| row.iter()
+                .flat_map(|&byte| (0..8u8).rev().map(move |bit| if (byte >> bit) & 1 == 1 { 0 } else { u8::MAX }))
+                .take(width))
+            .collect())
+    }
+
+    /// Reads the samples of a raw PGM, PPM or PAM image, which are single
+    /// bytes or, if the maximum value is over 255, big-endian pairs of bytes.
+    fn raw_samples(&self, count: usize, max_value: usize) -> Result<Vec<u8>, Error> {
+        if max_value <= 255 {
+            let data = self.raster(count)?;
+            Ok(data.iter().map(|&sample| scale_sample(usize::from(sample), max_value)).collect())
+        }
+        else {
+            let data = self.raster(count.saturating_mul(2))?;
+            Ok(data.chunks_exact(2)
+                .map(|pair| match *pair {
+                    [high, low] => scale_sample((usize::from(high) << 8u8) | usize::from(low), max_value),
+                    _ => 0,
+                })
+                .collect())
+        }
+    }
+
+    /// Gets the raw image data following the header, which is separated from
+    /// it by a single whitespace character.
+    fn raster(&self, size: usize) -> Result<&'data [u8], Error> {
+        let start = self.position.saturating_add(1);
+        self.bytes.get(start..start.saturating_add(size))
+            .ok_or_else(|| Error::msg("Pixl: decode_netpbm: unexpected end of data"))
+    }
+}
+
+/// Scales a sample from the 0 to `max_value` range to the 0-255 range.
+fn scale_sample(sample: usize, max_value: usize) -> u8 {
+    let scaled = sample.min(max_value).saturating_mul(255)
+        .saturating_add(max_value / 2)
+        .checked_div(max_value)
+        .unwrap_or(0);
+    cast::u8(scaled).unwrap_or(u8::MAX)
+}
@@ -0,0 +1,455 @@
+//! The `Path` builder, for describing shapes made of lines, curves and arcs,
+//! and the `PathNode` struct which fills and strokes one onto a texture.
+//!
+//! Paths are built the same way as on an HTML canvas: `move_to` starts a new
+//! piece of the shape, each following call adds to it, and `close` joins it
+//! back up to where it started.
+//! ```rust
+//! // a speech bubble with rounded corners and a tail
+//! let mut bubble = Path::new();
+//! bubble.move_to(20.0, 10.0)
+//!     .arc_to(90.0, 10.0, 90.0, 50.0, 8.0)
+//!     .arc_to(90.0, 50.0, 10.0, 50.0, 8.0)
+//!     .line_to(30.0, 50.0)
+//!     .line_to(18.0, 62.0)
+//!     .line_to(22.0, 50.0)
+//!     .arc_to(10.0, 50.0, 10.0, 10.0, 8.0)
+//!     .arc_to(10.0, 10.0, 90.0, 10.0, 8.0)
+//!     .close();
+//! let mut node = PathNode::new(bubble, Color::WHITE);
+//! node.stroke_color = Color::BLACK;
+//! node.stroke_width = 2;
+//! node.anti_aliased = true;
+//! texture.add(&node);
+//! ```
+
+use core::{f64::consts::{FRAC_PI_2, PI, TAU}, iter};
+
+use crate::{BlendMode, Color, Texture, component::{BlendModeComponent, DrawComponent, FillColorComponent, StrokeColorComponent, StrokeWidthComponent}, line, polygon::FillRule, raster::{self, Point, Segment}, stroke::{self, DashPattern, LineCap, LineJoin}, transform::Transform};
+
+/// A shape made of any number of pieces (subpaths), each a run of straight
+/// lines, Bézier curves and arcs.
+/// Coordinates are the corners between pixels, as with `PolygonNode`.
+/// Angles are in radians, with 0 pointing right and positive angles going
+/// clockwise.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct Path {
+    /// Every piece of the path, in the order they were started.
+    subpaths: Vec<Subpath>,
+}
+
+/// One connected piece of a `Path`.
+#[derive(Clone, Debug, PartialEq)]
+struct Subpath {
+    /// The point the piece starts at.
+    start: Point,
+    /// The lines and curves of the piece, each starting where the last ends.
+    segments: Vec<Segment>,
+    /// Whether the piece joins back up to its start.
+    closed: bool,
+}
+
+impl Path {
+    /// Creates a new, empty `Path`.
+    /// # Returns
+    /// A `Path` with nothing in it.
+    #[must_use]
+    pub const fn new() -> Path {
+        Path { subpaths: Vec::new() }
+    }
+
+    /// Starts a new piece of the path, without connecting it to the last one.
+    /// # Parameters
+    /// - `x`, `y`: The point the new piece starts at.
+    /// # Returns
+    /// The path, so that more calls can be chained onto it.
+    pub fn move_to(&mut self, x: f64, y: f64) -> &mut Path {
+        self.subpaths.push(Subpath { start: (x, y), segments: Vec::new(), closed: false });
+        self
+    }
+
+    /// Adds a straight line from the end of the path to a point. If the path
+    /// is empty, this starts it at the point instead.
+    /// # Parameters
+    /// - `x`, `y`: The point the line ends at.
+    /// # Returns
+    /// The path, so that more calls can be chained onto it.
+    pub fn line_to(&mut self, x: f64, y: f64) -> &mut Path {
+        let Some(from) = self.current_point() else {
+            return self.move_to(x, y);
+        };
+        self.push(Segment::Line(from, (x, y)))
+    }
+
+    /// Adds a quadratic Bézier curve from the end of the path to a point,
+    /// pulled towards a control point.
+    /// # Parameters
+    /// - `control_x`, `control_y`: The control point.
+    /// - `x`, `y`: The point the curve ends at.
+    /// # Returns
+    /// The path, so that more calls can be chained onto it.
+    pub fn quad_to(&mut self, control_x: f64, control_y: f64, x: f64, y: f64) -> &mut Path {
+        let from = self.start_point(control_x, control_y);
+        self.push(Segment::Quadratic(from, (control_x, control_y), (x, y)))
+    }
+
+    /// Adds a cubic Bézier curve from the end of the path to a point, pulled
+    /// towards two control points.
+    /// # Parameters
+    /// - `control_1_x`, `control_1_y`: The control point near the start.
+    /// - `control_2_x`, `control_2_y`: The control point near the end.
+    /// - `x`, `y`: The point the curve ends at.
+    /// # Returns
+    /// The path, so that more calls can be chained onto it.
+    pub fn cubic_to(&mut self, control_1_x: f64, control_1_y: f64, control_2_x: f64, control_2_y: f64, x: f64, y: f64) -> &mut Path {
+        let from = self.start_point(control_1_x, control_1_y);
+        self.push(Segment::Cubic(from, (control_1_x, control_1_y), (control_2_x, control_2_y), (x, y)))
+    }
+
+    /// Adds part of a circle, with a straight line to its start from the end
+    /// of the path. A whole turn or more draws the whole circle.
+    /// # Parameters
+    /// - `center_x`, `center_y`: The center of the circle.
+    /// - `radius`: The radius of the circle.
+    /// - `start_angle`: The angle the arc starts at.
+    /// - `end_angle`: The angle the arc ends at. The arc goes clockwise if
+    ///   this is more than `start_angle`, and anticlockwise if it is less.
+    /// # Returns
+    /// The path, so that more calls can be chained onto it.
+    /// # Example
+    /// ```rust
+    /// // a pie chart slice of a quarter
+    /// let mut slice = Path::new();
+    /// slice.move_to(40.0, 30.0).arc(40.0, 30.0, 20.0, 0.0, core::f64::consts::FRAC_PI_2).close();
+    /// ```
+    pub fn arc(&mut self, center_x: f64, center_y: f64, radius: f64, start_angle: f64, end_angle: f64) -> &mut Path {
+        self.add_arc((center_x, center_y), (radius, radius), 0.0, start_angle, (end_angle - start_angle).clamp(-TAU, TAU))
+    }
+
+    /// Adds a rounded corner, as on an HTML canvas: a straight line from the
+    /// end of the path towards a corner point, then an arc curving around the
+    /// corner to point along the line from the corner to an end point. The
+    /// path ends where the arc does, not at the end point.
+    /// If the path is empty, this starts it at the corner instead.
+    /// # Parameters
+    /// - `corner_x`, `corner_y`: The corner being rounded.
+    /// - `x`, `y`: A point the line after the corner passes through.
+    /// - `radius`: The radius of the rounded corner. A radius of 0 gives a
+    ///   sharp corner.
+    /// # Returns
+    /// The path, so that more calls can be chained onto it.
+    pub fn arc_to(&mut self, corner_x: f64, corner_y: f64, x: f64, y: f64, radius: f64) -> &mut Path {
+        let Some(from) = self.current_point() else {
+            return self.move_to(corner_x, corner_y);
+        };
+        let corner = (corner_x, corner_y);
+        let incoming = (from.0 - corner.0, from.1 - corner.1);
+        let outgoing = (x - corner.0, y - corner.1);
+        let (incoming_length, outgoing_length) = (incoming.0.hypot(incoming.1), outgoing.0.hypot(outgoing.1));
+        // the angle between the two lines meeting at the corner
+        let cross = incoming.0.mul_add(outgoing.1, -(incoming.1 * outgoing.0));
+        let opening = cross.abs().atan2(incoming.0.mul_add(outgoing.0, incoming.1 * outgoing.1));
+        let tangent_distance = radius / (opening / 2.0).tan();
+        // a straight line has no corner to round
+        if !(radius > 0.0f64 && incoming_length > 0.0f64 && outgoing_length > 0.0f64 && opening > 0.0f64 && opening < PI && tangent_distance.is_finite()) {
+            return self.line_to(corner_x, corner_y);
+        }
+        let along_incoming = tangent_distance / incoming_length;
+        let along_outgoing = tangent_distance / outgoing_length;
+        let start = (incoming.0.mul_add(along_incoming, corner.0), incoming.1.mul_add(along_incoming, corner.1));
+        let end = (outgoing.0.mul_add(along_outgoing, corner.0), outgoing.1.mul_add(along_outgoing, corner.1));
+        // the center is along the line halving the corner, which passes
+        // through the middle of the two tangent points
+        let middle = (start.0.midpoint(end.0) - corner.0, start.1.midpoint(end.1) - corner.1);
+        let along_middle = radius / (opening / 2.0).sin() / middle.0.hypot(middle.1);
+        let center = (middle.0.mul_add(along_middle, corner.0), middle.1.mul_add(along_middle, corner.1));
+        let start_angle = (start.1 - center.1).atan2(start.0 - center.0);
+        let end_angle = (end.1 - center.1).atan2(end.0 - center.0);
+        // the arc around a corner is always the short way round
+        let sweep = (end_angle - start_angle + PI).rem_euclid(TAU) - PI;
+        self.add_arc(center, (radius, radius), 0.0, start_angle, sweep)
+    }
+
+    /// Joins the current piece of the path back up to where it started, and
+    /// starts a new piece there.
+    /// # Returns
+    /// The path, so that more calls can be chained onto it.
+    pub fn close(&mut self) -> &mut Path {
+        let Some(subpath) = self.subpaths.last_mut() else {
+            return self;
+        };
+        subpath.closed = true;
+        let start = subpath.start;
+        self.move_to(start.0, start.1)
+    }
+
+    /// Creates a copy of the path with every point moved by a transformation.
+    /// Curves stay exactly the same shape, as transforming their control
+    /// points transforms the whole curve.
+    /// # Parameters
+    /// - `transform`: The transformation to apply.
+    /// # Returns
+    /// The transformed `Path`.
+    /// # Example
+    /// ```rust
+    /// let mut arrow = Path::new();
+    /// arrow.move_to(0.0, -5.0).line_to(10.0, 0.0).line_to(0.0, 5.0).close();
+    /// let pointing_down = arrow.transformed(Transform::rotation(core::f64::consts::FRAC_PI_2).then(Transform::translation(40.0, 20.0)));
+    /// ```
+    #[must_use]
+    pub fn transformed(&self, transform: Transform) -> Path {
+        let point = |(x, y): Point| transform.apply(x, y);
+        let subpaths = self.subpaths.iter().map(|subpath| Subpath {
+            start: point(subpath.start),
+            segments: subpath.segments.iter().map(|&segment| match segment {
+                Segment::Line(from, to) => Segment::Line(point(from), point(to)),
+                Segment::Quadratic(from, control, to) => Segment::Quadratic(point(from), point(control), point(to)),
+                Segment::Cubic(from, control_1, control_2, to) => Segment::Cubic(point(from), point(control_1), point(control_2), point(to)),
+            }).collect(),
+            closed: subpath.closed,
+        }).collect();
+        Path { subpaths }
+    }
+
+    /// Gets the point the path currently ends at.
+    /// Returns `None` if the path is empty.
+    fn current_point(&self) -> Option<Point> {
+        let subpath = self.subpaths.last()?;
+        Some(subpath.segments.last().map_or(subpath.start, |&segment| end_point(segment)))
+    }
+
+    /// Gets the point the next segment starts at, starting the path at
+    /// (`x`, `y`) if it is empty.
+    fn start_point(&mut self, x: f64, y: f64) -> Point {
+        self.current_point().unwrap_or_else(|| {
+            self.move_to(x, y);
+            (x, y)
+        })
+    }
+
+    /// Adds a segment to the current piece of the path.
+    fn push(&mut self, segment: Segment) -> &mut Path {
+        if let Some(subpath) = self.subpaths.last_mut() {
+            subpath.segments.push(segment);
+        }
+        self
+    }
+
+    /// Adds an arc around an ellipse, turned by `rotation`, as cubic Bézier
+    /// curves each a quarter turn or less, after a straight line to its start.
+    /// Angles are measured around the unrotated ellipse.
+    pub(crate) fn add_arc(&mut self, center: Point, radii: Point, rotation: f64, start_angle: f64, sweep: f64) -> &mut Path {
+        let (sin, cos) = rotation.sin_cos();
+        let rotate = |(x, y): Point| (cos.mul_add(x, -(sin * y)), sin.mul_add(x, cos * y));
+        let point = |angle: f64| {
+            let (x, y) = rotate((radii.0 * angle.cos(), radii.1 * angle.sin()));
+            (x + center.0, y + center.1)
+        };
+        // the direction the arc is going in at an angle
+        let tangent = |angle: f64| rotate((-radii.0 * angle.sin(), radii.1 * angle.cos()));
+        let start = point(start_angle);
+        self.line_to(start.0, start.1);
+        let Ok(pieces) = cast::usize((sweep.abs() / FRAC_PI_2).ceil()) else {
+            return self;
+        };
+        let piece_sweep = sweep / cast::f64(pieces.max(1));
+        // how far along the tangents the control points are
+        let control = 4.0f64 / 3.0f64 * (piece_sweep / 4.0).tan();
+        let mut from = start;
+        for piece in 1..=pieces {
+            let (from_tangent, to_tangent) = (
+                tangent(cast::f64(piece.saturating_sub(1)).mul_add(piece_sweep, start_angle)),
+                tangent(cast::f64(piece).mul_add(piece_sweep, start_angle)),
+            );
+            let to = point(cast::f64(piece).mul_add(piece_sweep, start_angle));
+            let control_1 = (control.mul_add(from_tangent.0, from.0), control.mul_add(from_tangent.1, from.1));
+            let control_2 = (control.mul_add(-to_tangent.0, to.0), control.mul_add(-to_tangent.1, to.1));
+            self.push(Segment::Cubic(from, control_1, control_2, to));
+            from = to;
+        }
+        self
+    }
+
+    /// Gets the outline of every piece of the path, each closed back up to
+    /// its start, ready to be filled.
+    pub(crate) fn fill_segments(&self) -> Vec<Segment> {
+        self.subpaths.iter().flat_map(|subpath| {
+            let end = subpath.segments.last().map_or(subpath.start, |&segment| end_point(segment));
+            subpath.segments.iter().copied().chain(iter::once(Segment::Line(end, subpath.start)))
+        }).collect()
+    }
+
+    /// Gets every piece of the path as the points along it, with curves split
+    /// into straight lines, and whether it is closed.
+    pub(crate) fn flattened(&self) -> Vec<(Vec<Point>, bool)> {
+        self.subpaths.iter().filter(|subpath| !subpath.segments.is_empty()).map(|subpath| {
+            let mut points = vec![subpath.start];
+            for &segment in &subpath.segments {
+                segment.flatten(|point| points.push(point));
+            }
+            (points, subpath.closed)
+        }).collect()
+    }
+}
+
+/// Gets the point a segment ends at.
+const fn end_point(segment: Segment) -> Point {
+    match segment {
+        Segment::Line(_, to) | Segment::Quadratic(_, _, to) | Segment::Cubic(_, _, _, to) => to,
+    }
+}
+
+/// A node which fills and outlines a `Path`.
+/// ## Implemented components:
+/// - `DrawComponent`
+/// - `FillColorComponent`
+/// - `StrokeColorComponent`
+/// - `StrokeWidthComponent`
+/// - `BlendModeComponent`
+/// # Example
+/// ```rust
+/// let mut wave = Path::new();
+/// wave.move_to(0.0, 30.0).cubic_to(20.0, 0.0, 40.0, 60.0, 60.0, 30.0);
+/// let mut node = PathNode::new(wave, Color::WHITE);
+/// node.filled = false;
+/// node.stroke_width = 3;
+/// node.line_cap = LineCap::Round;
+/// texture.add(&node);
+/// ```
+#[non_exhaustive]
+#[expect(clippy::module_name_repetitions, reason = "struct should be called 'PathNode' as it is standard.")]
+pub struct PathNode {
+    /// The shape to draw.
+    pub path: Path,
+
+    /// The color of the inside of the path. Pieces of the path which are not
+    /// closed are filled as if they were. Its alpha channel is blended over
+    /// the existing pixels.
+    pub fill_color: Color,
+
+    /// Whether the inside of the path is filled with `fill_color`.
+    /// Defaults to true.
+    pub filled: bool,
+
+    /// How the inside of the path is decided where its pieces overlap or
+    /// cross themselves. Defaults to `FillRule::NonZero`.
+    pub fill_rule: FillRule,
+
+    /// The color of the path's outline.
+    /// Defaults to the same color as `fill_color`.
+    pub stroke_color: Color,
+
+    /// The width of the path's outline, in pixels, centered on the path.
+    /// Defaults to 0, which draws no outline.
+    pub stroke_width: usize,
+
+    /// The shape drawn at the ends of pieces of the path which are not
+    /// closed. Defaults to `LineCap::Butt`.
+    pub line_cap: LineCap,
+
+    /// The shape drawn at the corners of the outline.
+    /// Defaults to `LineJoin::Miter`.
+    pub line_join: LineJoin,
+
+    /// The pattern of dashes the outline is drawn with, or `None` to draw it
+    /// solid. Defaults to `None`.
+    pub dash_pattern: Option<DashPattern>,
+
+    /// Whether the path is drawn with smooth, anti-aliased edges.
+    /// Defaults to false.
+    pub anti_aliased: bool,
+
+    /// How the fill and stroke colors are combined with the existing pixels
+    /// when drawn. Defaults to `BlendMode::SourceOver`.
+    pub blend_mode: BlendMode,
+}
+
+impl PathNode {
+    /// Create a new `PathNode` filling a path.
+    /// # Parameters
+    /// - `path`: The shape to draw.
+    /// - `fill_color`: The color to fill the path with.
+    /// # Returns
+    /// A filled `PathNode` with no outline.
+    #[must_use]
+    pub const fn new(path: Path, fill_color: Color) -> PathNode {
+        PathNode {
+            path, fill_color,
+            filled: true,
+            fill_rule: FillRule::NonZero,
+            stroke_color: fill_color,
+            stroke_width: 0,
+            line_cap: LineCap::Butt,
+            line_join: LineJoin::Miter,
+            dash_pattern: None,
+            anti_aliased: false,
+            blend_mode: BlendMode::SourceOver,
+        }
+    }
+}
+
+impl DrawComponent for PathNode {
+    fn draw(&self, texture: &mut Texture) {
+        if self.filled {
+            raster::fill(texture, &self.path.fill_segments(), self.fill_color, self.blend_mode, self.anti_aliased, self.fill_rule);
+        }
+        match self.stroke_width {
+            0 => {},
+            1 => {
+                // thin outlines are drawn as lines through the centers of
+                // pixels, which are halfway between the corners
+                for (points, closed) in self.path.flattened() {
+                    let centered: Vec<Point> = points.iter().map(|&(x, y)| (x - 0.5f64, y - 0.5f64)).collect();
+                    line::draw_thin_dashed(texture, &centered, closed, self.dash_pattern.as_ref(), self.stroke_color, self.blend_mode, self.anti_aliased);
+                }
+            },
+            width => {
+                let outline: Vec<Segment> = self.path.flattened().into_iter().flat_map(|(points, closed)| {
+                    stroke::dashed_outline(&points, closed, cast::f64(width), self.line_cap, self.line_join, self.dash_pattern.as_ref())
+                }).collect();
+                raster::fill(texture, &outline, self.stroke_color, self.blend_mode, self.anti_aliased, FillRule::NonZero);
+            },
+        }
+    }
+}
+
+impl FillColorComponent for PathNode {
+    fn get_fill_color(&self) -> &Color {
+        &self.fill_color
+    }
+
+    fn set_fill_color(&mut self, color: Color) {
+        self.fill_color = color;
+    }
+}
+
+impl StrokeColorComponent for PathNode {
+    fn get_stroke_color(&self) -> &Color {
+        &self.stroke_color
+    }
+
+    fn set_stroke_color(&mut self, color: Color) {
+        self.stroke_color = color;
+    }
+}
+
+impl StrokeWidthComponent for PathNode {
+    fn get_stroke_width(&self) -> usize {
+        self.stroke_width
+    }
+
+    fn set_stroke_width(&mut self, width: usize) {
+        self.stroke_width = width;
+    }
+}
+
+impl BlendModeComponent for PathNode {
+    fn get_blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+}
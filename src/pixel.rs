@@ -1,8 +1,8 @@
 //! `PixelNode` struct - represents a node for a single pixel of a `Texture`.
 
 
-use crate::Color;
-use crate::component::{DrawComponent, FillColorComponent, PositionComponent};
+use crate::{BlendMode, Color};
+use crate::component::{BlendModeComponent, DrawComponent, FillColorComponent, PositionComponent};
 
 
 /// A node representing a single pixel in a texture.
@@ -10,6 +10,7 @@ use crate::component::{DrawComponent, FillColorComponent, PositionComponent};
 /// - `DrawComponent`
 /// - `PositionComponent`
 /// - `FillColorComponent`
+/// - `BlendModeComponent`
 #[expect(clippy::module_name_repetitions, reason = "struct should be called 'PixelNode' as it is standard.")]
 #[non_exhaustive]
 pub struct PixelNode {
@@ -17,8 +18,12 @@ pub struct PixelNode {
     pub x: f64,
     /// The y-coordinate of the pixel on the texture.
     pub y: f64,
-    /// The color of the pixel. This (in the future) may have an alpha channel.
+    /// The color of the pixel. Its alpha channel is blended over the existing
+    /// pixel when drawn.
     pub color: Color,
+    /// How `color` is combined with the existing pixel when drawn.
+    /// Defaults to `BlendMode::SourceOver`.
+    pub blend_mode: BlendMode,
 }
 
 impl PixelNode {
@@ -26,19 +31,17 @@ impl PixelNode {
     /// the pixel's `color`.
     #[must_use]
     pub const fn new(x: f64, y: f64, color: Color) -> PixelNode {
-        PixelNode { x, y, color }
+        PixelNode { x, y, color, blend_mode: BlendMode::SourceOver }
     }
 }
 
 impl DrawComponent for PixelNode {
     fn draw(&self, texture: &mut crate::Texture) {
-        #[expect(clippy::as_conversions, clippy::cast_possible_truncation, clippy::cast_sign_loss,
-            reason = "you cannot .into an f64 to a usize")]
-        texture.set_pixel(self.x as usize, self.y as usize, self.color).unwrap_or(());
-        // TODO: error handling
-        // TODO: consider the alpha channel of `self.color` and blend between
-        // the existing color at (x,y) and the new color, depending on the alpha
-        // channel.
+        // pixels off the texture, including at negative coordinates, are
+        // not drawn
+        if let (Ok(x), Ok(y)) = (cast::usize(self.x.floor()), cast::usize(self.y.floor())) {
+            texture.draw_pixel(x, y, self.color, self.blend_mode).unwrap_or(());
+        }
     }
 }
 
@@ -70,3 +73,13 @@ impl FillColorComponent for PixelNode {
     }
 }
 
+
+impl BlendModeComponent for PixelNode {
+    fn get_blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+}
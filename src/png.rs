@@ -0,0 +1,112 @@
+//! PNG loading and saving for `Texture`.
+//!
+//! This module is only available when the `png` cargo feature is enabled, as
+//! it depends on the `png` crate:
+//! ```toml
+//! pixl = { version = "0.2", features = ["png"] }
+//! ```
+//! RGBA, RGB, grayscale (with or without alpha) and palette PNGs of any bit
+//! depth can be loaded. Textures are always saved as 8-bit RGBA PNGs.
+
+use std::{fs, io::Cursor, path::Path};
+
+use anyhow::{Context as _, Error};
+use ::png::{BitDepth, ColorType, Decoder, Encoder, Transformations};
+
+use crate::{Color, Texture};
+
+#[expect(clippy::multiple_inherent_impl, reason = "PNG support is kept in its own module behind a feature")]
+impl Texture {
+    /// Loads a PNG image file into a new `Texture`.
+    /// # Arguments
+    /// * `path` - The path of the PNG file to load.
+    /// # Returns
+    /// A `Texture` with the same size and pixels as the image.
+    /// # Errors
+    /// Errors if the file cannot be read or is not a valid PNG image.
+    /// # Example
+    /// ```rust
+    /// let sprite = Texture::load_png("assets/player.png")
+    ///     .expect("failed to load player sprite");
+    /// ```
+    pub fn load_png<P: AsRef<Path>>(path: P) -> Result<Texture, Error> {
+        let bytes = fs::read(path).context("Pixl: load_png: failed to read file")?;
+        Texture::decode_png(&bytes)
+    }
+
+    /// Saves the texture to a PNG image file, as 8-bit RGBA.
+    /// # Arguments
+    /// * `path` - The path of the PNG file to create or overwrite.
+    /// # Errors
+    /// Errors if the image cannot be encoded or the file cannot be written.
+    /// # Example
+    /// ```rust
+    /// texture.save_png("screenshot.png")
+    ///     .expect("failed to save screenshot");
+    /// ```
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        fs::write(path, self.encode_png()?).context("Pixl: save_png: failed to write file")
+    }
+
+    /// Decodes an in-memory PNG image into a new `Texture`.
+    /// Palette, grayscale and 16-bit images are converted to 8-bit RGBA
+    /// colors, including any transparency (`tRNS`) information.
+    /// # Arguments
+    /// * `bytes` - The contents of a PNG file.
+    /// # Returns
+    /// A `Texture` with the same size and pixels as the image.
+    /// # Errors
+    /// Errors if `bytes` is not a valid PNG image.
+    /// # Example
+    /// ```rust
+    /// let icon = Texture::decode_png(include_bytes!("icon.png"))
+    ///     .expect("invalid icon");
+    /// ```
+    pub fn decode_png(bytes: &[u8]) -> Result<Texture, Error> {
+        let mut decoder = Decoder::new(Cursor::new(bytes));
+        decoder.set_transformations(Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info().context("Pixl: decode_png: invalid PNG header")?;
+        let mut buffer = vec![0; reader.output_buffer_size()
+            .ok_or_else(|| Error::msg("Pixl: decode_png: image is too large"))?];
+        let info = reader.next_frame(&mut buffer).context("Pixl: decode_png: invalid PNG image data")?;
+        let pixels = buffer.get(..info.buffer_size())
+            .ok_or_else(|| Error::msg("Pixl: decode_png: image data was truncated"))?
+            .chunks_exact(info.color_type.samples())
+            .map(|chunk| match *chunk {
+                [gray] => Color::rgb(gray, gray, gray),
+                [gray, a] => Color::rgba(gray, gray, gray, a),
+                [r, g, b] => Color::rgb(r, g, b),
+                [r, g, b, a] => Color::rgba(r, g, b, a),
+                // every color type has between 1 and 4 samples per pixel
+                _ => Color::rgba(0, 0, 0, 0),
+            })
+            .collect();
+        Texture::from_pixels(cast::usize(info.width), cast::usize(info.height), pixels)
+    }
+
+    /// Encodes the texture as an in-memory 8-bit RGBA PNG image.
+    /// # Returns
+    /// The contents of the PNG file.
+    /// # Errors
+    /// Errors if the texture is too large to be stored in a PNG image.
+    /// # Example
+    /// ```rust
+    /// let bytes = texture.encode_png().expect("failed to encode texture");
+    /// ```
+    pub fn encode_png(&self) -> Result<Vec<u8>, Error> {
+        let width = u32::try_from(self.get_width()).context("Pixl: encode_png: texture is too wide")?;
+        let height = u32::try_from(self.get_height()).context("Pixl: encode_png: texture is too tall")?;
+        let data: Vec<u8> = self.get_pixels().iter()
+            .flat_map(|pixel| [pixel.r, pixel.g, pixel.b, pixel.a])
+            .collect();
+
+        let mut bytes = Vec::new();
+        let mut encoder = Encoder::new(&mut bytes, width, height);
+        encoder.set_color(ColorType::Rgba);
+        encoder.set_depth(BitDepth::Eight);
+        let mut writer = encoder.write_header().context("Pixl: encode_png: failed to write PNG header")?;
+        writer.write_image_data(&data).context("Pixl: encode_png: failed to write PNG image data")?;
+        writer.finish().context("Pixl: encode_png: failed to finish PNG image")?;
+        Ok(bytes)
+    }
+}
@@ -0,0 +1,179 @@
+//! `PolygonNode` struct - represents a node for a filled shape with straight
+//! sides, such as terrain, a speech bubble's tail or a star.
+
+use crate::{BlendMode, Color, Texture, component::{BlendModeComponent, DrawComponent, FillColorComponent, StrokeColorComponent, StrokeWidthComponent}, line, raster::{self, Segment}, stroke::{self, DashPattern, LineCap, LineJoin}};
+
+/// How the inside of a shape is decided where its outline crosses itself, or
+/// where several of its contours overlap.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FillRule {
+    /// A point is inside if the outline goes around it more times clockwise
+    /// than anticlockwise, or the other way round. Overlapping contours going
+    /// the same way are filled once, and a contour going the opposite way to
+    /// the one around it cuts a hole.
+    #[default]
+    NonZero,
+    /// A point is inside if the outline goes around it an odd number of
+    /// times. Any contour inside another cuts a hole, whichever way it goes.
+    EvenOdd,
+}
+
+/// A node representing a polygon: a shape made of one or more closed
+/// contours of straight sides. Polygons can be concave, cross themselves and
+/// have holes, with `fill_rule` deciding which parts are inside.
+/// Coordinates are the corners between pixels, as with `RectangleNode`, so a
+/// polygon with corners at (0, 0) and (10, 10) covers 10 by 10 pixels.
+/// It can be given an outline, centered on its sides, by setting
+/// `stroke_width`.
+/// ## Implemented components:
+/// - `DrawComponent`
+/// - `FillColorComponent`
+/// - `StrokeColorComponent`
+/// - `StrokeWidthComponent`
+/// - `BlendModeComponent`
+/// # Example
+/// ```rust
+/// let mut window = PolygonNode::new(vec![(10.0, 10.0), (50.0, 10.0), (50.0, 40.0), (10.0, 40.0)], Color::BLUE);
+/// // a hole for the glass, cut out by the even-odd rule
+/// window.contours.push(vec![(14.0, 14.0), (46.0, 14.0), (46.0, 36.0), (14.0, 36.0)]);
+/// window.fill_rule = FillRule::EvenOdd;
+/// window.anti_aliased = true;
+/// texture.add(&window);
+/// ```
+#[non_exhaustive]
+#[expect(clippy::module_name_repetitions, reason = "struct should be called 'PolygonNode' as it is standard.")]
+pub struct PolygonNode {
+    /// The corners of each contour of the polygon, in order. Every contour is
+    /// closed, with its last corner joined back up to its first.
+    pub contours: Vec<Vec<(f64, f64)>>,
+
+    /// The color of the inside of the polygon. Its alpha channel is blended
+    /// over the existing pixels.
+    pub fill_color: Color,
+
+    /// Whether the inside of the polygon is filled with `fill_color`.
+    /// Defaults to true.
+    pub filled: bool,
+
+    /// How the inside of the polygon is decided where contours overlap or
+    /// cross themselves. Defaults to `FillRule::NonZero`.
+    pub fill_rule: FillRule,
+
+    /// The color of the polygon's outline.
+    /// Defaults to the same color as `fill_color`.
+    pub stroke_color: Color,
+
+    /// The width of the polygon's outline, in pixels. Defaults to 0, which
+    /// draws no outline.
+    pub stroke_width: usize,
+
+    /// The shape drawn at the corners of the outline.
+    /// Defaults to `LineJoin::Miter`.
+    pub line_join: LineJoin,
+
+    /// The pattern of dashes the outline is drawn with, or `None` to draw it
+    /// solid. Defaults to `None`.
+    pub dash_pattern: Option<DashPattern>,
+
+    /// Whether the polygon is drawn with smooth, anti-aliased edges.
+    /// Defaults to false.
+    pub anti_aliased: bool,
+
+    /// How the fill and stroke colors are combined with the existing pixels
+    /// when drawn. Defaults to `BlendMode::SourceOver`.
+    pub blend_mode: BlendMode,
+}
+
+impl PolygonNode {
+    /// Create a new `PolygonNode` with a single contour.
+    /// # Parameters
+    /// - `points`: The corners of the polygon, in order.
+    /// - `fill_color`: The color to fill the polygon with.
+    /// # Returns
+    /// A filled `PolygonNode` with no outline. More contours can be added to
+    /// `contours` afterwards.
+    #[must_use]
+    pub fn new(points: Vec<(f64, f64)>, fill_color: Color) -> PolygonNode {
+        PolygonNode {
+            contours: vec![points],
+            fill_color,
+            filled: true,
+            fill_rule: FillRule::NonZero,
+            stroke_color: fill_color,
+            stroke_width: 0,
+            line_join: LineJoin::Miter,
+            dash_pattern: None,
+            anti_aliased: false,
+            blend_mode: BlendMode::SourceOver,
+        }
+    }
+}
+
+impl DrawComponent for PolygonNode {
+    fn draw(&self, texture: &mut Texture) {
+        if self.filled {
+            let segments: Vec<Segment> = self.contours.iter().flat_map(|contour| {
+                contour.iter().zip(contour.iter().cycle().skip(1)).map(|(&from, &to)| Segment::Line(from, to))
+            }).collect();
+            raster::fill(texture, &segments, self.fill_color, self.blend_mode, self.anti_aliased, self.fill_rule);
+        }
+        match self.stroke_width {
+            0 => {},
+            1 => {
+                // thin outlines are drawn as lines through the centers of
+                // pixels, which are halfway between the corners
+                for contour in &self.contours {
+                    let centered: Vec<(f64, f64)> = contour.iter().map(|&(x, y)| (x - 0.5f64, y - 0.5f64)).collect();
+                    line::draw_thin_dashed(texture, &centered, true, self.dash_pattern.as_ref(), self.stroke_color, self.blend_mode, self.anti_aliased);
+                }
+            },
+            width => {
+                let outline: Vec<Segment> = self.contours.iter().flat_map(|contour| {
+                    stroke::dashed_outline(contour, true, cast::f64(width), LineCap::Butt, self.line_join, self.dash_pattern.as_ref())
+                }).collect();
+                raster::fill(texture, &outline, self.stroke_color, self.blend_mode, self.anti_aliased, FillRule::NonZero);
+            },
+        }
+    }
+}
+
+impl FillColorComponent for PolygonNode {
+    fn get_fill_color(&self) -> &Color {
+        &self.fill_color
+    }
+
+    fn set_fill_color(&mut self, color: Color) {
+        self.fill_color = color;
+    }
+}
+
+impl StrokeColorComponent for PolygonNode {
+    fn get_stroke_color(&self) -> &Color {
+        &self.stroke_color
+    }
+
+    fn set_stroke_color(&mut self, color: Color) {
+        self.stroke_color = color;
+    }
+}
+
+impl StrokeWidthComponent for PolygonNode {
+    fn get_stroke_width(&self) -> usize {
+        self.stroke_width
+    }
+
+    fn set_stroke_width(&mut self, width: usize) {
+        self.stroke_width = width;
+    }
+}
+
+impl BlendModeComponent for PolygonNode {
+    fn get_blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+}
@@ -0,0 +1,131 @@
+//! `PolylineNode` struct - represents a node for a line through several
+//! points, such as a trail, a graph or a road.
+
+use crate::{BlendMode, Color, Texture, component::{BlendModeComponent, DrawComponent, StrokeColorComponent, StrokeWidthComponent}, line, polygon::FillRule, raster, stroke::{self, DashPattern, LineCap, LineJoin}};
+
+/// A node representing a line through a list of points, drawn as one shape so
+/// that translucent lines do not darken where their segments meet.
+/// Integer coordinates are the centers of pixels, as with `LineNode`.
+/// ## Implemented components:
+/// - `DrawComponent`
+/// - `StrokeColorComponent`
+/// - `StrokeWidthComponent`
+/// - `BlendModeComponent`
+/// # Example
+/// ```rust
+/// let mut trail = PolylineNode::new(vec![(10.0, 50.0), (30.0, 20.0), (60.0, 40.0)], Color::WHITE);
+/// trail.stroke_width = 4;
+/// trail.line_join = LineJoin::Round;
+/// trail.line_cap = LineCap::Round;
+/// texture.add(&trail);
+/// ```
+#[non_exhaustive]
+#[expect(clippy::module_name_repetitions, reason = "struct should be called 'PolylineNode' as it is standard.")]
+pub struct PolylineNode {
+    /// The (x, y) coordinates of every point the line passes through, in
+    /// order.
+    pub points: Vec<(f64, f64)>,
+
+    /// The color of the line. Its alpha channel is blended over the existing
+    /// pixels when drawn.
+    pub color: Color,
+
+    /// Whether the last point is joined back up to the first, making an
+    /// outline instead of a line with two ends. Defaults to false.
+    pub closed: bool,
+
+    /// Whether the line is drawn with smooth, anti-aliased edges.
+    /// Defaults to false.
+    pub anti_aliased: bool,
+
+    /// The width of the line, in pixels. Defaults to 1, and a width of 0
+    /// draws nothing.
+    pub stroke_width: usize,
+
+    /// The shape drawn at the two ends of the line, unless it is closed.
+    /// Defaults to `LineCap::Butt`.
+    pub line_cap: LineCap,
+
+    /// The shape drawn at the corners where the segments of the line meet.
+    /// Defaults to `LineJoin::Miter`.
+    pub line_join: LineJoin,
+
+    /// The pattern of dashes the line is drawn with, or `None` to draw it
+    /// solid. Every dash is given the line's caps. Defaults to `None`.
+    pub dash_pattern: Option<DashPattern>,
+
+    /// How the line's color is combined with the existing pixels when drawn.
+    /// Defaults to `BlendMode::SourceOver`.
+    pub blend_mode: BlendMode,
+}
+
+impl PolylineNode {
+    /// Create a new `PolylineNode` through the specified points.
+    /// # Parameters
+    /// - `points`: The (x, y) coordinates of every point the line passes
+    ///   through, in order.
+    /// - `color`: The stroke color of the line.
+    /// # Returns
+    /// A new, open `PolylineNode` one pixel wide.
+    #[must_use]
+    pub const fn new(points: Vec<(f64, f64)>, color: Color) -> PolylineNode {
+        PolylineNode {
+            points, color,
+            closed: false,
+            anti_aliased: false,
+            stroke_width: 1,
+            line_cap: LineCap::Butt,
+            line_join: LineJoin::Miter,
+            dash_pattern: None,
+            blend_mode: BlendMode::SourceOver,
+        }
+    }
+}
+
+impl DrawComponent for PolylineNode {
+    fn draw(&self, texture: &mut Texture) {
+        match self.stroke_width {
+            0 => {},
+            1 => {
+                line::draw_thin_dashed(texture, &self.points, self.closed, self.dash_pattern.as_ref(), self.color, self.blend_mode, self.anti_aliased);
+            },
+            width => {
+                // the outline is filled in texture coordinates, where the
+                // centers of pixels are halfway between integers
+                let points: Vec<(f64, f64)> = self.points.iter().map(|&(x, y)| (x + 0.5f64, y + 0.5f64)).collect();
+                let outline = stroke::dashed_outline(&points, self.closed, cast::f64(width), self.line_cap, self.line_join, self.dash_pattern.as_ref());
+                raster::fill(texture, &outline, self.color, self.blend_mode, self.anti_aliased, FillRule::NonZero);
+            },
+        }
+    }
+}
+
+impl StrokeColorComponent for PolylineNode {
+    fn get_stroke_color(&self) -> &Color {
+        &self.color
+    }
+
+    fn set_stroke_color(&mut self, color: Color) {
+        self.color = color;
+    }
+}
+
+impl StrokeWidthComponent for PolylineNode {
+    fn get_stroke_width(&self) -> usize {
+        self.stroke_width
+    }
+
+    fn set_stroke_width(&mut self, width: usize) {
+        self.stroke_width = width;
+    }
+}
+
+impl BlendModeComponent for PolylineNode {
+    fn get_blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+}
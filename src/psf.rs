@@ -0,0 +1,208 @@
+//! Loading of PSF (PC Screen Font) fonts, the bitmap fonts used by the Linux
+//! console, in both the PSF1 and PSF2 formats.
+//!
+//! Fonts are usually installed compressed (`.psf.gz`), and must be
+//! decompressed before loading:
+//! ```rust
+//! // gunzip -k /usr/share/consolefonts/Lat2-Terminus16.psf.gz
+//! let font = PsfFont::load("Lat2-Terminus16.psf").unwrap();
+//! let status = TextNode::with_font(&font, "HP: 10/10", 4.0, 4.0, Color::GREEN);
+//! texture.add(&status);
+//! ```
+
+use std::{collections::HashMap, fs, path::Path};
+
+use anyhow::{Context as _, Error};
+
+use crate::{BlendMode, Color, Texture, font::{Font, draw_bitmap_glyph}, reader::ByteReader};
+
+/// The magic bytes at the start of a PSF1 font.
+const PSF1_MAGIC: [u8; 2] = [0x36, 0x04];
+
+/// The magic bytes at the start of a PSF2 font.
+const PSF2_MAGIC: [u8; 4] = [0x72, 0xB5, 0x4A, 0x86];
+
+/// A fixed-width bitmap font loaded from a PSF1 or PSF2 file.
+/// Characters missing from the font are drawn as `?`, or skipped if the font
+/// has no `?` either.
+#[non_exhaustive]
+#[expect(clippy::module_name_repetitions, reason = "the name 'Psf' alone would be unclear")]
+#[expect(clippy::partial_pub_fields, reason = "the glyph data only comes from loading a font")]
+pub struct PsfFont {
+    /// The width of every character in pixels.
+    width: usize,
+    /// The height of every character in pixels.
+    height: usize,
+    /// The bitmaps of every glyph, one after another.
+    glyphs: Vec<u8>,
+    /// The number of glyphs in the font.
+    glyph_count: usize,
+    /// The number of bytes used by each glyph in `glyphs`.
+    bytes_per_glyph: usize,
+    /// The number of bytes used by each row of a glyph.
+    bytes_per_row: usize,
+    /// The glyph drawn for each character, if the font has a Unicode table.
+    /// Otherwise, each character is drawn with the glyph at its code point.
+    characters: HashMap<char, usize>,
+    /// How many texture pixels wide and tall each pixel of the font is drawn.
+    /// Defaults to 1.
+    pub scale: usize,
+}
+
+impl PsfFont {
+    /// Loads a PSF1 or PSF2 font file. The file must not be compressed.
+    /// # Arguments
+    /// * `path` - The path of the `.psf` file to load.
+    /// # Returns
+    /// A `PsfFont` which can be used to draw text.
+    /// # Errors
+    /// Errors if the file cannot be read or is not a valid PSF font.
+    /// # Example
+    /// ```rust
+    /// let font = PsfFont::load("assets/fonts/terminus.psf")
+    ///     .expect("failed to load font");
+    /// ```
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<PsfFont, Error> {
+        PsfFont::decode(&fs::read(path).context("Pixl: PsfFont::load: failed to read file")?)
+    }
+
+    /// Decodes an in-memory PSF1 or PSF2 font.
+    /// # Arguments
+    /// * `bytes` - The contents of a `.psf` file.
+    /// # Returns
+    /// A `PsfFont` which can be used to draw text.
+    /// # Errors
+    /// Errors if `bytes` is not a valid PSF font.
+    /// # Example
+    /// ```rust
+    /// let font = PsfFont::decode(include_bytes!("console.psf"))
+    ///     .expect("invalid font");
+    /// ```
+    pub fn decode(bytes: &[u8]) -> Result<PsfFont, Error> {
+        let mut reader = ByteReader::new(bytes, "PsfFont::decode");
+        if bytes.starts_with(&PSF1_MAGIC) {
+            reader.skip(PSF1_MAGIC.len())?;
+            let mode = reader.u8()?;
+            let height = usize::from(reader.u8()?);
+            if height == 0 {
+                return Err(Error::msg("Pixl: PsfFont::decode: font had no height"));
+            }
+            let glyph_count = if mode & 0x01 == 0 { 256usize } else { 512usize };
+            let glyphs = reader.bytes(glyph_count.saturating_mul(height))?.to_vec();
+            let mut font = PsfFont {
+                width: 8, height, glyphs, glyph_count,
+                bytes_per_glyph: height,
+                bytes_per_row: 1,
+                characters: HashMap::new(),
+                scale: 1,
+            };
+            // the Unicode table holds each glyph's characters as 16-bit
+            // values, ending with 0xFFFF, and 0xFFFE starts a list of
+            // combining sequences which are not supported
+            if mode & 0x06 != 0 {
+                for glyph in 0..glyph_count {
+                    let mut in_sequence = false;
+                    loop {
+                        match reader.u16_le()? {
+                            0xFFFF => break,
+                            0xFFFE => in_sequence = true,
+                            value if !in_sequence => font.map_character(u32::from(value), glyph),
+                            _ => {},
+                        }
+                    }
+                }
+            }
+            Ok(font)
+        } else if bytes.starts_with(&PSF2_MAGIC) {
+            reader.skip(8)?; // magic and version
+            let header_size = cast::usize(reader.u32_le()?);
+            let flags = reader.u32_le()?;
+            let glyph_count = cast::usize(reader.u32_le()?);
+            let bytes_per_glyph = cast::usize(reader.u32_le()?);
+            let height = cast::usize(reader.u32_le()?);
+            let width = cast::usize(reader.u32_le()?);
+            if width == 0 || height == 0 || bytes_per_glyph == 0 {
+                return Err(Error::msg("Pixl: PsfFont::decode: font had no width or height"));
+            }
+            let bytes_per_row = width.div_ceil(8);
+            if bytes_per_row.checked_mul(height).is_none_or(|size| size > bytes_per_glyph) {
+                return Err(Error::msg("Pixl: PsfFont::decode: glyphs were too small for the font size"));
+            }
+            reader.seek(header_size)?;
+            let glyphs_size = glyph_count.checked_mul(bytes_per_glyph)
+                .ok_or_else(|| Error::msg("Pixl: PsfFont::decode: font was too large"))?;
+            let glyphs = reader.bytes(glyphs_size)?.to_vec();
+            let mut font = PsfFont {
+                width, height, glyphs, glyph_count, bytes_per_glyph, bytes_per_row,
+                characters: HashMap::new(),
+                scale: 1,
+            };
+            // the Unicode table holds each glyph's characters as UTF-8,
+            // ending with 0xFF, and 0xFE starts a list of combining sequences
+            // which are not supported
+            if flags & 0x01 != 0 {
+                let table = reader.bytes(bytes.len().saturating_sub(reader.position()))?;
+                for (glyph, entry) in table.split(|&byte| byte == 0xFF).take(glyph_count).enumerate() {
+                    let characters = entry.split(|&byte| byte == 0xFE).next().unwrap_or_default();
+                    for character in String::from_utf8_lossy(characters).chars() {
+                        font.map_character(u32::from(character), glyph);
+                    }
+                }
+            }
+            Ok(font)
+        } else {
+            Err(Error::msg("Pixl: PsfFont::decode: not a PSF font"))
+        }
+    }
+
+    /// Gets the size of every character, before scaling.
+    /// # Returns
+    /// The (width, height) of a character in pixels.
+    #[must_use]
+    pub const fn get_glyph_size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// Draws `glyph` for `code_point`, unless an earlier glyph already
+    /// claimed it.
+    fn map_character(&mut self, code_point: u32, glyph: usize) {
+        if let Some(character) = char::from_u32(code_point) {
+            self.characters.entry(character).or_insert(glyph);
+        }
+    }
+
+    /// Gets the index of the glyph drawn for `character`.
+    fn glyph_index(&self, character: char) -> Option<usize> {
+        let lookup = |candidate: char| if self.characters.is_empty() {
+            Some(cast::usize(u32::from(candidate))).filter(|&index| index < self.glyph_count)
+        } else {
+            self.characters.get(&candidate).copied()
+        };
+        lookup(character).or_else(|| lookup('?'))
+    }
+}
+
+impl Font for PsfFont {
+    fn get_line_height(&self) -> f64 {
+        cast::f64(self.height.saturating_mul(self.scale))
+    }
+
+    fn get_advance(&self, _character: char) -> f64 {
+        cast::f64(self.width.saturating_mul(self.scale))
+    }
+
+    fn draw_char(&self, texture: &mut Texture, character: char, x: f64, y: f64, color: Color, blend_mode: BlendMode) {
+        let Some(glyph) = self.glyph_index(character)
+            .and_then(|index| self.glyphs.chunks_exact(self.bytes_per_glyph).nth(index)) else {
+            return;
+        };
+        // each row starts on a new byte, with the leftmost pixel in the
+        // highest bit
+        draw_bitmap_glyph(texture, (x, y), (self.width, self.height), self.scale, color, blend_mode, |column, row| {
+            let byte = row.checked_mul(self.bytes_per_row)
+                .and_then(|start| start.checked_add(column / 8))
+                .and_then(|index| glyph.get(index));
+            byte.is_some_and(|bits| (bits << (column % 8)) & 0x80 != 0)
+        });
+    }
+}
@@ -0,0 +1,235 @@
+//! QOI ("Quite OK Image") loading and saving for `Texture`, with no extra
+//! dependencies.
+//!
+//! QOI is a lossless format which is extremely simple and fast to encode,
+//! making it a good fit for saving frame captures every frame.
+//! See <https://qoiformat.org> for the specification.
+
+use std::{fs, path::Path};
+
+use anyhow::{Context as _, Error};
+
+use crate::{Color, Texture, reader::ByteReader};
+
+/// The `QOI_OP_INDEX` tag: the pixel is a previously seen pixel.
+const OP_INDEX: u8 = 0b0000_0000;
+/// The `QOI_OP_DIFF` tag: the pixel differs slightly from the previous one.
+const OP_DIFF: u8 = 0b0100_0000;
+/// The `QOI_OP_LUMA` tag: the pixel differs from the previous one, mostly in
+/// brightness.
+const OP_LUMA: u8 = 0b1000_0000;
+/// The `QOI_OP_RUN` tag: the previous pixel is repeated.
+const OP_RUN: u8 = 0b1100_0000;
+/// The `QOI_OP_RGB` tag: a full RGB pixel follows.
+const OP_RGB: u8 = 0b1111_1110;
+/// The `QOI_OP_RGBA` tag: a full RGBA pixel follows.
+const OP_RGBA: u8 = 0b1111_1111;
+/// The mask for the 2-bit tags.
+const TAG_MASK: u8 = 0b1100_0000;
+/// The longest run a single `QOI_OP_RUN` can encode.
+const MAX_RUN: u8 = 62;
+/// The bytes which mark the end of a QOI file.
+const END_MARKER: [u8; 8] = [0, 0, 0, 0, 0, 0, 0, 1];
+
+#[expect(clippy::multiple_inherent_impl, reason = "each image format is kept in its own module")]
+impl Texture {
+    /// Loads a QOI image file into a new `Texture`.
+    /// # Arguments
+    /// * `path` - The path of the QOI file to load.
+    /// # Returns
+    /// A `Texture` with the same size and pixels as the image.
+    /// # Errors
+    /// Errors if the file cannot be read or is not a valid QOI image.
+    /// # Example
+    /// ```rust
+    /// let background = Texture::load_qoi("assets/background.qoi")
+    ///     .expect("failed to load background");
+    /// ```
+    pub fn load_qoi<P: AsRef<Path>>(path: P) -> Result<Texture, Error> {
+        let bytes = fs::read(path).context("Pixl: load_qoi: failed to read file")?;
+        Texture::decode_qoi(&bytes)
+    }
+
+    /// Saves the texture to a QOI image file, including its alpha channel.
+    /// # Arguments
+    /// * `path` - The path of the QOI file to create or overwrite.
+    /// # Errors
+    /// Errors if the texture is too large for a QOI image, or the file cannot
+    /// be written.
+    /// # Example
+    /// ```rust
+    /// texture.save_qoi(format!("capture/frame{frame:05}.qoi"))
+    ///     .expect("failed to save frame");
+    /// ```
+    pub fn save_qoi<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        fs::write(path, self.encode_qoi()?).context("Pixl: save_qoi: failed to write file")
+    }
+
+    /// Decodes an in-memory QOI image into a new `Texture`.
+    /// # Arguments
+    /// * `bytes` - The contents of a QOI file.
+    /// # Returns
+    /// A `Texture` with the same size and pixels as the image.
+    /// # Errors
+    /// Errors if `bytes` is not a valid QOI image.
+    /// # Example
+    /// ```rust
+    /// let logo = Texture::decode_qoi(include_bytes!("logo.qoi"))
+    ///     .expect("invalid logo");
+    /// ```
+    pub fn decode_qoi(bytes: &[u8]) -> Result<Texture, Error> {
+        let mut reader = ByteReader::new(bytes, "decode_qoi");
+        if reader.array()? != *b"qoif" {
+            return Err(Error::msg("Pixl: decode_qoi: missing QOI signature"));
+        }
+        let width = cast::usize(reader.u32_be()?);
+        let height = cast::usize(reader.u32_be()?);
+        // the channel count and color space don't affect decoding
+        reader.skip(2)?;
+        let pixel_count = width.checked_mul(height)
+            .ok_or_else(|| Error::msg("Pixl: decode_qoi: image is too large"))?;
+
+        // a single byte can encode at most MAX_RUN pixels, so this stops a
+        // corrupt header from allocating huge amounts of memory
+        let mut pixels = Vec::with_capacity(pixel_count.min(bytes.len().saturating_mul(usize::from(MAX_RUN))));
+        let mut seen = [Color::rgba(0, 0, 0, 0); 64];
+        let mut pixel = Color::rgba(0, 0, 0, 255);
+        let mut run = 0u8;
+        while pixels.len() < pixel_count {
+            if run > 0 {
+                run = run.saturating_sub(1);
+                pixels.push(pixel);
+                continue;
+            }
+            let byte = reader.u8()?;
+            match byte {
+                OP_RGB => {
+                    let [r, g, b] = reader.array()?;
+                    pixel = Color::rgba(r, g, b, pixel.a);
+                },
+                OP_RGBA => {
+                    let [r, g, b, a] = reader.array()?;
+                    pixel = Color::rgba(r, g, b, a);
+                },
+                _ => match byte & TAG_MASK {
+                    OP_INDEX => pixel = seen.get(usize::from(byte)).copied().unwrap_or(pixel),
+                    OP_DIFF => {
+                        pixel.r = pixel.r.wrapping_add((byte >> 4) & 0b11).wrapping_sub(2);
+                        pixel.g = pixel.g.wrapping_add((byte >> 2) & 0b11).wrapping_sub(2);
+                        pixel.b = pixel.b.wrapping_add(byte & 0b11).wrapping_sub(2);
+                    },
+                    OP_LUMA => {
+                        let second = reader.u8()?;
+                        let green_diff = (byte & 0b0011_1111).wrapping_sub(32);
+                        pixel.r = pixel.r.wrapping_add(green_diff).wrapping_add(second >> 4).wrapping_sub(8);
+                        pixel.g = pixel.g.wrapping_add(green_diff);
+                        pixel.b = pixel.b.wrapping_add(green_diff).wrapping_add(second & 0b1111).wrapping_sub(8);
+                    },
+                    // OP_RUN, the only remaining tag
+                    _ => run = byte & !TAG_MASK,
+                },
+            }
+            if let Some(slot) = seen.get_mut(hash(pixel)) {
+                *slot = pixel;
+            }
+            pixels.push(pixel);
+        }
+        Texture::from_pixels(width, height, pixels)
+    }
+
+    /// Encodes the texture as an in-memory QOI image, including its alpha
+    /// channel.
+    /// # Returns
+    /// The contents of the QOI file.
+    /// # Errors
+    /// Errors if the texture is too large to be stored in a QOI image.
+    /// # Example
+    /// ```rust
+    /// let bytes = texture.encode_qoi().expect("failed to encode texture");
+    /// ```
+    #[expect(clippy::big_endian_bytes, reason = "QOI files are big-endian")]
+    pub fn encode_qoi(&self) -> Result<Vec<u8>, Error> {
+        let width = u32::try_from(self.get_width()).context("Pixl: encode_qoi: texture is too wide")?;
+        let height = u32::try_from(self.get_height()).context("Pixl: encode_qoi: texture is too tall")?;
+
+        let mut bytes = Vec::with_capacity(self.get_pixels().len().saturating_mul(2));
+        bytes.extend_from_slice(b"qoif");
+        bytes.extend_from_slice(&width.to_be_bytes());
+        bytes.extend_from_slice(&height.to_be_bytes());
+        // 4 channels (RGBA), in the sRGB color space
+        bytes.extend_from_slice(&[4, 0]);
+
+        let mut seen = [Color::rgba(0, 0, 0, 0); 64];
+        let mut previous = Color::rgba(0, 0, 0, 255);
+        let mut run = 0u8;
+        for &pixel in self.get_pixels() {
+            if pixel == previous {
+                run = run.saturating_add(1);
+                if run == MAX_RUN {
+                    bytes.push(OP_RUN | run.saturating_sub(1));
+                    run = 0;
+                }
+                continue;
+            }
+            if run > 0 {
+                bytes.push(OP_RUN | run.saturating_sub(1));
+                run = 0;
+            }
+
+            let index = hash(pixel);
+            if seen.get(index).is_some_and(|&seen_pixel| seen_pixel == pixel) {
+                bytes.push(OP_INDEX | cast::u8(index).unwrap_or(0));
+            }
+            else {
+                if let Some(slot) = seen.get_mut(index) {
+                    *slot = pixel;
+                }
+                encode_difference(&mut bytes, pixel, previous);
+            }
+            previous = pixel;
+        }
+        if run > 0 {
+            bytes.push(OP_RUN | run.saturating_sub(1));
+        }
+        bytes.extend_from_slice(&END_MARKER);
+        Ok(bytes)
+    }
+}
+
+/// Encodes a pixel which isn't a repeat of the previous pixel and isn't in
+/// the index of seen pixels, using the smallest possible operation.
+#[expect(clippy::single_call_fn, reason = "kept separate from encode_qoi for readability")]
+fn encode_difference(bytes: &mut Vec<u8>, pixel: Color, previous: Color) {
+    if pixel.a != previous.a {
+        bytes.extend_from_slice(&[OP_RGBA, pixel.r, pixel.g, pixel.b, pixel.a]);
+        return;
+    }
+    // the differences wrap around, and are biased so they are never negative
+    let red = pixel.r.wrapping_sub(previous.r);
+    let green = pixel.g.wrapping_sub(previous.g);
+    let blue = pixel.b.wrapping_sub(previous.b);
+    let small = |diff: u8| diff.wrapping_add(2) < 4;
+    if small(red) && small(green) && small(blue) {
+        bytes.push(OP_DIFF | (red.wrapping_add(2) << 4u8) | (green.wrapping_add(2) << 2u8) | blue.wrapping_add(2));
+        return;
+    }
+    let red_green = red.wrapping_sub(green).wrapping_add(8);
+    let blue_green = blue.wrapping_sub(green).wrapping_add(8);
+    if green.wrapping_add(32) < 64 && red_green < 16 && blue_green < 16 {
+        bytes.extend_from_slice(&[OP_LUMA | green.wrapping_add(32), (red_green << 4u8) | blue_green]);
+        return;
+    }
+    bytes.extend_from_slice(&[OP_RGB, pixel.r, pixel.g, pixel.b]);
+}
+
+/// Gets the position of a color in the index of previously seen pixels.
+fn hash(color: Color) -> usize {
+    usize::from(
+        color.r.wrapping_mul(3)
+            .wrapping_add(color.g.wrapping_mul(5))
+            .wrapping_add(color.b.wrapping_mul(7))
+            .wrapping_add(color.a.wrapping_mul(11))
+            & 0b0011_1111
+    )
+}
+
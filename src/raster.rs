@@ -0,0 +1,287 @@
+//! A coverage-based scanline rasterizer, which fills shapes made of lines and
+//! curves with smooth, anti-aliased edges.
+//!
+//! Rather than testing whether the center of each pixel is inside the shape,
+//! every edge adds the exact area it covers to an accumulation buffer. Summing
+//! the buffer along each row then gives the fraction of every pixel covered by
+//! the shape.
+
+use crate::{BlendMode, Color, Texture, polygon::FillRule, transform::Transform};
+
+/// A point, as (x, y) coordinates in pixels.
+pub type Point = (f64, f64);
+
+/// The most straight lines a single curve is split into.
+const MAX_CURVE_STEPS: usize = 1024;
+
+/// How far aliased shapes are moved right and down before being filled, so
+/// that edges passing exactly through the centers of pixels fill only one of
+/// the pixels on either side of them.
+const ALIASED_BIAS: f64 = 1.0 / 256.0;
+
+/// One piece of the outline of a shape.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Segment {
+    /// A straight line from the first point to the second.
+    Line(Point, Point),
+    /// A quadratic Bézier curve from the first point to the third, pulled
+    /// towards the second.
+    Quadratic(Point, Point, Point),
+    /// A cubic Bézier curve from the first point to the fourth, pulled
+    /// towards the second and third.
+    Cubic(Point, Point, Point, Point),
+}
+
+impl Segment {
+    /// Moves every point of the segment, including its control points, by
+    /// `transform`.
+    pub const fn transformed(self, transform: Transform) -> Segment {
+        match self {
+            Segment::Line(from, to) => Segment::Line(apply(transform, from), apply(transform, to)),
+            Segment::Quadratic(from, control, to) => Segment::Quadratic(
+                apply(transform, from), apply(transform, control), apply(transform, to),
+            ),
+            Segment::Cubic(from, control_1, control_2, to) => Segment::Cubic(
+                apply(transform, from), apply(transform, control_1), apply(transform, control_2), apply(transform, to),
+            ),
+        }
+    }
+
+    /// Gets every point of the segment, including its control points. Unused
+    /// points are repeats of the first one.
+    pub const fn points(self) -> [Point; 4] {
+        match self {
+            Segment::Line(from, to) => [from, to, from, from],
+            Segment::Quadratic(from, control, to) => [from, control, to, from],
+            Segment::Cubic(from, control_1, control_2, to) => [from, control_1, control_2, to],
+        }
+    }
+
+    /// Splits the segment into straight lines, using more lines the more
+    /// sharply it bends so that curves look smooth at any size.
+    /// `add` is given the end of each line in order, starting with the end of
+    /// the first line rather than the start of the segment.
+    pub fn flatten<F: FnMut(Point)>(self, mut add: F) {
+        match self {
+            Segment::Line(_, to) => add(to),
+            Segment::Quadratic(from, control, to) => {
+                let deviation = distance(from, control, control, to);
+                curve((3.0f64 * deviation).sqrt(), |t| {
+                    let inverse = 1.0f64 - t;
+                    mix3(from, control, to, inverse * inverse, 2.0 * inverse * t, t * t)
+                }, add);
+            },
+            Segment::Cubic(from, control_1, control_2, to) => {
+                let deviation = distance(from, control_1, control_1, control_2)
+                    .max(distance(control_1, control_2, control_2, to));
+                curve((8.0f64 * deviation).sqrt(), |t| {
+                    let inverse = 1.0f64 - t;
+                    let (start_x, start_y) = mix3(from, control_1, control_2, inverse * inverse * inverse, 3.0 * inverse * inverse * t, 3.0 * inverse * t * t);
+                    (to.0.mul_add(t * t * t, start_x), to.1.mul_add(t * t * t, start_y))
+                }, add);
+            },
+        }
+    }
+}
+
+/// Gets the smallest box containing every point of `segments`, including
+/// their control points, as its (top-left, bottom-right) corners.
+/// Returns `None` if there are no segments.
+pub fn bounds(segments: &[Segment]) -> Option<(Point, Point)> {
+    segments.iter().flat_map(|segment| segment.points()).fold(None, |bounds, (x, y)| {
+        Some(bounds.map_or(((x, y), (x, y)), |((left, top), (right, bottom)): (Point, Point)| {
+            ((left.min(x), top.min(y)), (right.max(x), bottom.max(y)))
+        }))
+    })
+}
+
+/// Fills the shapes outlined by `segments` onto a texture, deciding what is
+/// inside them by `fill_rule`. If `anti_aliased` is true, each pixel is
+/// blended by how much of it is covered, and otherwise pixels are drawn fully
+/// if more than half covered and not at all if not.
+/// Only the part of the shapes on the texture is rasterized.
+pub fn fill(texture: &mut Texture, segments: &[Segment], color: Color, blend_mode: BlendMode, anti_aliased: bool, fill_rule: FillRule) {
+    let Some(((left, top), (right, bottom))) = bounds(segments) else {
+        return;
+    };
+    let pixel_left = left.floor().max(0.0);
+    let pixel_top = top.floor().max(0.0);
+    let pixel_right = right.ceil().min(cast::f64(texture.get_width()));
+    let pixel_bottom = bottom.ceil().min(cast::f64(texture.get_height()));
+    let (Ok(first_x), Ok(first_y), Ok(width), Ok(height)) = (
+        cast::usize(pixel_left), cast::usize(pixel_top),
+        cast::usize(pixel_right - pixel_left), cast::usize(pixel_bottom - pixel_top),
+    ) else {
+        return;
+    };
+    let bias = if anti_aliased { 0.0f64 } else { ALIASED_BIAS };
+    let offset = Transform::translation(bias - pixel_left, bias - pixel_top);
+    let mut rasterizer = Rasterizer::new(width, height);
+    for &segment in segments {
+        rasterizer.add(segment.transformed(offset));
+    }
+    for (index, coverage) in rasterizer.coverage(fill_rule).into_iter().enumerate() {
+        let alpha = if anti_aliased {
+            coverage
+        } else if coverage > 0.5f64 {
+            1.0f64
+        } else {
+            0.0f64
+        };
+        if let (true, Some(column), Some(row)) = (alpha > 0.0f64, index.checked_rem(width), index.checked_div(width)) {
+            texture.draw_pixel(first_x.saturating_add(column), first_y.saturating_add(row), color.multiply_alpha(alpha), blend_mode)
+                .unwrap_or(());
+        }
+    }
+}
+
+/// Fills shapes into a `width` by `height` grid of coverage values.
+/// Anything outside of the grid is clipped.
+pub struct Rasterizer {
+    /// The width of the grid in pixels.
+    width: usize,
+    /// The height of the grid in pixels.
+    height: usize,
+    /// How much the coverage changes at each pixel, row by row. It has one
+    /// extra value, as an edge on the right of the last row changes the
+    /// coverage just after it.
+    accumulation: Vec<f64>,
+}
+
+impl Rasterizer {
+    /// Creates a new `Rasterizer` with nothing filled.
+    pub fn new(width: usize, height: usize) -> Rasterizer {
+        Rasterizer {
+            width, height,
+            accumulation: vec![0.0; width.saturating_mul(height).saturating_add(1)],
+        }
+    }
+
+    /// Adds one piece of an outline. Shapes must be closed, with the last
+    /// segment of every contour ending where the first one starts.
+    pub fn add(&mut self, segment: Segment) {
+        let [mut previous, ..] = segment.points();
+        segment.flatten(|point| {
+            self.line(previous, point);
+            previous = point;
+        });
+    }
+
+    /// Gets the fraction of every pixel covered by the shapes added so far,
+    /// from 0.0 to 1.0, row by row. `fill_rule` decides whether pixels where
+    /// shapes overlap are covered once or, for an even number of overlaps,
+    /// not at all.
+    pub fn coverage(&self, fill_rule: FillRule) -> Vec<f64> {
+        let mut total = 0.0f64;
+        self.accumulation.iter().take(self.width.saturating_mul(self.height)).map(|&change| {
+            total += change;
+            match fill_rule {
+                FillRule::NonZero => total.abs().min(1.0),
+                // the total counts how many times the pixel is covered, so
+                // odd counts are inside and even counts are outside, with
+                // partly covered pixels in between
+                FillRule::EvenOdd => {
+                    let count = total.rem_euclid(2.0f64);
+                    if count > 1.0f64 { 2.0f64 - count } else { count }
+                },
+            }
+        }).collect()
+    }
+
+    /// Adds a straight edge, row by row.
+    fn line(&mut self, from: Point, to: Point) {
+        let (top, bottom, direction) = if from.1 < to.1 { (from, to, 1.0f64) } else { (to, from, -1.0f64) };
+        let height = bottom.1 - top.1;
+        if height.is_nan() || height <= 0.0f64 {
+            return;
+        }
+        let slope = (bottom.0 - top.0) / height;
+        let width = cast::f64(self.width);
+        let first_row = cast::usize(top.1.max(0.0).floor()).unwrap_or(0);
+        let end_row = cast::usize(bottom.1.max(0.0).ceil()).unwrap_or(0).min(self.height);
+        for row in first_row..end_row {
+            let row_top = cast::f64(row).max(top.1);
+            let row_bottom = cast::f64(row.saturating_add(1)).min(bottom.1);
+            // anything left of the grid still covers the whole row, so edges
+            // are clamped to it rather than dropped
+            let top_x = (row_top - top.1).mul_add(slope, top.0).clamp(0.0, width);
+            let bottom_x = (row_bottom - top.1).mul_add(slope, top.0).clamp(0.0, width);
+            let change = (row_bottom - row_top) * direction;
+            self.row(row.saturating_mul(self.width), top_x.min(bottom_x), top_x.max(bottom_x), change);
+        }
+    }
+
+    /// Adds the part of an edge crossing one row, which starts at `row_start`
+    /// in the accumulation buffer. The edge runs between `left` and `right`,
+    /// and changes the coverage of everything to its right by `change`, which
+    /// is spread across the pixels it passes through by the area to its right.
+    fn row(&mut self, row_start: usize, left: f64, right: f64, change: f64) {
+        let left_floor = left.floor();
+        let right_ceil = right.ceil();
+        let first = cast::usize(left_floor).unwrap_or(0);
+        let end = cast::usize(right_ceil).unwrap_or(0);
+        let index = row_start.saturating_add(first);
+        if end <= first.saturating_add(1) {
+            let middle = 0.5f64.mul_add(left + right, -left_floor);
+            self.accumulate(index, change * (1.0 - middle));
+            self.accumulate(index.saturating_add(1), change * middle);
+            return;
+        }
+        let inverse_width = (right - left).recip();
+        let left_fraction = left - left_floor;
+        let first_area = 0.5f64 * inverse_width * (1.0f64 - left_fraction) * (1.0f64 - left_fraction);
+        let right_fraction = right - right_ceil + 1.0f64;
+        let last_area = 0.5f64 * inverse_width * right_fraction * right_fraction;
+        self.accumulate(index, change * first_area);
+        if end == first.saturating_add(2) {
+            self.accumulate(index.saturating_add(1), change * (1.0 - first_area - last_area));
+        } else {
+            let second_area = inverse_width * (1.5f64 - left_fraction);
+            self.accumulate(index.saturating_add(1), change * (second_area - first_area));
+            for column in first.saturating_add(2)..end.saturating_sub(1) {
+                self.accumulate(row_start.saturating_add(column), change * inverse_width);
+            }
+            let middle_columns = cast::f64(end.saturating_sub(first).saturating_sub(3));
+            let before_last = middle_columns.mul_add(inverse_width, second_area);
+            self.accumulate(row_start.saturating_add(end).saturating_sub(1), change * (1.0 - before_last - last_area));
+        }
+        self.accumulate(row_start.saturating_add(end), change * last_area);
+    }
+
+    /// Adds to one value of the accumulation buffer, ignoring indexes past
+    /// its end.
+    fn accumulate(&mut self, index: usize, change: f64) {
+        if let Some(value) = self.accumulation.get_mut(index) {
+            *value += change;
+        }
+    }
+}
+
+/// Splits a curve into straight lines. `steps` is roughly how many lines are
+/// needed for it to look smooth, `point_at` gives the point on the curve at a
+/// time from 0.0 to 1.0, and `add` is given the end of each line.
+fn curve<P: Fn(f64) -> Point, A: FnMut(Point)>(steps: f64, point_at: P, mut add: A) {
+    let count = cast::usize(steps.floor()).unwrap_or(0).saturating_add(1).min(MAX_CURVE_STEPS);
+    for step in 1..=count {
+        add(point_at(cast::f64(step) / cast::f64(count)));
+    }
+}
+
+/// Moves a point by `transform`.
+const fn apply(transform: Transform, point: Point) -> Point {
+    transform.apply(point.0, point.1)
+}
+
+/// Gets the distance between the differences of two pairs of points, used to
+/// measure how sharply a curve bends.
+fn distance(first: Point, second: Point, third: Point, fourth: Point) -> f64 {
+    (first.0 - second.0 - third.0 + fourth.0).hypot(first.1 - second.1 - third.1 + fourth.1)
+}
+
+/// Adds together three points, each multiplied by a weight.
+fn mix3(first: Point, second: Point, third: Point, first_weight: f64, second_weight: f64, third_weight: f64) -> Point {
+    (
+        first.0.mul_add(first_weight, second.0.mul_add(second_weight, third.0 * third_weight)),
+        first.1.mul_add(first_weight, second.1.mul_add(second_weight, third.1 * third_weight)),
+    )
+}
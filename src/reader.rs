@@ -0,0 +1,120 @@
+//! A small cursor over a byte slice, used to parse the binary file formats
+//! supported by Pixl.
+//!
+//! Every read is bounds-checked, and running off the end of the data returns
+//! an error naming the function that was decoding, rather than panicking.
+
+use anyhow::Error;
+
+/// A cursor for reading integers and byte slices out of a binary file.
+pub struct ByteReader<'data> {
+    /// The full contents of the file being read.
+    data: &'data [u8],
+    /// The index of the next byte to be read from `data`.
+    position: usize,
+    /// The name of the decoding function, used in error messages.
+    context: &'static str,
+}
+
+impl<'data> ByteReader<'data> {
+    /// Creates a new `ByteReader` starting at the beginning of `data`.
+    /// `context` should be the name of the public function doing the decoding
+    /// (for example `"decode_bmp"`), and is used in error messages.
+    pub const fn new(data: &'data [u8], context: &'static str) -> ByteReader<'data> {
+        ByteReader { data, position: 0, context }
+    }
+
+    /// Gets the index of the next byte to be read.
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Moves the cursor to the given absolute index in the data.
+    pub fn seek(&mut self, position: usize) -> Result<(), Error> {
+        if position > self.data.len() {
+            return Err(self.end_of_data());
+        }
+        self.position = position;
+        Ok(())
+    }
+
+    /// Moves the cursor forward by `count` bytes without reading them.
+    pub fn skip(&mut self, count: usize) -> Result<(), Error> {
+        self.bytes(count).map(|_| ())
+    }
+
+    /// Reads the next `count` bytes as a slice.
+    pub fn bytes(&mut self, count: usize) -> Result<&'data [u8], Error> {
+        let end = self.position.checked_add(count).ok_or_else(|| self.end_of_data())?;
+        let bytes = self.data.get(self.position..end).ok_or_else(|| self.end_of_data())?;
+        self.position = end;
+        Ok(bytes)
+    }
+
+    /// Reads the next `N` bytes as a fixed-size array.
+    pub fn array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.bytes(N)?);
+        Ok(array)
+    }
+
+    /// Reads a single byte.
+    pub fn u8(&mut self) -> Result<u8, Error> {
+        let [byte] = self.array()?;
+        Ok(byte)
+    }
+
+    /// Reads a little-endian `u16`.
+    #[expect(clippy::little_endian_bytes, reason = "the file formats being read are little-endian")]
+    pub fn u16_le(&mut self) -> Result<u16, Error> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+
+    /// Reads a little-endian `i16`.
+    #[expect(clippy::little_endian_bytes, reason = "the file formats being read are little-endian")]
+    pub fn i16_le(&mut self) -> Result<i16, Error> {
+        Ok(i16::from_le_bytes(self.array()?))
+    }
+
+    /// Reads a little-endian `u32`.
+    #[expect(clippy::little_endian_bytes, reason = "the file formats being read are little-endian")]
+    pub fn u32_le(&mut self) -> Result<u32, Error> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    /// Reads a little-endian `i32`.
+    #[expect(clippy::little_endian_bytes, reason = "the file formats being read are little-endian")]
+    pub fn i32_le(&mut self) -> Result<i32, Error> {
+        Ok(i32::from_le_bytes(self.array()?))
+    }
+
+    /// Reads a big-endian `u16`.
+    #[expect(clippy::big_endian_bytes, reason = "the file formats being read are big-endian")]
+    pub fn u16_be(&mut self) -> Result<u16, Error> {
+        Ok(u16::from_be_bytes(self.array()?))
+    }
+
+    /// Reads a big-endian `i16`.
+    #[expect(clippy::big_endian_bytes, reason = "the file formats being read are big-endian")]
+    pub fn i16_be(&mut self) -> Result<i16, Error> {
+        Ok(i16::from_be_bytes(self.array()?))
+    }
+
+    /// Reads a big-endian `u32`.
+    #[expect(clippy::big_endian_bytes, reason = "the file formats being read are big-endian")]
+    pub fn u32_be(&mut self) -> Result<u32, Error> {
+        Ok(u32::from_be_bytes(self.array()?))
+    }
+
+    /// Reads a big-endian `i32`.
+    #[expect(clippy::big_endian_bytes, reason = "the file formats being read are big-endian")]
+    pub fn i32_be(&mut self) -> Result<i32, Error> {
+        Ok(i32::from_be_bytes(self.array()?))
+    }
+
+    /// Creates the error returned when trying to read past the end of the
+    /// data.
+    fn end_of_data(&self) -> Error {
+        Error::msg(format!("Pixl: {}: unexpected end of data", self.context))
+    }
+}
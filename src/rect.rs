@@ -0,0 +1,38 @@
+//! The `Rect` struct, describing a rectangular region of a `Texture` in whole
+//! pixels.
+
+/// A rectangular region of a texture, such as a single frame of a sprite
+/// sheet.
+/// Unlike `RectangleNode`, this is not drawable: it is used to select part of
+/// a texture, so its position and size are whole pixels.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Rect {
+    /// The x-coordinate of the left edge of the region.
+    pub x: usize,
+    /// The y-coordinate of the top edge of the region.
+    pub y: usize,
+    /// The width of the region in pixels.
+    pub width: usize,
+    /// The height of the region in pixels.
+    pub height: usize,
+}
+
+impl Rect {
+    /// Creates a new `Rect` with the specified position and size.
+    /// # Arguments
+    /// * `x` - The x-coordinate of the left edge of the region.
+    /// * `y` - The y-coordinate of the top edge of the region.
+    /// * `width` - The width of the region in pixels.
+    /// * `height` - The height of the region in pixels.
+    /// # Returns
+    /// A `Rect` with the specified position and size.
+    /// # Example
+    /// ```rust
+    /// let second_frame = Rect::new(16, 0, 16, 16);
+    /// ```
+    #[must_use]
+    pub const fn new(x: usize, y: usize, width: usize, height: usize) -> Rect {
+        Rect { x, y, width, height }
+    }
+}
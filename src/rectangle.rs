@@ -1,16 +1,37 @@
 //! `RectangleNode` struct - represents a node for a rectangle shape in a
 //! texture.
 
-use crate::{Color, component::{DrawComponent, FillColorComponent, PositionComponent, SizeComponent}};
+use core::{f64::consts::{FRAC_PI_2, PI}, ops::Range};
+
+use crate::{BlendMode, Color, Texture, component::{BlendModeComponent, DrawComponent, FillColorComponent, PositionComponent, SizeComponent, StrokeColorComponent, StrokeWidthComponent}, line, path::Path, polygon::FillRule, raster::{self, Segment}, rect::Rect, stroke::{self, DashPattern, LineCap, LineJoin, StrokeAlignment}};
 
 
 /// A node representing a rectangle shape to be drawn on a texture.
-/// The rectangle has flat sides, parallel to the texture edges.
+/// The rectangle has flat sides, parallel to the texture edges, and its
+/// corners can each be rounded by setting `corner_radii`.
+/// It can be given an outline by setting `stroke_width`, and drawn hollow by
+/// setting `filled` to false.
 /// ## Implemented components:
 /// - `DrawComponent`
 /// - `PositionComponent`
 /// - `SizeComponent`
 /// - `FillColorComponent`
+/// - `StrokeColorComponent`
+/// - `StrokeWidthComponent`
+/// - `BlendModeComponent`
+/// # Example
+/// ```rust
+/// let mut frame = RectangleNode::new(8.0, 8.0, 48.0, 32.0, Color::WHITE);
+/// frame.filled = false;
+/// frame.stroke_width = 1;
+/// texture.add(&frame);
+///
+/// // a tab, with only its top corners rounded
+/// let mut tab = RectangleNode::new(70.0, 8.0, 40.0, 16.0, Color::BLUE);
+/// tab.corner_radii = [6.0, 6.0, 0.0, 0.0];
+/// tab.anti_aliased = true;
+/// texture.add(&tab);
+/// ```
 #[non_exhaustive]
 #[expect(clippy::module_name_repetitions, reason = "struct should be called 'RectangleNode' as it is standard.")]
 pub struct RectangleNode {
@@ -30,8 +51,46 @@ pub struct RectangleNode {
     pub height: f64,
 
     /// The fill color of the rectangle.
-    /// This (in the future) may have an alpha channel.
+    /// Its alpha channel is blended over the existing pixels when drawn.
     pub fill_color: Color,
+
+    /// Whether the inside of the rectangle is filled with `fill_color`.
+    /// Defaults to true.
+    pub filled: bool,
+
+    /// The color of the rectangle's outline.
+    /// Defaults to the same color as `fill_color`.
+    pub stroke_color: Color,
+
+    /// The width of the rectangle's outline, in pixels. Defaults to 0, which
+    /// draws no outline.
+    pub stroke_width: usize,
+
+    /// Where the outline is drawn relative to the edge of the rectangle.
+    /// Defaults to `StrokeAlignment::Inside`.
+    pub stroke_alignment: StrokeAlignment,
+
+    /// The radius of each corner of the rectangle, in pixels, in the order
+    /// top-left, top-right, bottom-right, bottom-left. Defaults to 0.0 for
+    /// every corner, which leaves them square. If the corners on a side add up
+    /// to more than its length, every radius is scaled down together, as in
+    /// CSS, so a radius of half the height gives a pill shape.
+    /// The outline follows the corners, with its inner and outer edges rounded
+    /// around the same centers.
+    pub corner_radii: [f64; 4],
+
+    /// Whether rounded corners are drawn with smooth, anti-aliased edges.
+    /// Straight sides always lie between pixels, so square rectangles look the
+    /// same either way. Defaults to false.
+    pub anti_aliased: bool,
+
+    /// The pattern of dashes the outline is drawn with, or `None` to draw it
+    /// solid. Defaults to `None`.
+    pub dash_pattern: Option<DashPattern>,
+
+    /// How the fill and stroke colors are combined with the existing pixels
+    /// when drawn. Defaults to `BlendMode::SourceOver`.
+    pub blend_mode: BlendMode,
 }
 
 impl RectangleNode {
@@ -49,10 +108,123 @@ impl RectangleNode {
     pub const fn new(
         x: f64, y: f64, width: f64, height: f64, fill_color: Color
     ) -> RectangleNode {
-        RectangleNode { x, y, width, height, fill_color }
+        RectangleNode {
+            x, y, width, height, fill_color,
+            filled: true,
+            stroke_color: fill_color,
+            stroke_width: 0,
+            stroke_alignment: StrokeAlignment::Inside,
+            corner_radii: [0.0; 4],
+            anti_aliased: false,
+            dash_pattern: None,
+            blend_mode: BlendMode::SourceOver,
+        }
+    }
+
+    /// Create a new `RectangleNode` with every corner rounded by the same
+    /// radius, such as for a button.
+    /// # Parameters
+    /// - `x`: The x-coordinate of the top-left corner of the rectangle.
+    /// - `y`: The y-coordinate of the top-left corner of the rectangle.
+    /// - `width`: The width of the rectangle, in pixels.
+    /// - `height`: The height of the rectangle in pixels.
+    /// - `radius`: The radius of every corner, in pixels.
+    /// - `fill_color`: The fill color of the rectangle.
+    /// # Returns
+    /// A new, anti-aliased `RectangleNode` with rounded corners.
+    /// # Example
+    /// ```rust
+    /// let mut button = RectangleNode::rounded(20.0, 20.0, 80.0, 24.0, 6.0, Color::rgb(60, 120, 220));
+    /// button.stroke_color = Color::WHITE;
+    /// button.stroke_width = 2;
+    /// texture.add(&button);
+    /// ```
+    #[must_use]
+    pub fn rounded(
+        x: f64, y: f64, width: f64, height: f64, radius: f64, fill_color: Color
+    ) -> RectangleNode {
+        RectangleNode {
+            corner_radii: [radius; 4],
+            anti_aliased: true,
+            ..RectangleNode::new(x, y, width, height, fill_color)
+        }
+    }
+
+    /// Draws the rectangle with rounded corners, as filled paths.
+    /// # Parameters
+    /// - `bounds`: The (left, top, right, bottom) edges of the rectangle.
+    /// - `inside`: The width of the stroke inside the edges.
+    /// - `outside`: The width of the stroke outside the edges.
+    fn draw_rounded(&self, texture: &mut Texture, bounds: Bounds, inside: f64, outside: f64) {
+        let (left, top, right, bottom) = bounds;
+        // radii too big to fit are all scaled down by the same amount
+        let [top_left, top_right, bottom_right, bottom_left] = self.corner_radii.map(|radius| radius.clamp(0.0, f64::MAX));
+        let (width, height) = (right - left, bottom - top);
+        let scale = [width / (top_left + top_right), width / (bottom_left + bottom_right), height / (top_left + bottom_left), height / (top_right + bottom_right)]
+            .into_iter().fold(1.0f64, f64::min);
+        let radii = [top_left, top_right, bottom_right, bottom_left].map(|radius| radius * scale);
+        // the edges of the stroke are the rectangle moved in or out, with
+        // square corners staying square
+        let offset = |distance: f64| {
+            let moved = (left - distance, top - distance, right + distance, bottom + distance);
+            let moved_radii = radii.map(|radius| if radius > 0.0f64 { (radius + distance).max(0.0) } else { 0.0f64 });
+            (moved.0 < moved.2 && moved.1 < moved.3).then(|| rounded_outline(moved, moved_radii))
+        };
+        let inner = offset(-inside);
+        if self.filled && let Some(fill) = inner.as_ref() {
+            raster::fill(texture, &fill.fill_segments(), self.fill_color, self.blend_mode, self.anti_aliased, FillRule::NonZero);
+        }
+        if self.stroke_width == 0 {
+            return;
+        }
+        let Some(pattern) = self.dash_pattern.as_ref() else {
+            // the stroke is everything between its outer and inner edges
+            let ring: Vec<Segment> = offset(outside).into_iter().chain(inner).flat_map(|edge| edge.fill_segments()).collect();
+            raster::fill(texture, &ring, self.stroke_color, self.blend_mode, self.anti_aliased, FillRule::EvenOdd);
+            return;
+        };
+        // dashes are drawn along the center of the stroke
+        for (points, closed) in offset((outside - inside) / 2.0f64).map(|center| center.flattened()).unwrap_or_default() {
+            if self.stroke_width == 1 {
+                let centered: Vec<(f64, f64)> = points.iter().map(|&(x, y)| (x - 0.5f64, y - 0.5f64)).collect();
+                line::draw_thin_dashed(texture, &centered, closed, Some(pattern), self.stroke_color, self.blend_mode, self.anti_aliased);
+            } else {
+                let outline = stroke::dashed_outline(&points, closed, cast::f64(self.stroke_width), LineCap::Butt, LineJoin::Miter, Some(pattern));
+                raster::fill(texture, &outline, self.stroke_color, self.blend_mode, self.anti_aliased, FillRule::NonZero);
+            }
+        }
     }
 }
 
+/// The (left, top, right, bottom) edges of a rectangle.
+type Bounds = (f64, f64, f64, f64);
+
+/// Builds the outline of a rectangle with rounded corners, going clockwise.
+/// # Parameters
+/// - `bounds`: The (left, top, right, bottom) edges of the rectangle.
+/// - `radii`: The radius of each corner, in the same order as
+///   `corner_radii`. A radius of 0.0 gives a square corner.
+#[expect(clippy::single_call_fn, reason = "kept separate from RectangleNode::draw_rounded for readability")]
+fn rounded_outline((left, top, right, bottom): Bounds, radii: [f64; 4]) -> Path {
+    let [top_left, top_right, bottom_right, bottom_left] = radii;
+    let corners = [
+        ((left + top_left, top + top_left), top_left, PI),
+        ((right - top_right, top + top_right), top_right, -FRAC_PI_2),
+        ((right - bottom_right, bottom - bottom_right), bottom_right, 0.0f64),
+        ((left + bottom_left, bottom - bottom_left), bottom_left, FRAC_PI_2),
+    ];
+    let mut path = Path::new();
+    for (center, radius, start_angle) in corners {
+        if radius > 0.0f64 {
+            path.add_arc(center, (radius, radius), 0.0, start_angle, FRAC_PI_2);
+        } else {
+            path.line_to(center.0, center.1);
+        }
+    }
+    path.close();
+    path
+}
+
 impl PositionComponent for RectangleNode {
     fn get_x(&self) -> f64 {
         self.x
@@ -99,16 +271,95 @@ impl FillColorComponent for RectangleNode {
     }
 }
 
+impl StrokeColorComponent for RectangleNode {
+    fn get_stroke_color(&self) -> &Color {
+        &self.stroke_color
+    }
+
+    fn set_stroke_color(&mut self, color: Color) {
+        self.stroke_color = color;
+    }
+}
+
+impl StrokeWidthComponent for RectangleNode {
+    fn get_stroke_width(&self) -> usize {
+        self.stroke_width
+    }
+
+    fn set_stroke_width(&mut self, width: usize) {
+        self.stroke_width = width;
+    }
+}
+
 impl DrawComponent for RectangleNode {
-    fn draw(&self, texture: &mut crate::Texture) {
-        for dy in 0..cast::usize(self.height).unwrap() {
-            for dx in 0..cast::usize(self.width).unwrap() {
-                let px = cast::usize(self.x).unwrap().saturating_add(dx);
-                let py = cast::usize(self.y).unwrap().saturating_add(dy);
-                texture.set_pixel(px, py, self.fill_color)
-                    .unwrap_or(());
+    fn draw(&self, texture: &mut Texture) {
+        let (inside, outside) = self.stroke_alignment.split(self.stroke_width);
+        let inside_width = cast::f64(inside);
+        let outside_width = cast::f64(outside);
+        let left = self.x.floor();
+        let top = self.y.floor();
+        let right = left + self.width.floor();
+        let bottom = top + self.height.floor();
+        if right > left && bottom > top && self.corner_radii.iter().any(|&radius| radius > 0.0f64) {
+            self.draw_rounded(texture, (left, top, right, bottom), inside_width, outside_width);
+            return;
+        }
+        let rows = pixel_range(top - outside_width, bottom + outside_width, texture.get_height());
+        let columns = pixel_range(left - outside_width, right + outside_width, texture.get_width());
+        // pixels inside the stroke are filled, and everything else is stroke
+        let fill_rows = pixel_range(top + inside_width, bottom - inside_width, texture.get_height());
+        let fill_columns = pixel_range(left + inside_width, right - inside_width, texture.get_width());
+        let dashes = self.dash_pattern.as_ref().filter(|_| self.stroke_width > 0);
+        if self.filled {
+            let fill = Rect::new(fill_columns.start, fill_rows.start, fill_columns.len(), fill_rows.len());
+            texture.fill_rect(fill, self.fill_color, self.blend_mode);
+        }
+        if dashes.is_none() {
+            for py in rows {
+                // rows beside the fill only have stroke to its left and right
+                if fill_rows.contains(&py) && !fill_columns.is_empty() {
+                    texture.fill_span(columns.start, py, fill_columns.start.saturating_sub(columns.start), self.stroke_color, self.blend_mode);
+                    texture.fill_span(fill_columns.end, py, columns.end.saturating_sub(fill_columns.end), self.stroke_color, self.blend_mode);
+                } else {
+                    texture.fill_span(columns.start, py, columns.len(), self.stroke_color, self.blend_mode);
+                }
+            }
+        }
+        if let Some(pattern) = dashes {
+            // the center of the stroke, halfway between its inside and
+            // outside edges
+            let middle = (outside_width - inside_width) / 2.0f64;
+            let path = [
+                (left - middle, top - middle), (right + middle, top - middle),
+                (right + middle, bottom + middle), (left - middle, bottom + middle),
+            ];
+            if self.stroke_width == 1 {
+                // thin strokes are drawn as lines through the centers of
+                // pixels, so that they have no gaps
+                let centered = path.map(|(x, y)| (x - 0.5f64, y - 0.5f64));
+                line::draw_thin_dashed(texture, &centered, true, Some(pattern), self.stroke_color, self.blend_mode, false);
+            } else {
+                let outline = stroke::dashed_outline(&path, true, cast::f64(self.stroke_width), LineCap::Butt, LineJoin::Miter, Some(pattern));
+                raster::fill(texture, &outline, self.stroke_color, self.blend_mode, false, FillRule::NonZero);
             }
         }
     }
 }
 
+/// Gets the pixels from `start` up to `end` which are within a texture
+/// `size` pixels wide or high.
+fn pixel_range(start: f64, end: f64, size: usize) -> Range<usize> {
+    let limit = cast::f64(size);
+    cast::usize(start.clamp(0.0, limit)).unwrap_or(0)..cast::usize(end.clamp(0.0, limit)).unwrap_or(0)
+}
+
+
+impl BlendModeComponent for RectangleNode {
+    fn get_blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+}
@@ -0,0 +1,235 @@
+//! `SpriteNode` struct - represents a node which draws a `Texture` (or part of
+//! one) onto another texture.
+
+use crate::{BlendMode, Color, Texture, component::{BlendModeComponent, DrawComponent, PositionComponent, SizeComponent}, rect::Rect, texture::Filter, transform::Transform};
+
+/// A node which draws a source `Texture`, or a region of one, onto another
+/// texture.
+/// The sprite borrows its source texture, so many sprites can share a single
+/// loaded image.
+/// The position is the top-left corner of the sprite, and the size is the
+/// size it is drawn at: by default, the size of the source region. If the size
+/// differs from the source region, the sprite is stretched.
+/// The sprite can also be rotated around a pivot point, and is sampled using
+/// its `filter`: nearest-neighbour for crisp pixel art, or bilinear for
+/// smooth scaling. Positions are `f64`, so a moving sprite glides smoothly
+/// between pixels.
+/// ## Implemented components:
+/// - `DrawComponent`
+/// - `PositionComponent`
+/// - `SizeComponent`
+/// - `BlendModeComponent`
+/// # Example
+/// ```rust
+/// let player_texture = Texture::load_qoi("assets/player.qoi").unwrap();
+/// let mut player = SpriteNode::new(&player_texture, 40.0, 30.0);
+/// player.flip_x = true; // face left
+/// player.center_pivot();
+/// player.rotation = core::f64::consts::FRAC_PI_8; // lean forwards
+/// texture.add(&player);
+/// ```
+#[non_exhaustive]
+#[expect(clippy::module_name_repetitions, reason = "struct should be called 'SpriteNode' as it is standard.")]
+pub struct SpriteNode<'texture> {
+
+    /// The texture that the sprite's pixels are taken from.
+    pub texture: &'texture Texture,
+
+    /// The x-coordinate of the top-left corner of the sprite.
+    pub x: f64,
+
+    /// The y-coordinate of the top-left corner of the sprite.
+    pub y: f64,
+
+    /// The width the sprite is drawn at, in pixels.
+    pub width: f64,
+
+    /// The height the sprite is drawn at, in pixels.
+    pub height: f64,
+
+    /// The region of `texture` to draw, or `None` to draw the whole texture.
+    pub source: Option<Rect>,
+
+    /// Whether the sprite is mirrored horizontally (left to right).
+    pub flip_x: bool,
+
+    /// Whether the sprite is mirrored vertically (top to bottom).
+    pub flip_y: bool,
+
+    /// The angle the sprite is rotated by around its pivot, in radians.
+    /// Positive angles rotate clockwise.
+    pub rotation: f64,
+
+    /// The x-coordinate of the point the sprite rotates around, relative to
+    /// the top-left corner of the (unrotated) sprite.
+    pub pivot_x: f64,
+
+    /// The y-coordinate of the point the sprite rotates around, relative to
+    /// the top-left corner of the (unrotated) sprite.
+    pub pivot_y: f64,
+
+    /// How the source texture is sampled when the sprite is scaled, rotated
+    /// or drawn between pixels. Defaults to `Filter::Nearest`.
+    pub filter: Filter,
+
+    /// A color multiplied with every pixel of the sprite, including its alpha
+    /// channel. `Color::WHITE` (the default) leaves the sprite unchanged, and
+    /// a translucent white fades the whole sprite.
+    pub tint: Color,
+
+    /// How the sprite's pixels are combined with the existing pixels when
+    /// drawn. Defaults to `BlendMode::SourceOver`.
+    pub blend_mode: BlendMode,
+}
+
+impl<'texture> SpriteNode<'texture> {
+    /// Create a new `SpriteNode` drawing the whole of `texture` with its
+    /// top-left corner at (`x`, `y`), at its original size.
+    /// # Parameters
+    /// - `texture`: The texture to take the sprite's pixels from.
+    /// - `x`: The x-coordinate of the top-left corner of the sprite.
+    /// - `y`: The y-coordinate of the top-left corner of the sprite.
+    /// # Returns
+    /// A new `SpriteNode` with no flipping, rotation, tint or source region.
+    #[must_use]
+    pub fn new(texture: &'texture Texture, x: f64, y: f64) -> SpriteNode<'texture> {
+        SpriteNode {
+            texture, x, y,
+            width: cast::f64(texture.get_width()),
+            height: cast::f64(texture.get_height()),
+            source: None,
+            flip_x: false,
+            flip_y: false,
+            rotation: 0.0,
+            pivot_x: 0.0,
+            pivot_y: 0.0,
+            filter: Filter::Nearest,
+            tint: Color::WHITE,
+            blend_mode: BlendMode::SourceOver,
+        }
+    }
+
+    /// Create a new `SpriteNode` drawing only the `source` region of
+    /// `texture`, with its top-left corner at (`x`, `y`), at the region's
+    /// original size.
+    /// # Parameters
+    /// - `texture`: The texture to take the sprite's pixels from.
+    /// - `source`: The region of `texture` to draw.
+    /// - `x`: The x-coordinate of the top-left corner of the sprite.
+    /// - `y`: The y-coordinate of the top-left corner of the sprite.
+    /// # Returns
+    /// A new `SpriteNode` with no flipping, rotation or tint.
+    #[must_use]
+    pub fn from_region(texture: &'texture Texture, source: Rect, x: f64, y: f64) -> SpriteNode<'texture> {
+        SpriteNode {
+            width: cast::f64(source.width),
+            height: cast::f64(source.height),
+            source: Some(source),
+            ..SpriteNode::new(texture, x, y)
+        }
+    }
+
+    /// Gets the region of the source texture which is drawn: either `source`,
+    /// or the whole texture.
+    /// # Returns
+    /// The `Rect` of the source texture which is drawn.
+    #[must_use]
+    pub fn get_source(&self) -> Rect {
+        self.source.unwrap_or_else(|| Rect::new(0, 0, self.texture.get_width(), self.texture.get_height()))
+    }
+
+    /// Sets the size the sprite is drawn at to a multiple of the size of its
+    /// source region.
+    /// # Parameters
+    /// - `scale_x`: How many times wider than the source region to draw it.
+    /// - `scale_y`: How many times taller than the source region to draw it.
+    pub fn set_scale(&mut self, scale_x: f64, scale_y: f64) {
+        let source = self.get_source();
+        self.width = cast::f64(source.width) * scale_x;
+        self.height = cast::f64(source.height) * scale_y;
+    }
+
+    /// Moves the pivot to the center of the sprite, so that it spins in place
+    /// when rotated.
+    pub fn center_pivot(&mut self) {
+        self.pivot_x = self.width / 2.0f64;
+        self.pivot_y = self.height / 2.0f64;
+    }
+
+    /// Gets the transformation from the source region to the destination
+    /// texture used to draw the sprite, combining its flipping, size,
+    /// rotation and position.
+    /// # Returns
+    /// The `Transform` passed to `Texture::draw_texture` when drawing.
+    #[must_use]
+    pub fn get_transform(&self) -> Transform {
+        let source = self.get_source();
+        let source_width = cast::f64(source.width);
+        let source_height = cast::f64(source.height);
+        let flip = Transform::new(
+            if self.flip_x { -1.0 } else { 1.0 }, 0.0,
+            0.0, if self.flip_y { -1.0 } else { 1.0 },
+            if self.flip_x { source_width } else { 0.0 },
+            if self.flip_y { source_height } else { 0.0 },
+        );
+        flip.then(Transform::scaling(self.width / source_width, self.height / source_height))
+            .then(Transform::translation(-self.pivot_x, -self.pivot_y))
+            .then(Transform::rotation(self.rotation))
+            .then(Transform::translation(self.x + self.pivot_x, self.y + self.pivot_y))
+    }
+}
+
+impl DrawComponent for SpriteNode<'_> {
+    fn draw(&self, texture: &mut Texture) {
+        if self.width <= 0.0f64 || self.height <= 0.0f64 {
+            return;
+        }
+        texture.draw_texture(self.texture, self.get_source(), self.get_transform(), self.filter, self.tint, self.blend_mode);
+    }
+}
+
+impl PositionComponent for SpriteNode<'_> {
+    fn get_x(&self) -> f64 {
+        self.x
+    }
+
+    fn get_y(&self) -> f64 {
+        self.y
+    }
+
+    fn set_x(&mut self, x: f64) {
+        self.x = x;
+    }
+
+    fn set_y(&mut self, y: f64) {
+        self.y = y;
+    }
+}
+
+impl SizeComponent for SpriteNode<'_> {
+    fn get_width(&self) -> f64 {
+        self.width
+    }
+
+    fn get_height(&self) -> f64 {
+        self.height
+    }
+
+    fn set_width(&mut self, width: f64) {
+        self.width = width;
+    }
+
+    fn set_height(&mut self, height: f64) {
+        self.height = height;
+    }
+}
+
+impl BlendModeComponent for SpriteNode<'_> {
+    fn get_blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+}
@@ -0,0 +1,291 @@
+//! The `SpriteSheet` struct, which slices a single `Texture` into frames and
+//! groups those frames into named animations.
+
+use std::collections::HashMap;
+
+use anyhow::Error;
+
+use crate::{Texture, rect::Rect, sprite::SpriteNode};
+
+/// A single texture containing many frames, such as every pose of a player
+/// character, along with named animations made out of those frames.
+/// Frames are numbered in the order they were added, starting from 0. When
+/// slicing a grid, they are numbered left to right, then top to bottom.
+/// # Example
+/// ```rust
+/// let texture = Texture::load_qoi("assets/player.qoi").unwrap();
+/// let mut sheet = SpriteSheet::from_grid(texture, 16, 16).unwrap();
+/// sheet.add_animation("idle", Animation::uniform(&[0, 1], 0.5, true)).unwrap();
+/// sheet.add_animation("walk", Animation::uniform(&[2, 3, 4, 5], 0.1, true)).unwrap();
+/// let mut player = AnimatedSpriteNode::new(&sheet, "idle", 40.0, 30.0).unwrap();
+/// ```
+#[non_exhaustive]
+pub struct SpriteSheet {
+    /// The texture which every frame is taken from.
+    texture: Texture,
+    /// The region of `texture` covered by each frame.
+    frames: Vec<Rect>,
+    /// The animations of the sheet, by name.
+    animations: HashMap<String, Animation>,
+}
+
+impl SpriteSheet {
+    /// Creates a new `SpriteSheet` from a texture, with no frames or
+    /// animations. Frames can be added with `add_frame`.
+    /// # Arguments
+    /// * `texture` - The texture which every frame is taken from.
+    /// # Returns
+    /// An empty `SpriteSheet` owning `texture`.
+    /// # Example
+    /// ```rust
+    /// let mut sheet = SpriteSheet::new(texture);
+    /// sheet.add_frame(Rect::new(0, 0, 24, 32)).unwrap();
+    /// ```
+    #[must_use]
+    pub fn new(texture: Texture) -> SpriteSheet {
+        SpriteSheet { texture, frames: Vec::new(), animations: HashMap::new() }
+    }
+
+    /// Creates a new `SpriteSheet` by slicing a texture into a grid of
+    /// equally-sized cells. Each whole cell becomes a frame, numbered left to
+    /// right, then top to bottom. Any pixels left over at the right or bottom
+    /// edge are ignored.
+    /// # Arguments
+    /// * `texture` - The texture which every frame is taken from.
+    /// * `cell_width` - The width of each cell in pixels.
+    /// * `cell_height` - The height of each cell in pixels.
+    /// # Returns
+    /// A `SpriteSheet` with one frame per cell.
+    /// # Errors
+    /// Errors if either cell dimension is zero, or if the texture is too small
+    /// to fit a single cell.
+    /// # Example
+    /// ```rust
+    /// let sheet = SpriteSheet::from_grid(texture, 16, 16).unwrap();
+    /// ```
+    pub fn from_grid(texture: Texture, cell_width: usize, cell_height: usize) -> Result<SpriteSheet, Error> {
+        if cell_width == 0 || cell_height == 0 {
+            return Err(Error::msg("Pixl: from_grid: cell size must not be zero"));
+        }
+        let columns = texture.get_width().checked_div(cell_width).unwrap_or(0);
+        let rows = texture.get_height().checked_div(cell_height).unwrap_or(0);
+        if columns == 0 || rows == 0 {
+            return Err(Error::msg("Pixl: from_grid: cell size was larger than the texture"));
+        }
+        let frames = (0..rows).flat_map(|row| (0..columns).map(move |column| Rect::new(
+            column.saturating_mul(cell_width), row.saturating_mul(cell_height),
+            cell_width, cell_height,
+        ))).collect();
+        Ok(SpriteSheet { frames, ..SpriteSheet::new(texture) })
+    }
+
+    /// Creates a new `SpriteSheet` from a texture and an explicit list of
+    /// frames, which may be any size and in any order.
+    /// # Arguments
+    /// * `texture` - The texture which every frame is taken from.
+    /// * `frames` - The region of `texture` covered by each frame.
+    /// # Returns
+    /// A `SpriteSheet` with the passed frames.
+    /// # Errors
+    /// Errors if any frame lies outside of the texture.
+    /// # Example
+    /// ```rust
+    /// let sheet = SpriteSheet::from_rects(texture, vec![
+    ///     Rect::new(0, 0, 24, 32),
+    ///     Rect::new(24, 0, 30, 32),
+    /// ]).unwrap();
+    /// ```
+    pub fn from_rects(texture: Texture, frames: Vec<Rect>) -> Result<SpriteSheet, Error> {
+        let mut sheet = SpriteSheet::new(texture);
+        for frame in frames {
+            sheet.add_frame(frame)?;
+        }
+        Ok(sheet)
+    }
+
+    /// Adds a frame to the sheet.
+    /// # Arguments
+    /// * `frame` - The region of the texture covered by the frame.
+    /// # Returns
+    /// The index of the new frame.
+    /// # Errors
+    /// Errors if the frame lies outside of the texture.
+    /// # Example
+    /// ```rust
+    /// let jump = sheet.add_frame(Rect::new(96, 0, 16, 20)).unwrap();
+    /// ```
+    pub fn add_frame(&mut self, frame: Rect) -> Result<usize, Error> {
+        let right = frame.x.checked_add(frame.width);
+        let bottom = frame.y.checked_add(frame.height);
+        if right.is_none_or(|edge| edge > self.texture.get_width())
+            || bottom.is_none_or(|edge| edge > self.texture.get_height()) {
+            return Err(Error::msg("Pixl: add_frame: frame was out of bounds for texture"));
+        }
+        self.frames.push(frame);
+        Ok(self.frames.len().saturating_sub(1))
+    }
+
+    /// Adds a named animation to the sheet, replacing any existing animation
+    /// with the same name.
+    /// # Arguments
+    /// * `name` - The name of the animation, such as `"walk"`.
+    /// * `animation` - The frames of the animation.
+    /// # Errors
+    /// Errors if the animation has no frames, or uses a frame index which is
+    /// not in the sheet.
+    /// # Example
+    /// ```rust
+    /// sheet.add_animation("attack", Animation::new(vec![
+    ///     AnimationFrame::new(6, 0.05),
+    ///     AnimationFrame::new(7, 0.3),
+    /// ], false)).unwrap();
+    /// ```
+    pub fn add_animation(&mut self, name: &str, animation: Animation) -> Result<(), Error> {
+        if animation.frames.is_empty() {
+            return Err(Error::msg("Pixl: add_animation: animation had no frames"));
+        }
+        if animation.frames.iter().any(|frame| frame.frame >= self.frames.len()) {
+            return Err(Error::msg("Pixl: add_animation: animation used a frame which is not in the sheet"));
+        }
+        self.animations.insert(name.to_owned(), animation);
+        Ok(())
+    }
+
+    /// Gets the texture which every frame is taken from.
+    /// # Returns
+    /// A reference to the sheet's `Texture`.
+    #[must_use]
+    pub const fn get_texture(&self) -> &Texture {
+        &self.texture
+    }
+
+    /// Gets the region of the texture covered by a frame.
+    /// # Arguments
+    /// * `index` - The index of the frame.
+    /// # Returns
+    /// The frame's `Rect`, or `None` if there is no frame with that index.
+    #[must_use]
+    pub fn get_frame(&self, index: usize) -> Option<Rect> {
+        self.frames.get(index).copied()
+    }
+
+    /// Gets every frame of the sheet, in order.
+    /// # Returns
+    /// A slice of the region covered by each frame.
+    #[must_use]
+    pub fn get_frames(&self) -> &[Rect] {
+        &self.frames
+    }
+
+    /// Gets a named animation.
+    /// # Arguments
+    /// * `name` - The name of the animation.
+    /// # Returns
+    /// The `Animation`, or `None` if the sheet has no animation with that name.
+    #[must_use]
+    pub fn get_animation(&self, name: &str) -> Option<&Animation> {
+        self.animations.get(name)
+    }
+
+    /// Creates a `SpriteNode` drawing a single frame of the sheet.
+    /// # Arguments
+    /// * `index` - The index of the frame to draw.
+    /// * `x` - The x-coordinate of the top-left corner of the sprite.
+    /// * `y` - The y-coordinate of the top-left corner of the sprite.
+    /// # Returns
+    /// A `SpriteNode` for the frame, or `None` if there is no frame with that
+    /// index.
+    /// # Example
+    /// ```rust
+    /// let coin = sheet.get_sprite(12, 50.0, 20.0).unwrap();
+    /// texture.add(&coin);
+    /// ```
+    #[must_use]
+    pub fn get_sprite(&self, index: usize, x: f64, y: f64) -> Option<SpriteNode<'_>> {
+        Some(SpriteNode::from_region(&self.texture, self.get_frame(index)?, x, y))
+    }
+}
+
+/// A sequence of sprite sheet frames, each shown for its own duration.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct Animation {
+    /// The frames of the animation, in the order they are played.
+    pub frames: Vec<AnimationFrame>,
+    /// Whether the animation starts again from the first frame after the last
+    /// one. If not, it stops on the last frame.
+    pub looping: bool,
+}
+
+impl Animation {
+    /// Creates a new `Animation` from a list of frames and durations.
+    /// # Arguments
+    /// * `frames` - The frames of the animation, in the order they are played.
+    /// * `looping` - Whether the animation repeats once it has finished.
+    /// # Returns
+    /// An `Animation` made of the passed frames.
+    /// # Example
+    /// ```rust
+    /// let blink = Animation::new(vec![
+    ///     AnimationFrame::new(0, 2.0),
+    ///     AnimationFrame::new(1, 0.1),
+    /// ], true);
+    /// ```
+    #[must_use]
+    pub const fn new(frames: Vec<AnimationFrame>, looping: bool) -> Animation {
+        Animation { frames, looping }
+    }
+
+    /// Creates a new `Animation` where every frame is shown for the same
+    /// amount of time.
+    /// # Arguments
+    /// * `frames` - The indices of the sheet frames, in the order they are
+    ///   played.
+    /// * `frame_duration` - How long each frame is shown for, in seconds.
+    /// * `looping` - Whether the animation repeats once it has finished.
+    /// # Returns
+    /// An `Animation` made of the passed frames.
+    /// # Example
+    /// ```rust
+    /// let walk = Animation::uniform(&[2, 3, 4, 5], 0.1, true);
+    /// ```
+    #[must_use]
+    pub fn uniform(frames: &[usize], frame_duration: f64, looping: bool) -> Animation {
+        Animation::new(frames.iter().map(|&frame| AnimationFrame::new(frame, frame_duration)).collect(), looping)
+    }
+
+    /// Gets how long it takes to play the whole animation once.
+    /// # Returns
+    /// The total duration of every frame, in seconds.
+    #[must_use]
+    pub fn get_duration(&self) -> f64 {
+        self.frames.iter().map(|frame| frame.duration.max(0.0f64)).sum()
+    }
+}
+
+/// A single frame of an `Animation`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct AnimationFrame {
+    /// The index of the sprite sheet frame to show.
+    pub frame: usize,
+    /// How long the frame is shown for, in seconds.
+    pub duration: f64,
+}
+
+impl AnimationFrame {
+    /// Creates a new `AnimationFrame`.
+    /// # Arguments
+    /// * `frame` - The index of the sprite sheet frame to show.
+    /// * `duration` - How long the frame is shown for, in seconds.
+    /// # Returns
+    /// An `AnimationFrame` with the passed frame and duration.
+    /// # Example
+    /// ```rust
+    /// let hold = AnimationFrame::new(3, 0.25);
+    /// ```
+    #[must_use]
+    pub const fn new(frame: usize, duration: f64) -> AnimationFrame {
+        AnimationFrame { frame, duration }
+    }
+}
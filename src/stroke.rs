@@ -0,0 +1,402 @@
+//! Types controlling how the outlines (strokes) of shapes and thick lines are
+//! drawn, and the code turning a stroked line into a shape which can be
+//! filled.
+
+use core::{iter, mem};
+
+use crate::raster::{Point, Segment};
+
+/// The longest a miter join can be, from the inside to the outside of its
+/// corner, as a multiple of the stroke width, before it is drawn as a bevel
+/// instead. This is the same default as SVG's.
+const MITER_LIMIT: f64 = 4.0;
+
+/// How close to each other the cubic Bézier control points of a quarter
+/// circle are placed, as a fraction of its radius.
+const CIRCLE_CONTROL: f64 = 0.552_284_749_830_793_4;
+
+/// How close together points on a line can be before they are treated as the
+/// same point. Shorter segments, such as those left by rounding where a curve
+/// meets its start, have no meaningful direction to join or cap them by.
+const MIN_SEGMENT_LENGTH: f64 = 1.0 / 1024.0;
+
+/// The most dashes a single line is split into. Anything after them is left
+/// undrawn, so that a tiny pattern on a huge line cannot use up all memory.
+const MAX_DASHES: usize = 0x1_0000;
+
+/// Where a shape's stroke is drawn relative to its edge.
+/// # Example
+/// ```rust
+/// let mut button = RectangleNode::new(10.0, 10.0, 60.0, 20.0, Color::rgb(40, 40, 40));
+/// button.stroke_color = Color::WHITE;
+/// button.stroke_width = 2;
+/// button.stroke_alignment = StrokeAlignment::Outside;
+/// texture.add(&button);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+#[expect(clippy::module_name_repetitions, reason = "the name 'Alignment' alone would be unclear")]
+pub enum StrokeAlignment {
+    /// The stroke is drawn entirely inside the shape, so the shape takes up
+    /// the same space as it would without one.
+    #[default]
+    Inside,
+    /// The stroke is centered on the edge of the shape. For odd widths, the
+    /// extra pixel goes inside.
+    Centered,
+    /// The stroke is drawn entirely outside the shape, around its fill.
+    Outside,
+}
+
+impl StrokeAlignment {
+    /// Splits a stroke width into the parts inside and outside of the edge of
+    /// a shape.
+    /// # Parameters
+    /// - `width`: The width of the stroke, in pixels.
+    /// # Returns
+    /// The (inside, outside) widths of the stroke, in pixels, which add up to
+    /// `width`.
+    #[must_use]
+    pub const fn split(self, width: usize) -> (usize, usize) {
+        match self {
+            StrokeAlignment::Inside => (width, 0),
+            StrokeAlignment::Centered => (width.saturating_sub(width / 2), width / 2),
+            StrokeAlignment::Outside => (0, width),
+        }
+    }
+}
+
+/// The shape drawn at the open ends of a thick line.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LineCap {
+    /// The line stops exactly at its endpoints.
+    #[default]
+    Butt,
+    /// The line ends with a half circle around each endpoint.
+    Round,
+    /// The line continues past each endpoint by half of its width.
+    Square,
+}
+
+/// The shape drawn at the corners where the segments of a thick line meet.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LineJoin {
+    /// The outer edges of the segments are extended until they meet in a
+    /// sharp point. Very sharp corners, where the point would be more than
+    /// four times as long as the line is wide, are beveled instead.
+    #[default]
+    Miter,
+    /// The corner is rounded off with a circle around it.
+    Round,
+    /// The corner is cut off straight between the outer edges of the
+    /// segments.
+    Bevel,
+}
+
+/// A pattern of dashes and gaps, which strokes are drawn with to make them
+/// dashed or dotted.
+/// # Example
+/// ```rust
+/// let mut marquee = RectangleNode::new(20.0, 20.0, 64.0, 48.0, Color::WHITE);
+/// marquee.filled = false;
+/// marquee.stroke_width = 1;
+/// marquee.dash_pattern = Some(DashPattern::new(&[4.0, 4.0]));
+/// // marching ants: move the dashes along the outline every frame
+/// if let Some(pattern) = &mut marquee.dash_pattern {
+///     pattern.offset += 0.5;
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct DashPattern {
+    /// The lengths of the dashes and the gaps between them in pixels,
+    /// alternating and starting with a dash. An odd number of lengths is
+    /// repeated twice, so `[5.0]` is the same as `[5.0, 5.0]`. A dash of
+    /// length 0.0 is drawn as a dot if the line has round or square caps.
+    /// If the lengths are empty, negative or add up to 0.0, the stroke is
+    /// drawn solid.
+    pub dashes: Vec<f64>,
+
+    /// How far into the pattern the stroke starts, in pixels. Increasing it
+    /// moves the dashes backwards along the stroke.
+    pub offset: f64,
+}
+
+impl DashPattern {
+    /// Create a new `DashPattern` starting at the start of its first dash.
+    /// # Parameters
+    /// - `dashes`: The lengths of the dashes and the gaps between them in
+    ///   pixels, alternating and starting with a dash.
+    /// # Returns
+    /// A new `DashPattern` with an offset of 0.0.
+    #[must_use]
+    pub fn new(dashes: &[f64]) -> DashPattern {
+        DashPattern { dashes: dashes.to_vec(), offset: 0.0 }
+    }
+
+    /// Checks whether the point `distance` pixels along a stroke is in one of
+    /// the pattern's dashes, rather than in a gap.
+    /// # Parameters
+    /// - `distance`: How far along the stroke the point is, in pixels.
+    /// # Returns
+    /// Whether the point is in a dash, which is always true if the pattern is
+    /// drawn solid.
+    /// # Example
+    /// ```rust
+    /// let pattern = DashPattern::new(&[4.0, 2.0]);
+    /// assert!(pattern.is_dash(3.0) && !pattern.is_dash(5.0));
+    /// ```
+    #[must_use]
+    pub fn is_dash(&self, distance: f64) -> bool {
+        let Some((dashes, total)) = self.lengths() else {
+            return true;
+        };
+        let mut position = (distance + self.offset).rem_euclid(total);
+        for (index, length) in dashes.into_iter().enumerate() {
+            if position < length {
+                return index % 2 == 0;
+            }
+            position -= length;
+        }
+        true
+    }
+
+    /// Gets the lengths of the dashes and gaps, with an odd number of lengths
+    /// repeated twice, and the total length of the pattern.
+    /// # Returns
+    /// The lengths and their total, or `None` if the stroke is drawn solid.
+    fn lengths(&self) -> Option<(Vec<f64>, f64)> {
+        let repeats = if self.dashes.len() % 2 == 1 { 2 } else { 1 };
+        let dashes: Vec<f64> = iter::repeat_n(self.dashes.iter().copied(), repeats).flatten().collect();
+        let total: f64 = dashes.iter().sum();
+        let valid = dashes.iter().all(|&length| length >= 0.0f64 && length.is_finite()) && total > 0.0f64 && self.offset.is_finite();
+        valid.then_some((dashes, total))
+    }
+
+    /// Splits a line through `points` into the dashes drawn along it.
+    /// # Returns
+    /// The points each dash passes through, or the whole line, including its
+    /// closing segment, if the pattern is not valid.
+    fn split_line(&self, points: &[Point], closed: bool) -> Vec<Vec<Point>> {
+        let closing = closed.then(|| points.first().copied()).flatten();
+        let path: Vec<Point> = points.iter().copied().chain(closing).collect();
+        let Some((dashes, total)) = self.lengths() else {
+            return vec![path];
+        };
+        // find where in the pattern the line starts
+        let mut lengths = dashes.iter().copied().cycle();
+        let mut on = false;
+        let mut remaining = 0.0f64;
+        let mut skipped = self.offset.rem_euclid(total);
+        for length in lengths.by_ref() {
+            on = !on;
+            if skipped <= length {
+                remaining = length - skipped;
+                break;
+            }
+            skipped -= length;
+        }
+        let mut pieces = Vec::new();
+        let mut current: Vec<Point> = if on { path.first().copied().into_iter().collect() } else { Vec::new() };
+        for (from, to) in path.iter().copied().zip(path.iter().copied().skip(1)) {
+            let length = distance(from, to);
+            let mut travelled = 0.0f64;
+            while length - travelled > remaining && pieces.len() < MAX_DASHES {
+                travelled += remaining;
+                let fraction = travelled / length;
+                let point = ((to.0 - from.0).mul_add(fraction, from.0), (to.1 - from.1).mul_add(fraction, from.1));
+                current.push(point);
+                if on {
+                    pieces.push(mem::take(&mut current));
+                }
+                on = !on;
+                remaining = lengths.next().unwrap_or(0.0f64);
+            }
+            remaining -= length - travelled;
+            if on {
+                current.push(to);
+            }
+        }
+        if on && !current.is_empty() {
+            pieces.push(current);
+        }
+        pieces
+    }
+}
+
+/// Gets the outline of a thick line, split into dashes if it has a pattern.
+/// # Parameters
+/// - `points`: The points the center of the line passes through.
+/// - `closed`: Whether the line joins back up to its first point.
+/// - `width`: The width of the line, in pixels.
+/// - `cap`: The shape drawn at the ends of the line and every dash.
+/// - `join`: The shape drawn where its segments meet.
+/// - `pattern`: The pattern of dashes, or `None` for a solid line.
+#[expect(clippy::pub_with_shorthand, reason = "shared by every stroked node, but not part of the public API")]
+pub(crate) fn dashed_outline(points: &[Point], closed: bool, width: f64, cap: LineCap, join: LineJoin, pattern: Option<&DashPattern>) -> Vec<Segment> {
+    pattern.map_or_else(|| outline(points, closed, width, cap, join), |dashes| {
+        dashes.split_line(points, closed).iter().flat_map(|dash| outline(dash, false, width, cap, join)).collect()
+    })
+}
+
+/// Splits a line through `points` into the dashes drawn along it.
+/// # Returns
+/// The points each dash passes through, or the whole line, including its
+/// closing segment, if there is no pattern.
+#[expect(clippy::pub_with_shorthand, reason = "shared by every stroked node, but not part of the public API")]
+pub(crate) fn dashes(points: &[Point], closed: bool, pattern: Option<&DashPattern>) -> Vec<Vec<Point>> {
+    pattern.map_or_else(
+        || vec![points.iter().copied().chain(closed.then(|| points.first().copied()).flatten()).collect()],
+        |dashes| dashes.split_line(points, closed),
+    )
+}
+
+/// Gets the outline of a thick line through `points`, as shapes which can be
+/// filled by the rasterizer. Every piece of the outline is given the same
+/// direction, so where the pieces overlap they are filled once.
+/// # Parameters
+/// - `points`: The points the center of the line passes through.
+/// - `closed`: Whether the line joins back up to its first point, instead of
+///   having capped ends.
+/// - `width`: The width of the line, in pixels.
+/// - `cap`: The shape drawn at the ends of the line, if it is not closed.
+/// - `join`: The shape drawn where its segments meet.
+#[expect(clippy::pub_with_shorthand, reason = "shared by every stroked node, but not part of the public API")]
+pub(crate) fn outline(points: &[Point], closed: bool, width: f64, cap: LineCap, join: LineJoin) -> Vec<Segment> {
+    let half_width = width / 2.0f64;
+    let mut segments = Vec::new();
+    // repeated points have no direction, so are skipped
+    let mut path: Vec<Point> = Vec::with_capacity(points.len());
+    for &point in points {
+        if path.last().is_none_or(|&last| distance(last, point) > MIN_SEGMENT_LENGTH) {
+            path.push(point);
+        }
+    }
+    // as is a closed line's last point, if it is back where it started
+    if closed && path.len() > 1 && path.first().zip(path.last()).is_some_and(|(&first, &last)| distance(first, last) <= MIN_SEGMENT_LENGTH) {
+        path.pop();
+    }
+    let (Some(&first), Some(&last)) = (path.first(), path.last()) else {
+        return segments;
+    };
+    if path.len() == 1 {
+        // a line without length is a dot, if its caps give it a size
+        if !closed {
+            add_cap(&mut segments, first, (1.0f64, 0.0f64), half_width, cap);
+            add_cap(&mut segments, first, (-1.0f64, 0.0f64), half_width, cap);
+        }
+        return segments;
+    }
+    let closing = (closed && path.len() > 2).then_some((last, first));
+    let edges: Vec<(Point, Point)> = path.iter().copied().zip(path.iter().copied().skip(1)).chain(closing).collect();
+    for &(from, to) in &edges {
+        let (direction_x, direction_y) = direction(from, to);
+        let (normal_x, normal_y) = (-direction_y * half_width, direction_x * half_width);
+        add_polygon(&mut segments, &[
+            (from.0 + normal_x, from.1 + normal_y), (to.0 + normal_x, to.1 + normal_y),
+            (to.0 - normal_x, to.1 - normal_y), (from.0 - normal_x, from.1 - normal_y),
+        ]);
+    }
+    let wrap_around = closing.and_then(|_| Some((*edges.last()?, *edges.first()?)));
+    for (incoming, outgoing) in edges.iter().copied().zip(edges.iter().copied().skip(1)).chain(wrap_around) {
+        add_join(&mut segments, incoming, outgoing, half_width, join);
+    }
+    if let (None, Some(&(start, second)), Some(&(before_end, end))) = (closing, edges.first(), edges.last()) {
+        add_cap(&mut segments, start, direction(second, start), half_width, cap);
+        add_cap(&mut segments, end, direction(before_end, end), half_width, cap);
+    }
+    segments
+}
+
+/// Adds the shape filling the gap on the outside of the corner between two
+/// segments of a thick line.
+#[expect(clippy::single_call_fn, reason = "kept separate from outline for readability")]
+fn add_join(segments: &mut Vec<Segment>, incoming: (Point, Point), outgoing: (Point, Point), half_width: f64, join: LineJoin) {
+    let corner = incoming.1;
+    if join == LineJoin::Round {
+        add_circle(segments, corner, half_width);
+        return;
+    }
+    let (incoming_x, incoming_y) = direction(incoming.0, incoming.1);
+    let (outgoing_x, outgoing_y) = direction(outgoing.0, outgoing.1);
+    let turn = incoming_x.mul_add(outgoing_y, -(incoming_y * outgoing_x));
+    if turn == 0.0f64 {
+        return;
+    }
+    // the gap is on the opposite side to the one the line turns towards
+    let side = if turn > 0.0f64 { -half_width } else { half_width };
+    let incoming_normal = (-incoming_y * side, incoming_x * side);
+    let outgoing_normal = (-outgoing_y * side, outgoing_x * side);
+    let incoming_corner = (corner.0 + incoming_normal.0, corner.1 + incoming_normal.1);
+    let outgoing_corner = (corner.0 + outgoing_normal.0, corner.1 + outgoing_normal.1);
+    let bisector = (incoming_normal.0 + outgoing_normal.0, incoming_normal.1 + outgoing_normal.1);
+    let projection = bisector.0.mul_add(incoming_normal.0, bisector.1 * incoming_normal.1);
+    let miter_scale = half_width * half_width / projection;
+    // the tip is as far outside the corner as the inside of the corner is
+    // inside it, so this is half of the miter's length
+    let tip_distance = bisector.0.hypot(bisector.1) * miter_scale;
+    if join == LineJoin::Miter && tip_distance <= MITER_LIMIT * half_width {
+        let tip = (bisector.0.mul_add(miter_scale, corner.0), bisector.1.mul_add(miter_scale, corner.1));
+        add_polygon(segments, &[corner, incoming_corner, tip, outgoing_corner]);
+    } else {
+        add_polygon(segments, &[corner, incoming_corner, outgoing_corner]);
+    }
+}
+
+/// Adds the cap at one end of a thick line, where `(outward_x, outward_y)`
+/// is the direction pointing away from the rest of the line.
+fn add_cap(segments: &mut Vec<Segment>, end: Point, (outward_x, outward_y): Point, half_width: f64, cap: LineCap) {
+    match cap {
+        LineCap::Butt => {},
+        LineCap::Round => add_circle(segments, end, half_width),
+        LineCap::Square => {
+            let (normal_x, normal_y) = (-outward_y * half_width, outward_x * half_width);
+            let (extension_x, extension_y) = (outward_x * half_width, outward_y * half_width);
+            add_polygon(segments, &[
+                (end.0 + normal_x, end.1 + normal_y),
+                (end.0 + normal_x + extension_x, end.1 + normal_y + extension_y),
+                (end.0 - normal_x + extension_x, end.1 - normal_y + extension_y),
+                (end.0 - normal_x, end.1 - normal_y),
+            ]);
+        },
+    }
+}
+
+/// Adds a closed polygon, reversing it if needed so that it goes the same way
+/// round as every other piece of an outline.
+fn add_polygon(segments: &mut Vec<Segment>, points: &[Point]) {
+    let area: f64 = points.iter().zip(points.iter().cycle().skip(1))
+        .map(|(&(x1, y1), &(x2, y2))| x1.mul_add(y2, -(x2 * y1)))
+        .sum();
+    let mut ordered = points.to_vec();
+    if area < 0.0f64 {
+        ordered.reverse();
+    }
+    segments.extend(ordered.iter().zip(ordered.iter().cycle().skip(1)).map(|(&from, &to)| Segment::Line(from, to)));
+}
+
+/// Adds a circle, made of four cubic Bézier curves going the same way round
+/// as `add_polygon`'s polygons.
+fn add_circle(segments: &mut Vec<Segment>, center: Point, radius: f64) {
+    let control = radius * CIRCLE_CONTROL;
+    let point = |x: f64, y: f64| (center.0 + x, center.1 + y);
+    segments.extend([
+        Segment::Cubic(point(radius, 0.0f64), point(radius, control), point(control, radius), point(0.0f64, radius)),
+        Segment::Cubic(point(0.0f64, radius), point(-control, radius), point(-radius, control), point(-radius, 0.0f64)),
+        Segment::Cubic(point(-radius, 0.0f64), point(-radius, -control), point(-control, -radius), point(0.0f64, -radius)),
+        Segment::Cubic(point(0.0f64, -radius), point(control, -radius), point(radius, -control), point(radius, 0.0f64)),
+    ]);
+}
+
+/// Gets the distance between two points.
+fn distance(from: Point, to: Point) -> f64 {
+    (to.0 - from.0).hypot(to.1 - from.1)
+}
+
+/// Gets the direction from one point to another, as a vector one pixel long.
+fn direction(from: Point, to: Point) -> Point {
+    let length = distance(from, to);
+    ((to.0 - from.0) / length, (to.1 - from.1) / length)
+}
@@ -0,0 +1,1096 @@
+//! Loading of SVG (`.svg`) vector images, such as icons exported from design
+//! tools like Figma, Inkscape and Illustrator, which stay sharp at any size.
+//! ```rust
+//! let icon = Svg::load("assets/icons/settings.svg").unwrap();
+//! let mut node = SvgNode::new(&icon, 10.0, 10.0);
+//! node.set_size((48.0, 48.0));
+//! texture.add(&node);
+//! ```
+//! A practical subset of SVG is supported:
+//! - The `path`, `rect`, `circle`, `ellipse`, `line`, `polyline` and
+//!   `polygon` shapes, grouped with `g`.
+//! - The `transform` attribute, and the `viewBox` and `preserveAspectRatio` of
+//!   the image.
+//! - Solid fill and stroke colors, as hex, `rgb()`, `rgba()` or one of the
+//!   basic color names, along with their opacity, the fill rule, and the
+//!   width, caps, joins and dashes of strokes. These can be given either as
+//!   attributes or in a `style` attribute.
+//!
+//! Everything else, such as text, gradients, `use`, masks and `<style>`
+//! sheets, is skipped. The opacity of a group is applied to each shape in it
+//! separately, so overlapping shapes in a translucent group show through each
+//! other.
+
+use core::{f64::consts::{FRAC_PI_2, PI, TAU}, iter};
+use std::{fs, path::Path as FilePath};
+
+use anyhow::{Context as _, Error};
+
+use crate::{BlendMode, Color, Texture, color::channel_from_f64, component::{BlendModeComponent, DrawComponent, PositionComponent, SizeComponent}, path::Path, polygon::FillRule, raster::{self, Point, Segment}, stroke::{self, DashPattern, LineCap, LineJoin}, transform::Transform};
+
+/// The size of an image which gives neither its own size nor a `viewBox`, the
+/// same as in web browsers.
+const DEFAULT_SIZE: (f64, f64) = (300.0, 150.0);
+
+/// The elements whose contents are never drawn directly, and are skipped.
+const SKIPPED_ELEMENTS: [&str; 16] = [
+    "clipPath", "defs", "desc", "filter", "foreignObject", "linearGradient", "marker", "mask",
+    "metadata", "pattern", "radialGradient", "script", "style", "symbol", "text", "title",
+];
+
+/// The colors which can be given by name.
+const NAMED_COLORS: [(&str, Color); 21] = [
+    ("black", Color::BLACK),
+    ("silver", Color::rgb(192, 192, 192)),
+    ("gray", Color::rgb(128, 128, 128)),
+    ("grey", Color::rgb(128, 128, 128)),
+    ("white", Color::WHITE),
+    ("maroon", Color::rgb(128, 0, 0)),
+    ("red", Color::RED),
+    ("purple", Color::rgb(128, 0, 128)),
+    ("fuchsia", Color::rgb(255, 0, 255)),
+    ("magenta", Color::rgb(255, 0, 255)),
+    ("green", Color::rgb(0, 128, 0)),
+    ("lime", Color::rgb(0, 255, 0)),
+    ("olive", Color::rgb(128, 128, 0)),
+    ("yellow", Color::rgb(255, 255, 0)),
+    ("navy", Color::rgb(0, 0, 128)),
+    ("blue", Color::BLUE),
+    ("teal", Color::rgb(0, 128, 128)),
+    ("aqua", Color::rgb(0, 255, 255)),
+    ("cyan", Color::rgb(0, 255, 255)),
+    ("orange", Color::rgb(255, 165, 0)),
+    ("transparent", Color::rgba(0, 0, 0, 0)),
+];
+
+/// A vector image loaded from an SVG file, which can be drawn at any size
+/// with an `SvgNode`.
+/// # Example
+/// ```rust
+/// let logo = Svg::load("assets/logo.svg").unwrap();
+/// // draw the logo at twice its size
+/// let mut node = SvgNode::new(&logo, 0.0, 0.0);
+/// node.set_size((logo.get_width() * 2.0, logo.get_height() * 2.0));
+/// texture.add(&node);
+/// ```
+#[non_exhaustive]
+pub struct Svg {
+    /// The width of the image, in pixels.
+    width: f64,
+    /// The height of the image, in pixels.
+    height: f64,
+    /// Every shape in the image, in the order they are drawn, positioned in
+    /// pixels from the top-left corner of the image.
+    shapes: Vec<Shape>,
+}
+
+/// A single filled and stroked shape of an `Svg`.
+struct Shape {
+    /// The outline of the shape.
+    path: Path,
+    /// The color the inside of the shape is filled with, or `None` if it is
+    /// not filled.
+    fill: Option<Color>,
+    /// How the inside of the shape is decided where its outline overlaps
+    /// itself.
+    fill_rule: FillRule,
+    /// How the outline of the shape is drawn, or `None` if it is not.
+    stroke: Option<Stroke>,
+}
+
+/// How the outline of an SVG shape is drawn.
+struct Stroke {
+    /// The color of the outline.
+    color: Color,
+    /// The width of the outline, in pixels.
+    width: f64,
+    /// The shape drawn at the open ends of the outline.
+    cap: LineCap,
+    /// The shape drawn at the corners of the outline.
+    join: LineJoin,
+    /// The pattern of dashes the outline is drawn with, in pixels, or `None`
+    /// to draw it solid.
+    dash_pattern: Option<DashPattern>,
+}
+
+impl Svg {
+    /// Loads an SVG file.
+    /// # Arguments
+    /// * `path` - The path of the `.svg` file to load.
+    /// # Returns
+    /// An `Svg` which can be drawn with an `SvgNode`.
+    /// # Errors
+    /// Errors if the file cannot be read, or is not a valid SVG image.
+    /// # Example
+    /// ```rust
+    /// let icon = Svg::load("assets/icons/close.svg")
+    ///     .expect("failed to load icon");
+    /// ```
+    pub fn load<P: AsRef<FilePath>>(path: P) -> Result<Svg, Error> {
+        let bytes = fs::read(path).context("Pixl: Svg::load: failed to read file")?;
+        Svg::decode(&bytes)
+    }
+
+    /// Decodes an in-memory SVG file.
+    /// Shapes are drawn up to the first error in their path data, as in web
+    /// browsers, and attributes with invalid values are ignored.
+    /// # Arguments
+    /// * `bytes` - The contents of a `.svg` file.
+    /// # Returns
+    /// An `Svg` which can be drawn with an `SvgNode`.
+    /// # Errors
+    /// Errors if the file is not valid XML, or its root element is not an
+    /// `svg` element.
+    /// # Example
+    /// ```rust
+    /// let icon = Svg::decode(include_bytes!("close.svg")).expect("invalid icon");
+    /// ```
+    pub fn decode(bytes: &[u8]) -> Result<Svg, Error> {
+        let text = String::from_utf8_lossy(bytes);
+        let mut tags = tags(&text)?.into_iter();
+        let root = tags.find_map(|tag| match tag {
+            Tag::Open(element) => Some(element),
+            Tag::Close => None,
+        }).ok_or_else(|| Error::msg("Pixl: Svg::decode: the file contained no elements"))?;
+        if root.name != "svg" {
+            return Err(Error::msg("Pixl: Svg::decode: the root element was not an svg element"));
+        }
+        let (width, height, view_box) = viewport(&root);
+        let base = Style { transform: view_box, ..Style::default() };
+        let mut stack = vec![base.child(&root)];
+        let mut shapes = Vec::new();
+        if root.empty {
+            return Ok(Svg { width, height, shapes });
+        }
+        for tag in tags {
+            let Tag::Open(element) = tag else {
+                stack.pop();
+                if stack.is_empty() {
+                    break;
+                }
+                continue;
+            };
+            let style = stack.last().and_then(Option::as_ref)
+                .filter(|_| !SKIPPED_ELEMENTS.contains(&element.name))
+                .and_then(|parent| parent.child(&element));
+            shapes.extend(style.as_ref().and_then(|current| shape(&element, current)));
+            if !element.empty {
+                stack.push(style);
+            }
+        }
+        Ok(Svg { width, height, shapes })
+    }
+
+    /// Gets the width of the image, which is the width it is drawn at by
+    /// default.
+    /// # Returns
+    /// The width of the image, in pixels.
+    #[must_use]
+    pub const fn get_width(&self) -> f64 {
+        self.width
+    }
+
+    /// Gets the height of the image, which is the height it is drawn at by
+    /// default.
+    /// # Returns
+    /// The height of the image, in pixels.
+    #[must_use]
+    pub const fn get_height(&self) -> f64 {
+        self.height
+    }
+}
+
+/// A node which draws an `Svg` image onto a texture, scaled to any size.
+/// The position is the top-left corner of the image, and the size is the size
+/// it is drawn at: by default, the size of the image itself.
+/// ## Implemented components:
+/// - `DrawComponent`
+/// - `PositionComponent`
+/// - `SizeComponent`
+/// - `BlendModeComponent`
+/// # Example
+/// ```rust
+/// let heart = Svg::load("assets/icons/heart.svg").unwrap();
+/// for life in 0..lives {
+///     let mut node = SvgNode::new(&heart, 4.0 + cast::f64(life) * 20.0, 4.0);
+///     node.set_size((16.0, 16.0));
+///     texture.add(&node);
+/// }
+/// ```
+#[non_exhaustive]
+#[expect(clippy::module_name_repetitions, reason = "struct should be called 'SvgNode' as it is standard.")]
+pub struct SvgNode<'svg> {
+    /// The image to draw.
+    pub svg: &'svg Svg,
+
+    /// The x-coordinate of the top-left corner of the image.
+    pub x: f64,
+
+    /// The y-coordinate of the top-left corner of the image.
+    pub y: f64,
+
+    /// The width the image is drawn at, in pixels.
+    pub width: f64,
+
+    /// The height the image is drawn at, in pixels.
+    pub height: f64,
+
+    /// Whether the image is drawn with smooth, anti-aliased edges.
+    /// Defaults to true, as vector images are made to be drawn smoothly.
+    pub anti_aliased: bool,
+
+    /// How the image's colors are combined with the existing pixels when
+    /// drawn. Defaults to `BlendMode::SourceOver`.
+    pub blend_mode: BlendMode,
+}
+
+impl<'svg> SvgNode<'svg> {
+    /// Create a new `SvgNode` drawing `svg` with its top-left corner at
+    /// (`x`, `y`), at its original size.
+    /// # Parameters
+    /// - `svg`: The image to draw.
+    /// - `x`: The x-coordinate of the top-left corner of the image.
+    /// - `y`: The y-coordinate of the top-left corner of the image.
+    /// # Returns
+    /// A new, anti-aliased `SvgNode`.
+    #[must_use]
+    pub const fn new(svg: &'svg Svg, x: f64, y: f64) -> SvgNode<'svg> {
+        SvgNode {
+            svg, x, y,
+            width: svg.width,
+            height: svg.height,
+            anti_aliased: true,
+            blend_mode: BlendMode::SourceOver,
+        }
+    }
+}
+
+impl DrawComponent for SvgNode<'_> {
+    fn draw(&self, texture: &mut Texture) {
+        let scale = |size: f64, original: f64| if original > 0.0f64 { size / original } else { 1.0f64 };
+        let (scale_x, scale_y) = (scale(self.width, self.svg.width), scale(self.height, self.svg.height));
+        let transform = Transform::scaling(scale_x, scale_y).then(Transform::translation(self.x, self.y));
+        // strokes are scaled by the average of the two scales
+        let stroke_scale = (scale_x * scale_y).abs().sqrt();
+        for shape in &self.svg.shapes {
+            let path = shape.path.transformed(transform);
+            if let Some(color) = shape.fill {
+                raster::fill(texture, &path.fill_segments(), color, self.blend_mode, self.anti_aliased, shape.fill_rule);
+            }
+            let Some(stroke) = shape.stroke.as_ref() else {
+                continue;
+            };
+            let dash_pattern = stroke.dash_pattern.as_ref().map(|pattern| DashPattern {
+                dashes: pattern.dashes.iter().map(|&length| length * stroke_scale).collect(),
+                offset: pattern.offset * stroke_scale,
+            });
+            let outline: Vec<Segment> = path.flattened().into_iter().flat_map(|(points, closed)| {
+                stroke::dashed_outline(&points, closed, stroke.width * stroke_scale, stroke.cap, stroke.join, dash_pattern.as_ref())
+            }).collect();
+            raster::fill(texture, &outline, stroke.color, self.blend_mode, self.anti_aliased, FillRule::NonZero);
+        }
+    }
+}
+
+impl PositionComponent for SvgNode<'_> {
+    fn get_x(&self) -> f64 {
+        self.x
+    }
+
+    fn get_y(&self) -> f64 {
+        self.y
+    }
+
+    fn set_x(&mut self, x: f64) {
+        self.x = x;
+    }
+
+    fn set_y(&mut self, y: f64) {
+        self.y = y;
+    }
+}
+
+impl SizeComponent for SvgNode<'_> {
+    fn get_width(&self) -> f64 {
+        self.width
+    }
+
+    fn get_height(&self) -> f64 {
+        self.height
+    }
+
+    fn set_width(&mut self, width: f64) {
+        self.width = width;
+    }
+
+    fn set_height(&mut self, height: f64) {
+        self.height = height;
+    }
+}
+
+impl BlendModeComponent for SvgNode<'_> {
+    fn get_blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+}
+
+/// A piece of markup in an XML file.
+enum Tag<'text> {
+    /// The start of an element, or an empty element such as `<rect/>`.
+    Open(Element<'text>),
+    /// The end of an element.
+    Close,
+}
+
+/// An XML element, without its contents.
+struct Element<'text> {
+    /// The name of the element, without any namespace prefix.
+    name: &'text str,
+    /// The name and value of each of the element's attributes, in order.
+    attributes: Vec<(&'text str, &'text str)>,
+    /// Whether the element has no contents and closes itself, as in
+    /// `<rect/>`.
+    empty: bool,
+}
+
+/// Splits an XML file into its tags, skipping comments, text, declarations
+/// and processing instructions.
+#[expect(clippy::single_call_fn, reason = "kept separate from Svg::decode for readability")]
+fn tags(text: &str) -> Result<Vec<Tag<'_>>, Error> {
+    let mut tags = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        let (_, markup) = rest.split_at_checked(start).unwrap_or_default();
+        rest = if let Some(comment) = markup.strip_prefix("<!--") {
+            after(comment, "-->")?
+        } else if let Some(data) = markup.strip_prefix("<![CDATA[") {
+            after(data, "]]>")?
+        } else if let Some(instruction) = markup.strip_prefix("<?") {
+            after(instruction, "?>")?
+        } else if let Some(declaration) = markup.strip_prefix("<!") {
+            // a doctype can contain declarations of its own, in brackets
+            let internal = declaration.find('[').is_some_and(|bracket| declaration.find('>').is_none_or(|end| bracket < end));
+            after(if internal { after(declaration, "]")? } else { declaration }, ">")?
+        } else if let Some(end) = markup.strip_prefix("</") {
+            tags.push(Tag::Close);
+            after(end, ">")?
+        } else {
+            let (element, remaining) = open_tag(markup.strip_prefix('<').unwrap_or(markup))?;
+            tags.push(Tag::Open(element));
+            remaining
+        };
+    }
+    Ok(tags)
+}
+
+/// Gets the text after the first `terminator` in `text`.
+fn after<'text>(text: &'text str, terminator: &str) -> Result<&'text str, Error> {
+    text.split_once(terminator).map(|(_, rest)| rest)
+        .ok_or_else(|| Error::msg(format!("Pixl: Svg::decode: markup was missing its closing '{terminator}'")))
+}
+
+/// Reads an opening tag, starting just after its `<`.
+/// # Returns
+/// The element, and the text after the tag.
+#[expect(clippy::single_call_fn, reason = "kept separate from tags for readability")]
+fn open_tag(text: &str) -> Result<(Element<'_>, &str), Error> {
+    let name_end = text.find(|character: char| character.is_ascii_whitespace() || character == '/' || character == '>').unwrap_or(text.len());
+    let (name, mut rest) = text.split_at_checked(name_end).unwrap_or((text, ""));
+    let mut attributes = Vec::new();
+    loop {
+        rest = rest.trim_start();
+        if let Some(remaining) = rest.strip_prefix("/>") {
+            return Ok((Element { name: local_name(name), attributes, empty: true }, remaining));
+        }
+        if let Some(remaining) = rest.strip_prefix('>') {
+            return Ok((Element { name: local_name(name), attributes, empty: false }, remaining));
+        }
+        if rest.is_empty() {
+            return Err(Error::msg(format!("Pixl: Svg::decode: '{name}' element was missing its closing '>'")));
+        }
+        let invalid = || Error::msg(format!("Pixl: Svg::decode: invalid attribute in '{name}' element"));
+        let (attribute, after_name) = rest.split_once('=').ok_or_else(invalid)?;
+        let value_start = after_name.trim_start();
+        let quote = value_start.chars().next().filter(|&character| character == '"' || character == '\'').ok_or_else(invalid)?;
+        let (value, remaining) = value_start.strip_prefix(quote).and_then(|quoted| quoted.split_once(quote)).ok_or_else(invalid)?;
+        attributes.push((local_name(attribute.trim()), value));
+        rest = remaining;
+    }
+}
+
+/// Removes the namespace prefix from an element or attribute name, such as
+/// the `svg:` in `svg:rect`.
+fn local_name(name: &str) -> &str {
+    name.rsplit_once(':').map_or(name, |(_, local)| local)
+}
+
+/// Gets the value of an element's attribute, if it has it.
+fn attribute<'text>(element: &Element<'text>, name: &str) -> Option<&'text str> {
+    element.attributes.iter().find(|&&(key, _)| key == name).map(|&(_, value)| value)
+}
+
+/// Gets the size of the image from its root `svg` element, and the
+/// transformation from the `viewBox` to the image.
+/// # Returns
+/// The (width, height, transformation) of the image.
+#[expect(clippy::single_call_fn, reason = "kept separate from Svg::decode for readability")]
+fn viewport(root: &Element<'_>) -> (f64, f64, Transform) {
+    let view_box = attribute(root, "viewBox")
+        .and_then(|value| <[f64; 4]>::try_from(numbers(value)).ok())
+        .filter(|&[_, _, width, height]| width > 0.0f64 && height > 0.0f64);
+    let size = |name: &str| attribute(root, name).and_then(length).filter(|&size| size >= 0.0f64 && size.is_finite());
+    let (given_width, given_height) = (size("width"), size("height"));
+    let Some([min_x, min_y, view_width, view_height]) = view_box else {
+        return (given_width.unwrap_or(DEFAULT_SIZE.0), given_height.unwrap_or(DEFAULT_SIZE.1), Transform::IDENTITY);
+    };
+    // a missing width or height keeps the shape of the view box
+    let width = given_width.or_else(|| given_height.map(|height| height * view_width / view_height)).unwrap_or(view_width);
+    let height = given_height.unwrap_or(width * view_height / view_width);
+    let (mut scale_x, mut scale_y) = (width / view_width, height / view_height);
+    let mut words = attribute(root, "preserveAspectRatio").unwrap_or_default().split_whitespace().filter(|&word| word != "defer");
+    let align = words.next().unwrap_or("xMidYMid");
+    if align != "none" {
+        let scale = if words.next() == Some("slice") { scale_x.max(scale_y) } else { scale_x.min(scale_y) };
+        (scale_x, scale_y) = (scale, scale);
+    }
+    let position = |alignment: Option<&str>, minimum: &str, maximum: &str| match alignment {
+        Some(value) if value == minimum => 0.0f64,
+        Some(value) if value == maximum => 1.0f64,
+        _ => 0.5f64,
+    };
+    let align_x = position(align.get(..4), "xMin", "xMax");
+    let align_y = position(align.get(4..), "YMin", "YMax");
+    let transform = Transform::translation(-min_x, -min_y)
+        .then(Transform::scaling(scale_x, scale_y))
+        .then(Transform::translation(align_x * view_width.mul_add(-scale_x, width), align_y * view_height.mul_add(-scale_y, height)));
+    (width, height, transform)
+}
+
+/// What a shape is filled or stroked with.
+#[derive(Copy, Clone)]
+enum Paint {
+    /// Nothing: the shape is not filled or stroked.
+    None,
+    /// A solid color.
+    Color(Color),
+    /// The color given by the `color` property.
+    CurrentColor,
+}
+
+impl Paint {
+    /// Gets the color painted, if any.
+    /// # Parameters
+    /// - `current`: The color given by the `color` property.
+    /// - `opacity`: How opaque the paint is, from 0.0 to 1.0.
+    fn color(self, current: Color, opacity: f64) -> Option<Color> {
+        match self {
+            Paint::None => None,
+            Paint::Color(color) => Some(color.multiply_alpha(opacity)),
+            Paint::CurrentColor => Some(current.multiply_alpha(opacity)),
+        }
+    }
+}
+
+/// The properties an element is drawn with, which are passed down from each
+/// group to the elements inside it.
+#[derive(Clone)]
+struct Style {
+    /// What the inside of shapes is filled with.
+    fill: Paint,
+    /// How opaque the fill is, from 0.0 to 1.0.
+    fill_opacity: f64,
+    /// How the inside of shapes is decided where they overlap themselves.
+    fill_rule: FillRule,
+    /// What the outlines of shapes are drawn with.
+    stroke: Paint,
+    /// How opaque the outline is, from 0.0 to 1.0.
+    stroke_opacity: f64,
+    /// The width of the outline, before it is transformed.
+    stroke_width: f64,
+    /// The shape drawn at the open ends of the outline.
+    line_cap: LineCap,
+    /// The shape drawn at the corners of the outline.
+    line_join: LineJoin,
+    /// The lengths of the dashes and gaps of the outline, before they are
+    /// transformed, or empty for a solid outline.
+    dashes: Vec<f64>,
+    /// How far into the dashes the outline starts, before it is transformed.
+    dash_offset: f64,
+    /// The color used by `currentColor`.
+    color: Color,
+    /// How opaque the element is, including the opacity of every group
+    /// around it.
+    opacity: f64,
+    /// Whether shapes are drawn at all.
+    visible: bool,
+    /// The transformation from the element's coordinates to the image.
+    transform: Transform,
+}
+
+impl Default for Style {
+    fn default() -> Style {
+        Style {
+            fill: Paint::Color(Color::BLACK),
+            fill_opacity: 1.0,
+            fill_rule: FillRule::NonZero,
+            stroke: Paint::None,
+            stroke_opacity: 1.0,
+            stroke_width: 1.0,
+            line_cap: LineCap::Butt,
+            line_join: LineJoin::Miter,
+            dashes: Vec::new(),
+            dash_offset: 0.0,
+            color: Color::BLACK,
+            opacity: 1.0,
+            visible: true,
+            transform: Transform::IDENTITY,
+        }
+    }
+}
+
+impl Style {
+    /// Gets the style of an element inside an element with this style.
+    /// # Returns
+    /// The element's style, or `None` if it is hidden by `display: none`, so
+    /// neither it nor anything inside it is drawn.
+    fn child(&self, element: &Element<'_>) -> Option<Style> {
+        // properties in the style attribute take priority over attributes
+        let mut properties = element.attributes.clone();
+        for declaration in attribute(element, "style").unwrap_or_default().split(';') {
+            if let Some((name, value)) = declaration.split_once(':') {
+                let property = name.trim();
+                properties.retain(|&(existing, _)| existing != property);
+                properties.push((property, value.trim().trim_end_matches("!important").trim_end()));
+            }
+        }
+        if properties.contains(&("display", "none")) {
+            return None;
+        }
+        let mut style = self.clone();
+        if let Some(transform) = attribute(element, "transform").and_then(transform_list) {
+            style.transform = transform.then(self.transform);
+        }
+        for (name, value) in properties {
+            style.set(name, value);
+        }
+        Some(style)
+    }
+
+    /// Sets a property from its SVG name and value. Unknown properties and
+    /// invalid values are ignored, which also keeps the value of the group
+    /// around the element for `inherit`.
+    fn set(&mut self, name: &str, value: &str) {
+        match name {
+            "fill" => if let Some(paint) = paint(value) {
+                self.fill = paint;
+            },
+            "fill-opacity" => if let Some(opacity) = opacity(value) {
+                self.fill_opacity = opacity;
+            },
+            "fill-rule" => match value {
+                "nonzero" => self.fill_rule = FillRule::NonZero,
+                "evenodd" => self.fill_rule = FillRule::EvenOdd,
+                _ => {},
+            },
+            "stroke" => if let Some(paint) = paint(value) {
+                self.stroke = paint;
+            },
+            "stroke-opacity" => if let Some(opacity) = opacity(value) {
+                self.stroke_opacity = opacity;
+            },
+            "stroke-width" => if let Some(width) = length(value) {
+                self.stroke_width = width;
+            },
+            "stroke-linecap" => match value {
+                "butt" => self.line_cap = LineCap::Butt,
+                "round" => self.line_cap = LineCap::Round,
+                "square" => self.line_cap = LineCap::Square,
+                _ => {},
+            },
+            "stroke-linejoin" => match value {
+                "miter" | "miter-clip" | "arcs" => self.line_join = LineJoin::Miter,
+                "round" => self.line_join = LineJoin::Round,
+                "bevel" => self.line_join = LineJoin::Bevel,
+                _ => {},
+            },
+            "stroke-dasharray" => {
+                let dashes = if value == "none" {
+                    Some(Vec::new())
+                } else {
+                    value.split([',', ' ']).filter(|dash| !dash.is_empty()).map(length).collect()
+                };
+                if let Some(lengths) = dashes {
+                    self.dashes = lengths;
+                }
+            },
+            "stroke-dashoffset" => if let Some(offset) = length(value) {
+                self.dash_offset = offset;
+            },
+            "color" => if let Some(color) = color(value) {
+                self.color = color;
+            },
+            "opacity" => if let Some(opacity) = opacity(value) {
+                self.opacity *= opacity;
+            },
+            "visibility" => match value {
+                "visible" => self.visible = true,
+                "hidden" | "collapse" => self.visible = false,
+                _ => {},
+            },
+            _ => {},
+        }
+    }
+}
+
+/// Reads a shape element, such as a `rect` or `path`, into a shape in the
+/// image's coordinates.
+/// # Returns
+/// The shape, or `None` if the element is not a shape, is invalid, or would
+/// not draw anything.
+#[expect(clippy::single_call_fn, reason = "kept separate from Svg::decode for readability")]
+fn shape(element: &Element<'_>, style: &Style) -> Option<Shape> {
+    let number = |name: &str| attribute(element, name).and_then(length);
+    let coordinate = |name: &str| number(name).unwrap_or(0.0f64);
+    let local = match element.name {
+        "path" => path_data(attribute(element, "d")?),
+        "rect" => rectangle((coordinate("x"), coordinate("y")), (number("width")?, number("height")?), (number("rx"), number("ry")))?,
+        "circle" => {
+            let radius = number("r")?;
+            ellipse((coordinate("cx"), coordinate("cy")), (radius, radius))?
+        },
+        "ellipse" => ellipse((coordinate("cx"), coordinate("cy")), (number("rx")?, number("ry")?))?,
+        "line" => {
+            let mut line = Path::new();
+            line.move_to(coordinate("x1"), coordinate("y1")).line_to(coordinate("x2"), coordinate("y2"));
+            line
+        },
+        "polyline" | "polygon" => {
+            let mut polyline = Path::new();
+            for pair in numbers(attribute(element, "points")?).chunks_exact(2) {
+                if let &[x, y] = pair {
+                    polyline.line_to(x, y);
+                }
+            }
+            if element.name == "polygon" {
+                polyline.close();
+            }
+            polyline
+        },
+        _ => return None,
+    };
+    if !style.visible {
+        return None;
+    }
+    // strokes are scaled by the average of how much the transformation
+    // scales each way
+    let transform = style.transform;
+    let scale = transform.a.mul_add(transform.d, -(transform.b * transform.c)).abs().sqrt();
+    let fill = style.fill.color(style.color, style.fill_opacity * style.opacity);
+    let stroke = style.stroke.color(style.color, style.stroke_opacity * style.opacity)
+        .filter(|_| style.stroke_width > 0.0f64)
+        .map(|color| Stroke {
+            color,
+            width: style.stroke_width * scale,
+            cap: style.line_cap,
+            join: style.line_join,
+            dash_pattern: (!style.dashes.is_empty()).then(|| DashPattern {
+                dashes: style.dashes.iter().map(|&dash| dash * scale).collect(),
+                offset: style.dash_offset * scale,
+            }),
+        });
+    (fill.is_some() || stroke.is_some()).then(|| Shape { path: local.transformed(transform), fill, fill_rule: style.fill_rule, stroke })
+}
+
+/// Builds the outline of a `rect` element, with its corners rounded if it has
+/// `rx` or `ry` radii.
+/// # Returns
+/// The outline, or `None` if the rectangle has no area.
+#[expect(clippy::single_call_fn, reason = "kept separate from shape for readability")]
+fn rectangle((x, y): Point, (width, height): Point, radii: (Option<f64>, Option<f64>)) -> Option<Path> {
+    if !(width > 0.0f64 && height > 0.0f64) {
+        return None;
+    }
+    // a single radius is used for both directions
+    let radius_x = radii.0.or(radii.1).unwrap_or(0.0f64).clamp(0.0, width / 2.0);
+    let radius_y = radii.1.or(radii.0).unwrap_or(0.0f64).clamp(0.0, height / 2.0);
+    let mut path = Path::new();
+    if radius_x > 0.0f64 && radius_y > 0.0f64 {
+        let (left, top) = (x + radius_x, y + radius_y);
+        let (right, bottom) = (x + width - radius_x, y + height - radius_y);
+        path.move_to(left, y);
+        for (center, start_angle) in [((right, top), -FRAC_PI_2), ((right, bottom), 0.0f64), ((left, bottom), FRAC_PI_2), ((left, top), PI)] {
+            path.add_arc(center, (radius_x, radius_y), 0.0, start_angle, FRAC_PI_2);
+        }
+    } else {
+        path.move_to(x, y).line_to(x + width, y).line_to(x + width, y + height).line_to(x, y + height);
+    }
+    path.close();
+    Some(path)
+}
+
+/// Builds the outline of a `circle` or `ellipse` element.
+/// # Returns
+/// The outline, or `None` if the ellipse has no area.
+fn ellipse(center: Point, radii: Point) -> Option<Path> {
+    if !(radii.0 > 0.0f64 && radii.1 > 0.0f64) {
+        return None;
+    }
+    let mut path = Path::new();
+    path.move_to(center.0 + radii.0, center.1).add_arc(center, radii, 0.0, 0.0, TAU).close();
+    Some(path)
+}
+
+/// Reads path data, the `d` attribute of a `path` element. As in web
+/// browsers, the path is drawn up to the first error in it.
+#[expect(clippy::single_call_fn, reason = "kept separate from shape for readability")]
+fn path_data(data: &str) -> Path {
+    let mut parser = PathParser {
+        path: Path::new(),
+        current: (0.0f64, 0.0f64),
+        start: (0.0f64, 0.0f64),
+        cubic_control: None,
+        quadratic_control: None,
+    };
+    let mut scanner = Scanner { rest: data };
+    let (mut started, mut repeated) = (false, None);
+    while let Some(command) = scanner.letter().or_else(|| repeated.filter(|_| !scanner.is_empty())) {
+        // path data must start by moving to a point
+        if !(started || matches!(command, 'M' | 'm')) || parser.command(command, &mut scanner).is_none() {
+            break;
+        }
+        started = true;
+        // numbers after a command repeat it, with a move followed by lines
+        repeated = match command {
+            'M' => Some('L'),
+            'm' => Some('l'),
+            'Z' | 'z' => None,
+            other => Some(other),
+        };
+    }
+    parser.path
+}
+
+/// The state kept while reading path data.
+struct PathParser {
+    /// The path read so far.
+    path: Path,
+    /// The point the path currently ends at.
+    current: Point,
+    /// The point the current piece of the path started at.
+    start: Point,
+    /// The second control point of the last command, if it was a cubic
+    /// Bézier curve.
+    cubic_control: Option<Point>,
+    /// The control point of the last command, if it was a quadratic Bézier
+    /// curve.
+    quadratic_control: Option<Point>,
+}
+
+impl PathParser {
+    /// Reads the numbers for a single command, and adds it to the path.
+    /// Lowercase commands are relative to the current point.
+    /// # Returns
+    /// `None` if the command or its numbers are invalid.
+    fn command(&mut self, command: char, scanner: &mut Scanner<'_>) -> Option<()> {
+        let origin = if command.is_ascii_lowercase() { self.current } else { (0.0f64, 0.0f64) };
+        let (mut cubic_control, mut quadratic_control) = (None, None);
+        let to = match command.to_ascii_uppercase() {
+            'M' => {
+                let to = scanner.point(origin)?;
+                self.path.move_to(to.0, to.1);
+                self.start = to;
+                to
+            },
+            'L' => {
+                let to = scanner.point(origin)?;
+                self.path.line_to(to.0, to.1);
+                to
+            },
+            'H' => {
+                let to = (scanner.number()? + origin.0, self.current.1);
+                self.path.line_to(to.0, to.1);
+                to
+            },
+            'V' => {
+                let to = (self.current.0, scanner.number()? + origin.1);
+                self.path.line_to(to.0, to.1);
+                to
+            },
+            'C' | 'S' => {
+                let first = if command.eq_ignore_ascii_case(&'C') { scanner.point(origin)? } else { self.reflect(self.cubic_control) };
+                let (second, to) = (scanner.point(origin)?, scanner.point(origin)?);
+                self.path.cubic_to(first.0, first.1, second.0, second.1, to.0, to.1);
+                cubic_control = Some(second);
+                to
+            },
+            'Q' | 'T' => {
+                let control = if command.eq_ignore_ascii_case(&'Q') { scanner.point(origin)? } else { self.reflect(self.quadratic_control) };
+                let to = scanner.point(origin)?;
+                self.path.quad_to(control.0, control.1, to.0, to.1);
+                quadratic_control = Some(control);
+                to
+            },
+            'A' => {
+                let (radii, rotation) = ((scanner.number()?, scanner.number()?), scanner.number()?);
+                let (large_arc, sweep) = (scanner.flag()?, scanner.flag()?);
+                let to = scanner.point(origin)?;
+                self.arc(radii, rotation.to_radians(), large_arc, sweep, to);
+                to
+            },
+            'Z' => {
+                self.path.close();
+                self.start
+            },
+            _ => return None,
+        };
+        (self.current, self.cubic_control, self.quadratic_control) = (to, cubic_control, quadratic_control);
+        Some(())
+    }
+
+    /// Gets the first control point of a smooth curve, which is the last
+    /// curve's control point mirrored around the current point, or the
+    /// current point itself if the last command was not a matching curve.
+    fn reflect(&self, control: Option<Point>) -> Point {
+        control.map_or(self.current, |(x, y)| (self.current.0.mul_add(2.0, -x), self.current.1.mul_add(2.0, -y)))
+    }
+
+    /// Adds an elliptical arc from the current point, turned by `rotation`
+    /// radians. Of the four arcs between the points, `large_arc` chooses one
+    /// going more than half way around, and `sweep` one going clockwise.
+    /// Radii too small to reach are scaled up until they just do.
+    fn arc(&mut self, radii: Point, rotation: f64, large_arc: bool, sweep: bool, to: Point) {
+        let from = self.current;
+        let half = ((from.0 - to.0) / 2.0f64, (from.1 - to.1) / 2.0f64);
+        let (mut radius_x, mut radius_y) = (radii.0.abs(), radii.1.abs());
+        if half.0 == 0.0f64 && half.1 == 0.0f64 {
+            return;
+        }
+        if !(radius_x > 0.0f64 && radius_y > 0.0f64) {
+            self.path.line_to(to.0, to.1);
+            return;
+        }
+        // the start point relative to the middle of the arc, unrotated
+        let (sin, cos) = rotation.sin_cos();
+        let x = cos.mul_add(half.0, sin * half.1);
+        let y = cos.mul_add(half.1, -(sin * half.0));
+        let reach = (x / radius_x).hypot(y / radius_y);
+        if reach > 1.0f64 {
+            radius_x *= reach;
+            radius_y *= reach;
+        }
+        // find the center, relative to the middle of the arc
+        let (squared_x, squared_y) = ((radius_x * y).powi(2), (radius_y * x).powi(2));
+        let mut factor = (radius_x * radius_y).mul_add(radius_x * radius_y, -squared_x - squared_y) / (squared_x + squared_y);
+        factor = factor.max(0.0).sqrt();
+        if large_arc == sweep {
+            factor = -factor;
+        }
+        let (center_x, center_y) = (factor * radius_x * y / radius_y, -factor * radius_y * x / radius_x);
+        let center = (
+            cos.mul_add(center_x, sin.mul_add(-center_y, from.0.midpoint(to.0))),
+            sin.mul_add(center_x, cos.mul_add(center_y, from.1.midpoint(to.1))),
+        );
+        let start_angle = ((y - center_y) / radius_y).atan2((x - center_x) / radius_x);
+        let end_angle = ((-y - center_y) / radius_y).atan2((-x - center_x) / radius_x);
+        let mut sweep_angle = (end_angle - start_angle).rem_euclid(TAU);
+        if !sweep {
+            sweep_angle -= TAU;
+        }
+        self.path.add_arc(center, (radius_x, radius_y), rotation, start_angle, sweep_angle);
+    }
+}
+
+/// Reads numbers, flags and command letters one at a time from an attribute,
+/// skipping the whitespace and commas between them.
+struct Scanner<'text> {
+    /// The text not read yet.
+    rest: &'text str,
+}
+
+impl Scanner<'_> {
+    /// Skips any whitespace and commas.
+    fn skip_separators(&mut self) {
+        self.rest = self.rest.trim_start_matches(|character: char| character.is_ascii_whitespace() || character == ',');
+    }
+
+    /// Checks whether there is nothing left to read.
+    fn is_empty(&mut self) -> bool {
+        self.skip_separators();
+        self.rest.is_empty()
+    }
+
+    /// Reads a letter, such as a path command.
+    fn letter(&mut self) -> Option<char> {
+        self.skip_separators();
+        let letter = self.rest.chars().next().filter(char::is_ascii_alphabetic)?;
+        self.rest = self.rest.get(1..).unwrap_or_default();
+        Some(letter)
+    }
+
+    /// Reads a flag, which is a single `0` or `1` that may be written without
+    /// anything separating it from the next number.
+    fn flag(&mut self) -> Option<bool> {
+        self.skip_separators();
+        let flag = match self.rest.chars().next()? {
+            '0' => false,
+            '1' => true,
+            _ => return None,
+        };
+        self.rest = self.rest.get(1..).unwrap_or_default();
+        Some(flag)
+    }
+
+    /// Reads a number. Numbers can be written without anything separating
+    /// them where it is unambiguous, as in `1-2.5.5`, which is 1, -2.5 and
+    /// 0.5.
+    fn number(&mut self) -> Option<f64> {
+        self.skip_separators();
+        let bytes = self.rest.as_bytes();
+        let byte = |index: usize| bytes.get(index).copied().unwrap_or_default();
+        let digits_end = |start: usize| (start..bytes.len()).find(|&index| !byte(index).is_ascii_digit()).unwrap_or_else(|| bytes.len().max(start));
+        let sign = usize::from(matches!(byte(0), b'+' | b'-'));
+        let mut length = digits_end(sign);
+        let mut has_digits = length > sign;
+        if byte(length) == b'.' {
+            let fraction = length.saturating_add(1);
+            length = digits_end(fraction);
+            has_digits |= length > fraction;
+        }
+        if !has_digits {
+            return None;
+        }
+        if matches!(byte(length), b'e' | b'E') {
+            let exponent = length.saturating_add(1).saturating_add(usize::from(matches!(byte(length.saturating_add(1)), b'+' | b'-')));
+            let exponent_end = digits_end(exponent);
+            if exponent_end > exponent {
+                length = exponent_end;
+            }
+        }
+        let (number, rest) = self.rest.split_at_checked(length)?;
+        self.rest = rest;
+        number.parse().ok()
+    }
+
+    /// Reads a pair of numbers as a point, relative to `origin`.
+    fn point(&mut self, origin: Point) -> Option<Point> {
+        let x = self.number()?;
+        let y = self.number()?;
+        Some((x + origin.0, y + origin.1))
+    }
+}
+
+/// Reads every number in a list, such as a `viewBox` or the `points` of a
+/// `polygon`, stopping at anything which is not a number.
+fn numbers(list: &str) -> Vec<f64> {
+    let mut scanner = Scanner { rest: list };
+    iter::from_fn(|| scanner.number()).collect()
+}
+
+/// Reads a length, converting it to pixels. Percentages and font-relative
+/// units are not supported.
+fn length(value: &str) -> Option<f64> {
+    let mut scanner = Scanner { rest: value };
+    let number = scanner.number()?;
+    let pixels_per_unit = match scanner.rest.trim() {
+        "" | "px" => 1.0f64,
+        "pt" => 4.0f64 / 3.0f64,
+        "pc" => 16.0f64,
+        "mm" => 96.0f64 / 25.4f64,
+        "cm" => 96.0f64 / 2.54f64,
+        "in" => 96.0f64,
+        _ => return None,
+    };
+    Some(number * pixels_per_unit)
+}
+
+/// Reads an opacity, as a number or a percentage, clamped to between 0.0 and
+/// 1.0.
+fn opacity(value: &str) -> Option<f64> {
+    let opacity = match value.strip_suffix('%') {
+        Some(percentage) => percentage.trim().parse::<f64>().ok()? / 100.0f64,
+        None => value.parse::<f64>().ok()?,
+    };
+    (!opacity.is_nan()).then(|| opacity.clamp(0.0, 1.0))
+}
+
+/// Reads the paint of a fill or stroke.
+fn paint(value: &str) -> Option<Paint> {
+    if let Some(reference) = value.strip_prefix("url(") {
+        // gradients and patterns are not supported, so the fallback color
+        // after them is used instead, if there is one
+        let fallback = reference.split_once(')').map_or("", |(_, fallback)| fallback.trim());
+        return if fallback.is_empty() { Some(Paint::None) } else { paint(fallback) };
+    }
+    match value {
+        "none" => Some(Paint::None),
+        "currentColor" => Some(Paint::CurrentColor),
+        _ => color(value).map(Paint::Color),
+    }
+}
+
+/// Reads a color given as hex, `rgb()`, `rgba()` or a basic color name.
+fn color(value: &str) -> Option<Color> {
+    if let Some(hex) = value.strip_prefix('#') {
+        let digits: Vec<u8> = hex.chars().map(|digit| cast::u8(digit.to_digit(16)?).ok()).collect::<Option<_>>()?;
+        let channels: Vec<u8> = match digits.len() {
+            3 | 4 => digits.iter().map(|&digit| digit.saturating_mul(17)).collect(),
+            6 | 8 => digits.chunks_exact(2).map(|pair| pair.iter().fold(0, |channel: u8, &digit| channel.saturating_mul(16).saturating_add(digit))).collect(),
+            _ => return None,
+        };
+        return match *channels.as_slice() {
+            [red, green, blue] => Some(Color::rgb(red, green, blue)),
+            [red, green, blue, alpha] => Some(Color::rgba(red, green, blue, alpha)),
+            _ => None,
+        };
+    }
+    if let Some(arguments) = value.strip_prefix("rgba(").or_else(|| value.strip_prefix("rgb(")) {
+        let values: Vec<&str> = arguments.strip_suffix(')')?.split([',', '/', ' ']).filter(|argument| !argument.is_empty()).collect();
+        let channel = |argument: &str| Some(channel_from_f64(match argument.strip_suffix('%') {
+            Some(percentage) => percentage.parse::<f64>().ok()? * 2.55,
+            None => argument.parse::<f64>().ok()?,
+        }));
+        return match *values.as_slice() {
+            [red, green, blue] => Some(Color::rgb(channel(red)?, channel(green)?, channel(blue)?)),
+            [red, green, blue, alpha] => Some(Color::rgb(channel(red)?, channel(green)?, channel(blue)?).multiply_alpha(opacity(alpha)?)),
+            _ => None,
+        };
+    }
+    NAMED_COLORS.iter().find(|&&(name, _)| name.eq_ignore_ascii_case(value)).map(|&(_, color)| color)
+}
+
+/// Reads a list of transformations, such as `translate(10, 5) rotate(45)`,
+/// as a single transformation which applies the last one first.
+/// # Returns
+/// The transformation, or `None` if the list is invalid.
+#[expect(clippy::single_call_fn, reason = "kept separate from Style::child for readability")]
+fn transform_list(list: &str) -> Option<Transform> {
+    let mut transform = Transform::IDENTITY;
+    let mut rest = list;
+    loop {
+        rest = rest.trim_start_matches(|character: char| character.is_ascii_whitespace() || character == ',');
+        if rest.is_empty() {
+            return Some(transform);
+        }
+        let (name, arguments_start) = rest.split_once('(')?;
+        let (arguments, remaining) = arguments_start.split_once(')')?;
+        let values = numbers(arguments);
+        let item = match (name.trim(), values.as_slice()) {
+            ("matrix", &[scale_x, skew_y, skew_x, scale_y, x, y]) => Transform::new(scale_x, skew_y, skew_x, scale_y, x, y),
+            ("translate", &[x]) => Transform::translation(x, 0.0),
+            ("translate", &[x, y]) => Transform::translation(x, y),
+            ("scale", &[scale]) => Transform::scaling(scale, scale),
+            ("scale", &[x, y]) => Transform::scaling(x, y),
+            ("rotate", &[angle]) => Transform::rotation(angle.to_radians()),
+            ("rotate", &[angle, x, y]) => Transform::translation(-x, -y).then(Transform::rotation(angle.to_radians())).then(Transform::translation(x, y)),
+            ("skewX", &[angle]) => Transform::new(1.0, 0.0, angle.to_radians().tan(), 1.0, 0.0, 0.0),
+            ("skewY", &[angle]) => Transform::new(1.0, angle.to_radians().tan(), 0.0, 1.0, 0.0, 0.0),
+            _ => return None,
+        };
+        transform = item.then(transform);
+        rest = remaining;
+    }
+}
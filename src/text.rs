@@ -0,0 +1,180 @@
+//! `TextNode` struct - represents a node which draws a string of text using a
+//! `Font`.
+
+use crate::{BlendMode, Color, Texture, component::{BlendModeComponent, DrawComponent, FillColorComponent, PositionComponent, SizeComponent}, font::{BuiltinFont, Font}, layout::{TextAlign, TextLayout}};
+
+/// A node which draws a string of text onto a texture.
+/// By default the text is drawn with `BuiltinFont::DEFAULT`, so it works
+/// without loading any assets, but any `Font` can be used instead.
+/// A `'\n'` in the text starts a new line, and setting `max_width` wraps
+/// long lines between words.
+/// ## Implemented components:
+/// - `DrawComponent`
+/// - `PositionComponent`
+/// - `SizeComponent`
+/// - `FillColorComponent`
+/// - `BlendModeComponent`
+/// # Example
+/// ```rust
+/// let mut score = TextNode::new("Score: 0", 2.0, 2.0, Color::WHITE);
+/// score.text = format!("Score: {points}");
+/// texture.add(&score);
+/// ```
+#[non_exhaustive]
+#[expect(clippy::module_name_repetitions, reason = "struct should be called 'TextNode' as it is standard.")]
+pub struct TextNode<'font> {
+
+    /// The text to draw.
+    pub text: String,
+
+    /// The x-coordinate of the top-left corner of the text.
+    pub x: f64,
+
+    /// The y-coordinate of the top-left corner of the text.
+    pub y: f64,
+
+    /// The font the text is drawn with.
+    pub font: &'font dyn Font,
+
+    /// The color of the text.
+    pub fill_color: Color,
+
+    /// How the text is combined with the existing pixels when drawn.
+    /// Defaults to `BlendMode::SourceOver`.
+    pub blend_mode: BlendMode,
+
+    /// The width to wrap lines at, or `None` to only break lines at `'\n'`.
+    /// Defaults to `None`.
+    pub max_width: Option<f64>,
+
+    /// How each line is positioned horizontally. Lines are aligned within
+    /// `max_width` if it is set, or within the widest line otherwise.
+    /// Defaults to `TextAlign::Left`.
+    pub align: TextAlign,
+
+    /// The distance between the tops of lines, as a multiple of the font's
+    /// line height. Defaults to 1.0.
+    pub line_spacing: f64,
+}
+
+impl<'font> TextNode<'font> {
+    /// Create a new `TextNode` drawn with the built-in 8x8 font.
+    /// # Parameters
+    /// - `text`: The text to draw.
+    /// - `x`: The x-coordinate of the top-left corner of the text.
+    /// - `y`: The y-coordinate of the top-left corner of the text.
+    /// - `fill_color`: The color of the text.
+    /// # Returns
+    /// A new `TextNode` using `BuiltinFont::DEFAULT`.
+    #[must_use]
+    pub fn new(text: &str, x: f64, y: f64, fill_color: Color) -> TextNode<'font> {
+        TextNode::with_font(&BuiltinFont::DEFAULT, text, x, y, fill_color)
+    }
+
+    /// Create a new `TextNode` drawn with a specific font.
+    /// # Parameters
+    /// - `font`: The font to draw the text with.
+    /// - `text`: The text to draw.
+    /// - `x`: The x-coordinate of the top-left corner of the text.
+    /// - `y`: The y-coordinate of the top-left corner of the text.
+    /// - `fill_color`: The color of the text.
+    /// # Returns
+    /// A new `TextNode` using `font`.
+    #[must_use]
+    pub fn with_font(font: &'font dyn Font, text: &str, x: f64, y: f64, fill_color: Color) -> TextNode<'font> {
+        TextNode {
+            text: text.to_owned(),
+            x, y, font, fill_color,
+            blend_mode: BlendMode::SourceOver,
+            max_width: None,
+            align: TextAlign::Left,
+            line_spacing: 1.0,
+        }
+    }
+
+    /// Lays out the text with the node's font, wrapping and alignment.
+    /// # Returns
+    /// The laid out text, relative to the node's position.
+    /// # Example
+    /// ```rust
+    /// let (width, height) = dialogue.get_layout().get_size();
+    /// ```
+    #[must_use]
+    pub fn get_layout(&self) -> TextLayout {
+        TextLayout::new(self.font, &self.text, self.max_width, self.align, self.line_spacing)
+    }
+}
+
+impl DrawComponent for TextNode<'_> {
+    fn draw(&self, texture: &mut Texture) {
+        for line in self.get_layout().get_lines() {
+            let mut pen_x = self.x + line.x;
+            let mut previous = None;
+            for character in line.text.chars() {
+                if let Some(left) = previous {
+                    pen_x += self.font.get_kerning(left, character);
+                }
+                self.font.draw_char(texture, character, pen_x, self.y + line.y, self.fill_color, self.blend_mode);
+                pen_x += self.font.get_advance(character);
+                previous = Some(character);
+            }
+        }
+    }
+}
+
+impl PositionComponent for TextNode<'_> {
+    fn get_x(&self) -> f64 {
+        self.x
+    }
+
+    fn get_y(&self) -> f64 {
+        self.y
+    }
+
+    fn set_x(&mut self, x: f64) {
+        self.x = x;
+    }
+
+    fn set_y(&mut self, y: f64) {
+        self.y = y;
+    }
+}
+
+/// The size of a `TextNode` is the size of its laid out text. Setting the
+/// width wraps the text to it, but setting the height does nothing, as the
+/// height always follows from the text.
+impl SizeComponent for TextNode<'_> {
+    fn get_width(&self) -> f64 {
+        self.get_layout().get_size().0
+    }
+
+    fn get_height(&self) -> f64 {
+        self.get_layout().get_size().1
+    }
+
+    fn set_width(&mut self, width: f64) {
+        self.max_width = Some(width);
+    }
+
+    fn set_height(&mut self, _height: f64) {}
+}
+
+impl FillColorComponent for TextNode<'_> {
+    fn get_fill_color(&self) -> &Color {
+        &self.fill_color
+    }
+
+    fn set_fill_color(&mut self, color: Color) {
+        self.fill_color = color;
+    }
+}
+
+impl BlendModeComponent for TextNode<'_> {
+    fn get_blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+}
@@ -6,7 +6,20 @@
 
 use anyhow::Error;
 
-use crate::{color::Color, component::DrawComponent};
+use crate::{blend::BlendMode, color::{Color, channel_from_f64}, component::DrawComponent, rect::Rect, transform::Transform};
+
+/// How a texture is sampled when it is drawn scaled, rotated or at a
+/// fractional position.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Filter {
+    /// Use the single closest pixel. This keeps pixel art crisp and blocky.
+    #[default]
+    Nearest,
+    /// Blend the four closest pixels by their distance. This gives smooth
+    /// results for photos and detailed art, but blurs pixel art.
+    Bilinear,
+}
 
 /// A 2D texture represented as a grid of pixels, where each pixel is defined by
 /// a `Color`.
@@ -48,6 +61,42 @@ impl Texture {
         }
     }
 
+    /// Creates a new `Texture` instance from an existing flat list of pixels.
+    /// The pixels are stored row by row, starting from the top-left corner.
+    /// # Arguments
+    /// * `width` - The width of the texture in pixels.
+    /// * `height` - The height of the texture in pixels.
+    /// * `pixels` - The colors of every pixel, row by row. Must contain
+    ///   exactly `width * height` colors.
+    /// # Returns
+    /// A `Texture` instance containing the passed pixels.
+    /// # Errors
+    /// Errors if the number of pixels does not match `width * height`.
+    /// # Example
+    /// ```rust
+    /// let texture = Texture::from_pixels(2, 1, vec![Color::RED, Color::BLUE])
+    ///     .expect("wrong number of pixels");
+    /// ```
+    pub fn from_pixels(width: usize, height: usize, pixels: Vec<Color>) -> Result<Texture, Error> {
+        if width.checked_mul(height) != Some(pixels.len()) {
+            return Err(Error::msg("Pixl: from_pixels: pixel count did not match width * height"));
+        }
+        Ok(Texture { pixels, width, height })
+    }
+
+    /// Gets every pixel in the texture as a flat slice of colors, row by row,
+    /// starting from the top-left corner.
+    /// # Returns
+    /// A slice of `width * height` colors.
+    /// # Example
+    /// ```rust
+    /// let opaque = texture.get_pixels().iter().all(|pixel| pixel.a == 255);
+    /// ```
+    #[must_use]
+    pub fn get_pixels(&self) -> &[Color] {
+        &self.pixels
+    }
+
     /// Gets the color of the pixel at the specified (x, y) coordinates.
     /// # Arguments
     /// * `x` - The x-coordinate of the pixel.
@@ -62,6 +111,9 @@ impl Texture {
     /// ```
     #[must_use]
     pub fn get_pixel(&self, x: usize, y: usize) -> Option<Color> {
+        if x >= self.width {
+            return None;
+        }
         Some(*self.pixels.get(y.checked_mul(self.width)?.checked_add(x)?)?)
     }
 
@@ -93,6 +145,100 @@ impl Texture {
         Ok(())
     }
 
+    /// Blends a color *over* the pixel at the specified (x, y) coordinates,
+    /// using its alpha channel ("source-over" blending).
+    /// Unlike `set_pixel`, which replaces the pixel outright, a translucent
+    /// color will let the existing pixel show through.
+    /// # Arguments
+    /// * `x` - The x-coordinate of the pixel.
+    /// * `y` - The y-coordinate of the pixel.
+    /// * `color` - The color to blend onto the pixel.
+    /// # Errors
+    /// Errors if the coordinates are out of bounds. No return value on success.
+    /// # Example
+    /// ```rust
+    /// texture.blend_pixel(10, 10, Color::rgba(255, 0, 0, 128))
+    ///     .expect("coordinates were out of bounds");
+    /// ```
+    pub fn blend_pixel(&mut self, x: usize, y: usize, color: Color) -> Result<(), Error> {
+        self.draw_pixel(x, y, color, BlendMode::SourceOver)
+    }
+
+    /// Draws a color onto the pixel at the specified (x, y) coordinates,
+    /// combining it with the existing pixel using the given `BlendMode`.
+    /// # Arguments
+    /// * `x` - The x-coordinate of the pixel.
+    /// * `y` - The y-coordinate of the pixel.
+    /// * `color` - The color to draw onto the pixel.
+    /// * `blend_mode` - How `color` is combined with the existing pixel.
+    /// # Errors
+    /// Errors if the coordinates are out of bounds. No return value on success.
+    /// # Example
+    /// ```rust
+    /// texture.draw_pixel(10, 10, Color::rgb(64, 32, 0), BlendMode::Additive)
+    ///     .expect("coordinates were out of bounds");
+    /// ```
+    pub fn draw_pixel(&mut self, x: usize, y: usize, color: Color, blend_mode: BlendMode) -> Result<(), Error> {
+        let background = self.get_pixel(x, y)
+            .ok_or_else(|| Error::msg("Pixl: draw_pixel: coordinates were out of bounds for texture"))?;
+        self.set_pixel(x, y, blend_mode.blend(color, background))
+    }
+
+    /// Draws a color onto a horizontal run of pixels, starting at the
+    /// specified (x, y) coordinates and going right, combining it with the
+    /// existing pixels using the given `BlendMode`.
+    /// This is much faster than drawing each pixel separately, especially for
+    /// opaque colors, and is what the shape nodes use to fill their insides.
+    /// Only the part of the run on the texture is drawn.
+    /// # Arguments
+    /// * `x` - The x-coordinate of the leftmost pixel.
+    /// * `y` - The y-coordinate of the row.
+    /// * `length` - How many pixels to draw.
+    /// * `color` - The color to draw onto the pixels.
+    /// * `blend_mode` - How `color` is combined with the existing pixels.
+    /// # Example
+    /// ```rust
+    /// // a horizon line across the whole texture
+    /// texture.fill_span(0, 40, texture.get_width(), Color::WHITE, BlendMode::SourceOver);
+    /// ```
+    pub fn fill_span(&mut self, x: usize, y: usize, length: usize, color: Color, blend_mode: BlendMode) {
+        if y >= self.height {
+            return;
+        }
+        let row = y.saturating_mul(self.width);
+        let end = x.saturating_add(length).min(self.width);
+        let Some(pixels) = self.pixels.get_mut(row.saturating_add(x)..row.saturating_add(end)) else {
+            return;
+        };
+        // the result does not depend on the existing pixels, so they can be
+        // overwritten without being read
+        if blend_mode == BlendMode::Source || (blend_mode == BlendMode::SourceOver && color.a == u8::MAX) {
+            pixels.fill(color);
+            return;
+        }
+        for pixel in pixels {
+            *pixel = blend_mode.blend(color, *pixel);
+        }
+    }
+
+    /// Draws a color onto every pixel of a rectangular region, combining it
+    /// with the existing pixels using the given `BlendMode`.
+    /// Only the part of the region on the texture is drawn.
+    /// # Arguments
+    /// * `region` - The pixels to draw onto.
+    /// * `color` - The color to draw onto the pixels.
+    /// * `blend_mode` - How `color` is combined with the existing pixels.
+    /// # Example
+    /// ```rust
+    /// // darken the bottom of the screen behind a dialogue box
+    /// texture.fill_rect(Rect::new(0, 48, 80, 12), Color::rgba(0, 0, 0, 160), BlendMode::SourceOver);
+    /// ```
+    pub fn fill_rect(&mut self, region: Rect, color: Color, blend_mode: BlendMode) {
+        for y in region.y..region.y.saturating_add(region.height).min(self.height) {
+            self.fill_span(region.x, y, region.width, color, blend_mode);
+        }
+    }
+
     /// Gets the hexadecimal representation of the color of the pixel at the
     /// specified (x, y) coordinates.
     /// # Arguments
@@ -183,6 +329,126 @@ impl Texture {
         self.height
     }
 
+    /// Samples the color of the texture at a point which may lie between
+    /// pixels. Pixel (x, y) covers the area from (x, y) to (x + 1, y + 1), so
+    /// its center is at (x + 0.5, y + 0.5).
+    /// # Arguments
+    /// * `x` - The x-coordinate to sample at.
+    /// * `y` - The y-coordinate to sample at.
+    /// * `filter` - How to combine the pixels near the point.
+    /// # Returns
+    /// The sampled `Color`, or `None` if the point is outside of the texture.
+    /// # Example
+    /// ```rust
+    /// let color = texture.sample(10.25, 3.5, Filter::Bilinear)
+    ///     .expect("point was outside of the texture");
+    /// ```
+    #[must_use]
+    pub fn sample(&self, x: f64, y: f64, filter: Filter) -> Option<Color> {
+        self.sample_region(Rect::new(0, 0, self.width, self.height), x, y, filter)
+    }
+
+    /// Draws the `region` of a `source` texture onto this texture, moved,
+    /// scaled, rotated or skewed by `transform`.
+    /// The transform maps coordinates inside the region (from (0, 0) to
+    /// (`region.width`, `region.height`)) to coordinates on this texture, and
+    /// may place the region at fractional positions for smooth motion.
+    /// Every covered pixel of this texture samples the source at its center.
+    /// Anything outside of this texture is clipped.
+    /// # Arguments
+    /// * `source` - The texture to take pixels from.
+    /// * `region` - The region of `source` to draw. Bilinear filtering never
+    ///   blends in pixels from outside of this region.
+    /// * `transform` - Where and how the region is drawn.
+    /// * `filter` - How the source is sampled.
+    /// * `tint` - A color multiplied with every sampled pixel. Use
+    ///   `Color::WHITE` to leave the pixels unchanged.
+    /// * `blend_mode` - How the sampled pixels are combined with this texture.
+    /// # Example
+    /// ```rust
+    /// // draw the whole sprite at twice its size, turned 45 degrees
+    /// let transform = Transform::scaling(2.0, 2.0)
+    ///     .then(Transform::rotation(core::f64::consts::FRAC_PI_4))
+    ///     .then(Transform::translation(40.0, 30.0));
+    /// let region = Rect::new(0, 0, sprite.get_width(), sprite.get_height());
+    /// texture.draw_texture(&sprite, region, transform, Filter::Bilinear, Color::WHITE, BlendMode::SourceOver);
+    /// ```
+    pub fn draw_texture(&mut self, source: &Texture, region: Rect, transform: Transform, filter: Filter, tint: Color, blend_mode: BlendMode) {
+        // never read outside of the source texture
+        let clipped = Rect::new(
+            region.x, region.y,
+            region.width.min(source.width.saturating_sub(region.x)),
+            region.height.min(source.height.saturating_sub(region.y)),
+        );
+        if clipped.width == 0 || clipped.height == 0 {
+            return;
+        }
+        let Some(inverse) = transform.inverse() else {
+            return;
+        };
+
+        // only visit the destination pixels the transformed region can cover
+        let region_width = cast::f64(clipped.width);
+        let region_height = cast::f64(clipped.height);
+        let corners = [
+            transform.apply(0.0, 0.0),
+            transform.apply(region_width, 0.0),
+            transform.apply(0.0, region_height),
+            transform.apply(region_width, region_height),
+        ];
+        let min_x = corners.iter().fold(f64::INFINITY, |min, corner| min.min(corner.0));
+        let max_x = corners.iter().fold(f64::NEG_INFINITY, |max, corner| max.max(corner.0));
+        let min_y = corners.iter().fold(f64::INFINITY, |min, corner| min.min(corner.1));
+        let max_y = corners.iter().fold(f64::NEG_INFINITY, |max, corner| max.max(corner.1));
+        let left = pixel_index(min_x.floor(), self.width);
+        let right = pixel_index(max_x.ceil(), self.width);
+        let top = pixel_index(min_y.floor(), self.height);
+        let bottom = pixel_index(max_y.ceil(), self.height);
+
+        for dest_y in top..bottom {
+            for dest_x in left..right {
+                let (sample_x, sample_y) = inverse.apply(cast::f64(dest_x) + 0.5f64, cast::f64(dest_y) + 0.5f64);
+                let Some(color) = source.sample_region(clipped, sample_x, sample_y, filter) else {
+                    continue;
+                };
+                self.draw_pixel(dest_x, dest_y, color.tinted(tint), blend_mode).unwrap_or(());
+            }
+        }
+    }
+
+    /// Samples the color of the texture at (`x`, `y`) relative to the top-left
+    /// corner of `region`, never reading pixels outside of `region`.
+    /// Returns `None` if the point is outside of the region.
+    fn sample_region(&self, region: Rect, x: f64, y: f64, filter: Filter) -> Option<Color> {
+        let width = cast::f64(region.width);
+        let height = cast::f64(region.height);
+        if !(0.0f64..width).contains(&x) || !(0.0f64..height).contains(&y) {
+            return None;
+        }
+        let pixel = |column: f64, row: f64| self.get_pixel(
+            region.x.saturating_add(region_index(column, region.width)),
+            region.y.saturating_add(region_index(row, region.height)),
+        );
+        match filter {
+            Filter::Nearest => pixel(x.floor(), y.floor()),
+            Filter::Bilinear => {
+                // pixel centers are at +0.5, so find the four centers around
+                // the point and how far it is between them
+                let left = (x - 0.5f64).floor();
+                let top = (y - 0.5f64).floor();
+                let weight_x = x - 0.5f64 - left;
+                let weight_y = y - 0.5f64 - top;
+                let samples = [
+                    (pixel(left, top)?, (1.0 - weight_x) * (1.0 - weight_y)),
+                    (pixel(left + 1.0, top)?, weight_x * (1.0 - weight_y)),
+                    (pixel(left, top + 1.0)?, (1.0 - weight_x) * weight_y),
+                    (pixel(left + 1.0, top + 1.0)?, weight_x * weight_y),
+                ];
+                Some(interpolate(&samples))
+            },
+        }
+    }
+
     /// Blits (draws) a node with a `DrawComponent` *on top of* this texture.
     /// Note that these two lines are identical in effect:
     /// ```rust
@@ -204,3 +470,38 @@ impl Texture {
     }
 }
 
+/// Converts a destination coordinate into a pixel index, clamped to the
+/// `0..=limit` range of the destination texture.
+fn pixel_index(coordinate: f64, limit: usize) -> usize {
+    cast::usize(coordinate.max(0.0f64)).unwrap_or(limit).min(limit)
+}
+
+/// Converts a pixel coordinate inside a region into an index, clamped to the
+/// edge of the region (`0..size`) so filtering never reads outside of it.
+fn region_index(coordinate: f64, size: usize) -> usize {
+    cast::usize(coordinate.max(0.0f64)).unwrap_or(0).min(size.saturating_sub(1))
+}
+
+/// Combines colors by the weights paired with them, which should add up to 1.
+/// The colors are mixed with their alpha premultiplied so that transparent
+/// pixels do not darken the edges of a sprite.
+#[expect(clippy::pub_with_shorthand, reason = "shared with the triangle module, but not part of the public API")]
+pub(crate) fn interpolate(samples: &[(Color, f64)]) -> Color {
+    let (mut red, mut green, mut blue, mut alpha) = (0.0f64, 0.0f64, 0.0f64, 0.0f64);
+    for &(color, weight) in samples {
+        let coverage = f64::from(color.a) * weight;
+        red = f64::from(color.r).mul_add(coverage, red);
+        green = f64::from(color.g).mul_add(coverage, green);
+        blue = f64::from(color.b).mul_add(coverage, blue);
+        alpha += coverage;
+    }
+    if alpha <= 0.0f64 {
+        return Color::rgba(0, 0, 0, 0);
+    }
+    Color::rgba(
+        channel_from_f64(red / alpha),
+        channel_from_f64(green / alpha),
+        channel_from_f64(blue / alpha),
+        channel_from_f64(alpha),
+    )
+}
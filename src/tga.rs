@@ -0,0 +1,229 @@
+//! TGA (Truevision TARGA) loading and saving for `Texture`, with no extra
+//! dependencies.
+//!
+//! Color-mapped, true-color and grayscale TGA images can be loaded, both
+//! uncompressed and run-length encoded, at 8, 15, 16, 24 or 32 bits per
+//! pixel and with any origin corner.
+//! Textures are always saved as uncompressed 32-bit images with alpha.
+
+use core::iter;
+use std::{fs, path::Path};
+
+use anyhow::{Context as _, Error};
+
+use crate::{Color, Texture, reader::ByteReader};
+
+/// The bit of the image descriptor which is set when rows are stored
+/// right-to-left.
+const RIGHT_TO_LEFT: u8 = 0b0001_0000;
+/// The bit of the image descriptor which is set when rows are stored
+/// top-to-bottom.
+const TOP_TO_BOTTOM: u8 = 0b0010_0000;
+
+#[expect(clippy::multiple_inherent_impl, reason = "each image format is kept in its own module")]
+impl Texture {
+    /// Loads a TGA image file into a new `Texture`.
+    /// # Arguments
+    /// * `path` - The path of the TGA file to load.
+    /// # Returns
+    /// A `Texture` with the same size and pixels as the image.
+    /// # Errors
+    /// Errors if the file cannot be read or is not a supported TGA image.
+    /// # Example
+    /// ```rust
+    /// let enemy = Texture::load_tga("assets/enemy.tga")
+    ///     .expect("failed to load enemy sprite");
+    /// ```
+    pub fn load_tga<P: AsRef<Path>>(path: P) -> Result<Texture, Error> {
+        let bytes = fs::read(path).context("Pixl: load_tga: failed to read file")?;
+        Texture::decode_tga(&bytes)
+    }
+
+    /// Saves the texture to an uncompressed 32-bit TGA image file, including
+    /// its alpha channel.
+    /// # Arguments
+    /// * `path` - The path of the TGA file to create or overwrite.
+    /// # Errors
+    /// Errors if the texture is too large for a TGA image, or the file cannot
+    /// be written.
+    /// # Example
+    /// ```rust
+    /// texture.save_tga("screenshot.tga")
+    ///     .expect("failed to save screenshot");
+    /// ```
+    pub fn save_tga<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        fs::write(path, self.encode_tga()?).context("Pixl: save_tga: failed to write file")
+    }
+
+    /// Decodes an in-memory TGA image into a new `Texture`.
+    /// # Arguments
+    /// * `bytes` - The contents of a TGA file.
+    /// # Returns
+    /// A `Texture` with the same size and pixels as the image.
+    /// # Errors
+    /// Errors if `bytes` is not a supported TGA image.
+    /// # Example
+    /// ```rust
+    /// let font = Texture::decode_tga(include_bytes!("font.tga"))
+    ///     .expect("invalid font image");
+    /// ```
+    pub fn decode_tga(bytes: &[u8]) -> Result<Texture, Error> {
+        let mut reader = ByteReader::new(bytes, "decode_tga");
+        let id_length = reader.u8()?;
+        let color_map_type = reader.u8()?;
+        let image_type = reader.u8()?;
+        let first_entry = usize::from(reader.u16_le()?);
+        let entry_count = usize::from(reader.u16_le()?);
+        let entry_bits = reader.u8()?;
+        // the origin is only meaningful to the program which wrote the image
+        reader.skip(4)?;
+        let width = usize::from(reader.u16_le()?);
+        let height = usize::from(reader.u16_le()?);
+        let pixel_bits = reader.u8()?;
+        let descriptor = reader.u8()?;
+        reader.skip(usize::from(id_length))?;
+
+        let color_map = if color_map_type == 1 {
+            let entry_bytes = usize::from(entry_bits.div_ceil(8));
+            let mut color_map = vec![Color::rgba(0, 0, 0, 0); first_entry];
+            for _ in 0..entry_count {
+                color_map.push(true_color(reader.bytes(entry_bytes)?));
+            }
+            color_map
+        }
+        else {
+            Vec::new()
+        };
+
+        let kind = match image_type & !0b1000 {
+            1 => Kind::ColorMapped,
+            2 => Kind::TrueColor,
+            3 => Kind::Grayscale,
+            _ => return Err(Error::msg("Pixl: decode_tga: unsupported TGA image type")),
+        };
+        let run_length_encoded = image_type & 0b1000 != 0;
+        let pixel_bytes = usize::from(pixel_bits.div_ceil(8));
+        if pixel_bytes == 0 || pixel_bytes > 4 {
+            return Err(Error::msg("Pixl: decode_tga: unsupported TGA pixel depth"));
+        }
+        let pixel_count = width.saturating_mul(height);
+
+        let mut pixels = Vec::with_capacity(pixel_count.min(bytes.len().saturating_mul(128)));
+        while pixels.len() < pixel_count {
+            let (repeat, literal_count) = if run_length_encoded {
+                let packet = reader.u8()?;
+                let count = usize::from(packet & 0b0111_1111).saturating_add(1);
+                if packet & 0b1000_0000 == 0 { (1, count) } else { (count, 1) }
+            }
+            else {
+                (1, 1)
+            };
+            for _ in 0..literal_count {
+                let color = kind.decode(reader.bytes(pixel_bytes)?, &color_map)?;
+                pixels.extend(iter::repeat_n(color, repeat));
+            }
+        }
+        pixels.truncate(pixel_count);
+
+        // pixels are stored bottom-to-top and left-to-right by default
+        if descriptor & TOP_TO_BOTTOM == 0 {
+            pixels = pixels.chunks(width.max(1)).rev().flatten().copied().collect();
+        }
+        if descriptor & RIGHT_TO_LEFT != 0 {
+            for row in pixels.chunks_mut(width.max(1)) {
+                row.reverse();
+            }
+        }
+        // images without any alpha bits in the descriptor have no alpha
+        // channel, even if the pixels are 16-bit or 32-bit
+        if descriptor.trailing_zeros() >= 4 {
+            for pixel in &mut pixels {
+                pixel.a = u8::MAX;
+            }
+        }
+        Texture::from_pixels(width, height, pixels)
+    }
+
+    /// Encodes the texture as an in-memory uncompressed 32-bit TGA image,
+    /// including its alpha channel.
+    /// # Returns
+    /// The contents of the TGA file.
+    /// # Errors
+    /// Errors if the texture is wider or taller than 65535 pixels, which is
+    /// the largest size a TGA image can be.
+    /// # Example
+    /// ```rust
+    /// let bytes = texture.encode_tga().expect("failed to encode texture");
+    /// ```
+    #[expect(clippy::little_endian_bytes, reason = "TGA files are little-endian")]
+    pub fn encode_tga(&self) -> Result<Vec<u8>, Error> {
+        let width = u16::try_from(self.get_width()).context("Pixl: encode_tga: texture is too wide")?;
+        let height = u16::try_from(self.get_height()).context("Pixl: encode_tga: texture is too tall")?;
+
+        let mut bytes = Vec::with_capacity(self.get_pixels().len().saturating_mul(4).saturating_add(18));
+        // no ID or color map, uncompressed true-color, origin at (0, 0)
+        bytes.extend_from_slice(&[0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        bytes.extend_from_slice(&width.to_le_bytes());
+        bytes.extend_from_slice(&height.to_le_bytes());
+        // 32 bits per pixel, 8 of which are alpha, stored top-to-bottom
+        bytes.extend_from_slice(&[32, 8 | TOP_TO_BOTTOM]);
+        for pixel in self.get_pixels() {
+            bytes.extend_from_slice(&[pixel.b, pixel.g, pixel.r, pixel.a]);
+        }
+        Ok(bytes)
+    }
+}
+
+/// The kind of data stored in each pixel of a TGA image.
+#[derive(Copy, Clone)]
+enum Kind {
+    /// Each pixel is an index into the color map.
+    ColorMapped,
+    /// Each pixel is a BGR(A) color.
+    TrueColor,
+    /// Each pixel is a gray level, optionally followed by alpha.
+    Grayscale,
+}
+
+impl Kind {
+    /// Decodes the bytes of a single pixel.
+    #[expect(clippy::little_endian_bytes, reason = "TGA files are little-endian")]
+    fn decode(self, pixel: &[u8], color_map: &[Color]) -> Result<Color, Error> {
+        match self {
+            Kind::ColorMapped => {
+                let index = match *pixel {
+                    [low] => usize::from(low),
+                    [low, high, ..] => usize::from(u16::from_le_bytes([low, high])),
+                    _ => 0,
+                };
+                color_map.get(index).copied()
+                    .ok_or_else(|| Error::msg("Pixl: decode_tga: color map index out of range"))
+            },
+            Kind::TrueColor => Ok(true_color(pixel)),
+            Kind::Grayscale => Ok(match *pixel {
+                [gray, a, ..] => Color::rgba(gray, gray, gray, a),
+                [gray] => Color::rgb(gray, gray, gray),
+                _ => Color::rgba(0, 0, 0, 0),
+            }),
+        }
+    }
+}
+
+/// Decodes a true-color pixel (or color map entry) of 2, 3 or 4 bytes.
+/// 2-byte pixels are 5 bits per channel, with a single bit of alpha.
+#[expect(clippy::little_endian_bytes, reason = "TGA files are little-endian")]
+fn true_color(pixel: &[u8]) -> Color {
+    match *pixel {
+        [b, g, r, a] => Color::rgba(r, g, b, a),
+        [b, g, r] => Color::rgb(r, g, b),
+        [low, high] => {
+            let packed = u16::from_le_bytes([low, high]);
+            let channel = |shift: u16| {
+                let value = cast::u8((packed >> shift) & 0b1_1111).unwrap_or(0);
+                (value << 3u8) | (value >> 2u8)
+            };
+            Color::rgba(channel(10), channel(5), channel(0), if packed & 0x8000 == 0 { 0 } else { u8::MAX })
+        },
+        _ => Color::rgba(0, 0, 0, 0),
+    }
+}
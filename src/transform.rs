@@ -0,0 +1,163 @@
+//! The `Transform` struct, a 2D affine transformation (any combination of
+//! translation, scaling, rotation and skewing) used to position things drawn
+//! onto a `Texture`.
+
+/// A 2D affine transformation matrix.
+///
+/// A point (`x`, `y`) is transformed to:
+/// ```text
+/// x' = a * x + c * y + e
+/// y' = b * x + d * y + f
+/// ```
+/// which is the same layout as the SVG and HTML canvas `matrix(a, b, c, d, e,
+/// f)` transform.
+/// Transformations are combined with `then`, which applies one transformation
+/// after another.
+/// # Example
+/// ```rust
+/// // spin around (16, 16), then move to (100, 50)
+/// let transform = Transform::translation(-16.0, -16.0)
+///     .then(Transform::rotation(angle))
+///     .then(Transform::translation(100.0, 50.0));
+/// let (x, y) = transform.apply(0.0, 0.0);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct Transform {
+    /// How much the input x-coordinate scales the output x-coordinate.
+    pub a: f64,
+    /// How much the input x-coordinate moves the output y-coordinate.
+    pub b: f64,
+    /// How much the input y-coordinate moves the output x-coordinate.
+    pub c: f64,
+    /// How much the input y-coordinate scales the output y-coordinate.
+    pub d: f64,
+    /// The distance the output is moved along the x-axis.
+    pub e: f64,
+    /// The distance the output is moved along the y-axis.
+    pub f: f64,
+}
+
+impl Transform {
+    /// The identity transformation, which leaves every point where it is.
+    pub const IDENTITY: Transform = Transform::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0);
+
+    /// Creates a new `Transform` from the six values of its matrix, in the
+    /// same order as the SVG `matrix(a, b, c, d, e, f)` transform.
+    /// # Returns
+    /// A `Transform` with the specified matrix.
+    /// # Example
+    /// ```rust
+    /// let skew = Transform::new(1.0, 0.0, 0.5, 1.0, 0.0, 0.0);
+    /// ```
+    #[must_use]
+    #[expect(clippy::many_single_char_names, reason = "the arguments are named after the matrix values")]
+    pub const fn new(a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> Transform {
+        Transform { a, b, c, d, e, f }
+    }
+
+    /// Creates a transformation which moves every point by (`x`, `y`).
+    /// # Returns
+    /// A translation `Transform`.
+    /// # Example
+    /// ```rust
+    /// let right = Transform::translation(10.0, 0.0);
+    /// ```
+    #[must_use]
+    pub const fn translation(x: f64, y: f64) -> Transform {
+        Transform::new(1.0, 0.0, 0.0, 1.0, x, y)
+    }
+
+    /// Creates a transformation which scales every point away from the origin
+    /// by `x` horizontally and `y` vertically. Negative values mirror.
+    /// # Returns
+    /// A scaling `Transform`.
+    /// # Example
+    /// ```rust
+    /// let double = Transform::scaling(2.0, 2.0);
+    /// ```
+    #[must_use]
+    pub const fn scaling(x: f64, y: f64) -> Transform {
+        Transform::new(x, 0.0, 0.0, y, 0.0, 0.0)
+    }
+
+    /// Creates a transformation which rotates every point around the origin by
+    /// `angle` radians. As the y-axis points down in Pixl, positive angles
+    /// rotate clockwise on the screen.
+    /// # Returns
+    /// A rotation `Transform`.
+    /// # Example
+    /// ```rust
+    /// let quarter_turn = Transform::rotation(core::f64::consts::FRAC_PI_2);
+    /// ```
+    #[must_use]
+    pub fn rotation(angle: f64) -> Transform {
+        let (sin, cos) = angle.sin_cos();
+        Transform::new(cos, sin, -sin, cos, 0.0, 0.0)
+    }
+
+    /// Combines two transformations into one which applies `self` first, and
+    /// then `next`.
+    /// # Arguments
+    /// * `next` - The transformation to apply after this one.
+    /// # Returns
+    /// The combined `Transform`.
+    /// # Example
+    /// ```rust
+    /// let transform = Transform::scaling(2.0, 2.0).then(Transform::translation(5.0, 0.0));
+    /// assert_eq!(transform.apply(1.0, 1.0), (7.0, 2.0));
+    /// ```
+    #[must_use]
+    pub fn then(self, next: Transform) -> Transform {
+        Transform {
+            a: next.a.mul_add(self.a, next.c * self.b),
+            b: next.b.mul_add(self.a, next.d * self.b),
+            c: next.a.mul_add(self.c, next.c * self.d),
+            d: next.b.mul_add(self.c, next.d * self.d),
+            e: next.a.mul_add(self.e, next.c.mul_add(self.f, next.e)),
+            f: next.b.mul_add(self.e, next.d.mul_add(self.f, next.f)),
+        }
+    }
+
+    /// Transforms the point (`x`, `y`).
+    /// # Returns
+    /// The transformed point as an (x, y) tuple.
+    /// # Example
+    /// ```rust
+    /// let (x, y) = Transform::translation(3.0, 4.0).apply(1.0, 1.0); // (4.0, 5.0)
+    /// ```
+    #[must_use]
+    pub const fn apply(self, x: f64, y: f64) -> (f64, f64) {
+        (self.a.mul_add(x, self.c.mul_add(y, self.e)), self.b.mul_add(x, self.d.mul_add(y, self.f)))
+    }
+
+    /// Creates the transformation which undoes this one, if there is one.
+    /// # Returns
+    /// The inverse `Transform`, or `None` if this transformation squashes
+    /// everything onto a line or point (for example, a scale of zero).
+    /// # Example
+    /// ```rust
+    /// let inverse = transform.inverse().expect("transform cannot be undone");
+    /// ```
+    #[must_use]
+    pub fn inverse(self) -> Option<Transform> {
+        let determinant = self.a.mul_add(self.d, -(self.b * self.c));
+        if determinant.abs() < f64::EPSILON || !determinant.is_finite() {
+            return None;
+        }
+        Some(Transform {
+            a: self.d / determinant,
+            b: -self.b / determinant,
+            c: -self.c / determinant,
+            d: self.a / determinant,
+            e: self.c.mul_add(self.f, -(self.d * self.e)) / determinant,
+            f: self.b.mul_add(self.e, -(self.a * self.f)) / determinant,
+        })
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Transform {
+        Transform::IDENTITY
+    }
+}
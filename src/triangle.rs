@@ -0,0 +1,227 @@
+//! `TriangleNode` struct - represents a node for a triangle with a color, or
+//! part of a texture, at each corner. Triangles are the building block of
+//! meshes, as any shape can be split into them.
+
+use core::{cmp::Ordering, ops::Range};
+
+use crate::{BlendMode, Color, Texture, component::{BlendModeComponent, DrawComponent, FillColorComponent}, texture::{Filter, interpolate}};
+
+/// A node representing a triangle, colored by blending smoothly between the
+/// colors of its three corners (Gouraud shading), and optionally textured by
+/// mapping each corner to a point on a source texture.
+/// Coordinates are the corners between pixels, as with `RectangleNode`, and
+/// a pixel is drawn if its center is inside the triangle. Pixels exactly on
+/// an edge follow the top-left rule: they are only drawn if the edge is the
+/// top or a left side of the triangle, so triangles sharing an edge, as in a
+/// mesh, never draw the same pixel twice or leave gaps between them.
+/// ## Implemented components:
+/// - `DrawComponent`
+/// - `FillColorComponent`
+/// - `BlendModeComponent`
+/// # Example
+/// ```rust
+/// let mut gradient = TriangleNode::new([(40.0, 5.0), (75.0, 55.0), (5.0, 55.0)], Color::WHITE);
+/// gradient.colors = [Color::RED, Color::GREEN, Color::BLUE];
+/// texture.add(&gradient);
+///
+/// // the top-left half of a sprite, with its corners pulled out of shape
+/// let brick = Texture::load_qoi("assets/brick.qoi").unwrap();
+/// let half = TriangleNode::textured(&brick, [(10.0, 10.0), (50.0, 0.0), (0.0, 40.0)], [(0.0, 0.0), (16.0, 0.0), (0.0, 16.0)]);
+/// texture.add(&half);
+/// ```
+#[non_exhaustive]
+#[expect(clippy::module_name_repetitions, reason = "struct should be called 'TriangleNode' as it is standard.")]
+pub struct TriangleNode<'texture> {
+    /// The (x, y) coordinates of the three corners of the triangle, in any
+    /// order.
+    pub vertices: [(f64, f64); 3],
+
+    /// The color of each corner, in the same order as `vertices`. Each pixel
+    /// is a blend of the three, weighted by how close it is to each corner.
+    /// If the triangle is textured, these tint the texture instead.
+    /// `set_fill_color` sets all three, and `get_fill_color` gets the first.
+    pub colors: [Color; 3],
+
+    /// The texture that the triangle's pixels are taken from, or `None` to
+    /// only use `colors`.
+    pub texture: Option<&'texture Texture>,
+
+    /// The point on `texture` which each corner is mapped to, in pixels, in
+    /// the same order as `vertices`. Pixels mapped to points outside of the
+    /// texture are not drawn.
+    pub uvs: [(f64, f64); 3],
+
+    /// How `texture` is sampled. Defaults to `Filter::Nearest`.
+    pub filter: Filter,
+
+    /// How the triangle's pixels are combined with the existing pixels when
+    /// drawn. Defaults to `BlendMode::SourceOver`.
+    pub blend_mode: BlendMode,
+}
+
+impl<'texture> TriangleNode<'texture> {
+    /// Create a new `TriangleNode` in a single color.
+    /// # Parameters
+    /// - `vertices`: The (x, y) coordinates of the three corners.
+    /// - `color`: The color of every corner.
+    /// # Returns
+    /// A new, untextured `TriangleNode`. Its `colors` can be changed
+    /// afterwards to give it a gradient.
+    #[must_use]
+    pub const fn new(vertices: [(f64, f64); 3], color: Color) -> TriangleNode<'texture> {
+        TriangleNode {
+            vertices,
+            colors: [color; 3],
+            texture: None,
+            uvs: [(0.0, 0.0); 3],
+            filter: Filter::Nearest,
+            blend_mode: BlendMode::SourceOver,
+        }
+    }
+
+    /// Create a new `TriangleNode` showing part of a texture.
+    /// # Parameters
+    /// - `texture`: The texture to take the triangle's pixels from.
+    /// - `vertices`: The (x, y) coordinates of the three corners.
+    /// - `uvs`: The point on `texture` which each corner is mapped to, in
+    ///   pixels.
+    /// # Returns
+    /// A new `TriangleNode` with its texture untinted.
+    #[must_use]
+    pub const fn textured(texture: &'texture Texture, vertices: [(f64, f64); 3], uvs: [(f64, f64); 3]) -> TriangleNode<'texture> {
+        TriangleNode {
+            texture: Some(texture),
+            uvs,
+            ..TriangleNode::new(vertices, Color::WHITE)
+        }
+    }
+}
+
+impl DrawComponent for TriangleNode<'_> {
+    fn draw(&self, texture: &mut Texture) {
+        let [first_position, second_position, third_position] = self.vertices;
+        let [first_color, second_color, third_color] = self.colors;
+        let [first_uv, second_uv, third_uv] = self.uvs;
+        let first = Corner { position: first_position, color: first_color, uv: first_uv };
+        let mut second = Corner { position: second_position, color: second_color, uv: second_uv };
+        let mut third = Corner { position: third_position, color: third_color, uv: third_uv };
+        // the corners are put in clockwise order, so that the inside of the
+        // triangle is on the same side of every edge
+        let mut area = edge_value(first.position, second.position, third.position);
+        if area < 0.0f64 {
+            (second, third) = (third, second);
+            area = -area;
+        }
+        if area.is_nan() || area <= 0.0f64 {
+            return;
+        }
+        let edges = [(second, third), (third, first), (first, second)];
+        let top = first.position.1.min(second.position.1).min(third.position.1);
+        let bottom = first.position.1.max(second.position.1).max(third.position.1);
+        let uniform = self.texture.is_none() && first.color == second.color && second.color == third.color;
+        for y in pixel_centers(top, bottom, texture.get_height()) {
+            let center_y = cast::f64(y) + 0.5f64;
+            // the pixels whose centers are inside the triangle are a single
+            // run, found from where each edge crosses the row and then
+            // checked exactly
+            let (mut left, mut right) = (f64::NEG_INFINITY, f64::INFINITY);
+            for (from, to) in edges {
+                let (dx, dy) = (to.position.0 - from.position.0, to.position.1 - from.position.1);
+                let crossing = dx.mul_add((center_y - from.position.1) / dy, from.position.0);
+                match dy.partial_cmp(&0.0f64) {
+                    Some(Ordering::Greater) => right = right.min(crossing),
+                    Some(Ordering::Less) => left = left.max(crossing),
+                    _ => {},
+                }
+            }
+            let inside = |x: usize| {
+                let point = (cast::f64(x) + 0.5f64, center_y);
+                edges.iter().all(|&(from, to)| covers(from.position, to.position, point))
+            };
+            let columns = pixel_centers(left - 1.0, right + 1.0, texture.get_width());
+            let Some(start) = columns.clone().find(|&x| inside(x)) else {
+                continue;
+            };
+            let end = columns.rev().find(|&x| inside(x)).map_or(start, |x| x.saturating_add(1));
+            if uniform {
+                texture.fill_span(start, y, end.saturating_sub(start), first.color, self.blend_mode);
+                continue;
+            }
+            for x in start..end {
+                // each corner's weight is the area of the triangle made by the
+                // pixel and the opposite edge
+                let point = (cast::f64(x) + 0.5f64, center_y);
+                let weights = edges.map(|(from, to)| edge_value(from.position, to.position, point) / area);
+                let [first_weight, second_weight, third_weight] = weights;
+                let color = interpolate(&[(first.color, first_weight), (second.color, second_weight), (third.color, third_weight)]);
+                let Some(source) = self.texture else {
+                    texture.draw_pixel(x, y, color, self.blend_mode).unwrap_or(());
+                    continue;
+                };
+                let u = first.uv.0.mul_add(first_weight, second.uv.0.mul_add(second_weight, third.uv.0 * third_weight));
+                let v = first.uv.1.mul_add(first_weight, second.uv.1.mul_add(second_weight, third.uv.1 * third_weight));
+                if let Some(sample) = source.sample(u, v, self.filter) {
+                    texture.draw_pixel(x, y, sample.tinted(color), self.blend_mode).unwrap_or(());
+                }
+            }
+        }
+    }
+}
+
+/// One corner of a triangle, with everything interpolated across it.
+#[derive(Copy, Clone)]
+struct Corner {
+    /// The (x, y) coordinates of the corner.
+    position: (f64, f64),
+    /// The color of the corner.
+    color: Color,
+    /// The point on the source texture the corner is mapped to.
+    uv: (f64, f64),
+}
+
+/// Gets twice the area of the triangle made by an edge and a point, which is
+/// positive if the point is clockwise from the edge, and negative if not.
+fn edge_value(from: (f64, f64), to: (f64, f64), point: (f64, f64)) -> f64 {
+    (to.0 - from.0).mul_add(point.1 - from.1, -((to.1 - from.1) * (point.0 - from.0)))
+}
+
+/// Checks whether a point is on the inside of an edge of a clockwise
+/// triangle. Points exactly on the edge are only inside if it is a top edge,
+/// which is flat and goes right, or a left edge, which goes up.
+#[expect(clippy::single_call_fn, reason = "kept separate from TriangleNode::draw for readability")]
+fn covers(from: (f64, f64), to: (f64, f64), point: (f64, f64)) -> bool {
+    let value = edge_value(from, to, point);
+    let top_left = match to.1.partial_cmp(&from.1) {
+        Some(Ordering::Less) => true,
+        Some(Ordering::Equal) => to.0 > from.0,
+        _ => false,
+    };
+    value > 0.0 || (top_left && value >= 0.0)
+}
+
+/// Gets the pixels on a texture `size` pixels wide or high whose centers are
+/// between `start` and `end`.
+fn pixel_centers(start: f64, end: f64, size: usize) -> Range<usize> {
+    let limit = cast::f64(size);
+    cast::usize((start - 0.5).ceil().clamp(0.0, limit)).unwrap_or(0)..cast::usize(((end - 0.5).floor() + 1.0).clamp(0.0, limit)).unwrap_or(0)
+}
+
+impl FillColorComponent for TriangleNode<'_> {
+    fn get_fill_color(&self) -> &Color {
+        &self.colors[0]
+    }
+
+    fn set_fill_color(&mut self, color: Color) {
+        self.colors = [color; 3];
+    }
+}
+
+impl BlendModeComponent for TriangleNode<'_> {
+    fn get_blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+}
@@ -0,0 +1,728 @@
+//! Loading of TrueType (`.ttf`) and OpenType (`.otf`) fonts, which describe
+//! their characters as outlines, so text can be drawn smoothly at any size.
+//!
+//! Glyphs are rasterized with anti-aliasing the first time they are drawn at
+//! each size, and cached so that drawing the same text again is fast:
+//! ```rust
+//! let font = TrueTypeFont::load("assets/fonts/DejaVuSans.ttf").unwrap();
+//! let body = font.at_size(16.0);
+//! let heading = font.at_size(32.0);
+//! texture.add(&TextNode::with_font(&heading, "Settings", 8.0, 8.0, Color::WHITE));
+//! texture.add(&TextNode::with_font(&body, "Volume: 80%", 8.0, 48.0, Color::WHITE));
+//! ```
+
+use alloc::rc::Rc;
+use core::{cell::RefCell, iter};
+use std::{collections::HashMap, fs, path::Path};
+
+use anyhow::{Context as _, Error};
+
+use crate::{BlendMode, Color, Texture, cff::CffOutlines, font::Font, polygon::FillRule, raster::{self, Point, Rasterizer, Segment}, reader::ByteReader, rect::Rect, texture::Filter, transform::Transform};
+
+/// The context used in error messages while decoding a font.
+const DECODE_CONTEXT: &str = "TrueTypeFont::decode";
+
+/// The most ems wide or tall a rasterized glyph may be. Outlines reaching
+/// further than this are broken, and are not drawn.
+const MAX_GLYPH_EMS: f64 = 4.0;
+
+/// The deepest composite glyphs may be nested inside each other.
+const MAX_COMPONENT_DEPTH: usize = 8;
+
+/// The most components a glyph may be made of in total, counting those of
+/// nested composite glyphs, so that a glyph which uses the same components
+/// many times over cannot take forever to read.
+const MAX_COMPONENTS: usize = 1024;
+
+/// The most characters a character map may map, which is every Unicode code
+/// point. Ranges are read until this many have been mapped, even if they
+/// overlap, so that a small table cannot take forever to read.
+const MAX_MAPPED_CHARACTERS: u32 = 0x11_0000;
+
+/// A scalable font loaded from a TrueType or OpenType file.
+/// Both TrueType outlines (`glyf`) and CFF outlines (`CFF `) are supported,
+/// along with kerning from the `kern` table. Characters missing from the font
+/// are drawn with its "missing glyph", usually an empty box.
+///
+/// A `TrueTypeFont` has no size of its own: use `at_size` to get a `Font`
+/// which draws it at a specific size. Every size shares the same glyph cache.
+#[non_exhaustive]
+pub struct TrueTypeFont {
+    /// The contents of the font file.
+    data: Vec<u8>,
+    /// The number of font units in the font's em square, which is scaled to
+    /// the size the font is drawn at.
+    units_per_em: f64,
+    /// How far above the baseline the tallest characters reach, in font
+    /// units.
+    ascender: f64,
+    /// How far below the baseline the lowest characters reach, in font units.
+    /// This is usually negative.
+    descender: f64,
+    /// The extra space between lines, in font units.
+    line_gap: f64,
+    /// The advance width of every glyph, in font units.
+    advances: Vec<u16>,
+    /// The glyph drawn for each character.
+    characters: HashMap<char, u16>,
+    /// The kerning adjustment between pairs of glyphs, in font units.
+    kernings: HashMap<(u16, u16), i16>,
+    /// The (start, end) of the table holding the outlines of every glyph:
+    /// `glyf` for TrueType outlines, or `CFF ` for CFF outlines.
+    outline_table: (usize, usize),
+    /// Where each glyph starts in the `glyf` table, plus where the last glyph
+    /// ends. Empty for fonts with CFF outlines.
+    locations: Vec<usize>,
+    /// The parsed `CFF ` table, for fonts with CFF outlines.
+    cff: Option<CffOutlines>,
+    /// The glyphs rasterized so far, by glyph and the bits of their size.
+    cache: RefCell<HashMap<(u16, u64), Rc<RasterGlyph>>>,
+}
+
+/// A glyph rasterized at a specific size, as a white texture whose alpha
+/// channel is how much of each pixel the glyph covers.
+/// # Example
+/// ```rust
+/// let glyph = font.get_glyph('A', 48.0);
+/// let region = Rect::new(0, 0, glyph.texture.get_width(), glyph.texture.get_height());
+/// let transform = Transform::translation(pen_x + glyph.offset_x, baseline_y + glyph.offset_y);
+/// texture.draw_texture(&glyph.texture, region, transform, Filter::Nearest, Color::RED, BlendMode::SourceOver);
+/// ```
+#[non_exhaustive]
+pub struct RasterGlyph {
+    /// The rasterized glyph. It is empty for characters with no outline, such
+    /// as spaces.
+    pub texture: Texture,
+    /// The distance from the pen position to the left edge of `texture`, in
+    /// whole pixels.
+    pub offset_x: f64,
+    /// The distance from the baseline to the top edge of `texture`, in whole
+    /// pixels. This is negative for glyphs which reach above the baseline.
+    pub offset_y: f64,
+}
+
+impl TrueTypeFont {
+    /// Loads a TrueType (`.ttf`) or OpenType (`.otf`) font file. For font
+    /// collections (`.ttc`), the first font is loaded.
+    /// # Arguments
+    /// * `path` - The path of the font file to load.
+    /// # Returns
+    /// A `TrueTypeFont`, which can be drawn at any size with `at_size`.
+    /// # Errors
+    /// Errors if the file cannot be read or is not a supported font.
+    /// # Example
+    /// ```rust
+    /// let font = TrueTypeFont::load("assets/fonts/DejaVuSans.ttf")
+    ///     .expect("failed to load font");
+    /// ```
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<TrueTypeFont, Error> {
+        TrueTypeFont::decode(&fs::read(path).context("Pixl: TrueTypeFont::load: failed to read file")?)
+    }
+
+    /// Decodes an in-memory TrueType or OpenType font. For font collections,
+    /// the first font is decoded.
+    /// # Arguments
+    /// * `bytes` - The contents of a `.ttf`, `.otf` or `.ttc` file.
+    /// # Returns
+    /// A `TrueTypeFont`, which can be drawn at any size with `at_size`.
+    /// # Errors
+    /// Errors if `bytes` is not a supported font.
+    /// # Example
+    /// ```rust
+    /// let font = TrueTypeFont::decode(include_bytes!("ui.ttf"))
+    ///     .expect("invalid font");
+    /// ```
+    pub fn decode(bytes: &[u8]) -> Result<TrueTypeFont, Error> {
+        let tables = read_table_directory(bytes)?;
+        let table = |tag: &[u8; 4]| {
+            let &(start, end) = tables.get(tag)?;
+            bytes.get(start..end)
+        };
+        let required = |tag: &[u8; 4]| table(tag).ok_or_else(|| Error::msg(format!(
+            "Pixl: TrueTypeFont::decode: font has no '{}' table", String::from_utf8_lossy(tag),
+        )));
+
+        let mut head = ByteReader::new(required(b"head")?, DECODE_CONTEXT);
+        head.seek(18)?;
+        let units_per_em = head.u16_be()?;
+        if !(16..=0x4000).contains(&units_per_em) {
+            return Err(Error::msg("Pixl: TrueTypeFont::decode: font has an invalid number of units per em"));
+        }
+        head.seek(50)?;
+        let long_locations = head.i16_be()? != 0;
+
+        let mut maxp = ByteReader::new(required(b"maxp")?, DECODE_CONTEXT);
+        maxp.seek(4)?;
+        let glyph_count = usize::from(maxp.u16_be()?);
+
+        let mut hhea = ByteReader::new(required(b"hhea")?, DECODE_CONTEXT);
+        hhea.seek(4)?;
+        let ascender = f64::from(hhea.i16_be()?);
+        let descender = f64::from(hhea.i16_be()?);
+        let line_gap = f64::from(hhea.i16_be()?);
+        hhea.seek(34)?;
+        let metric_count = usize::from(hhea.u16_be()?);
+
+        let (outline_table, locations, cff) = if let (Some(&glyf), Some(loca)) = (tables.get(b"glyf"), table(b"loca")) {
+            (glyf, read_locations(loca, glyph_count, long_locations)?, None)
+        } else if let Some(&cff_table) = tables.get(b"CFF ") {
+            (cff_table, Vec::new(), Some(CffOutlines::parse(required(b"CFF ")?)?))
+        } else {
+            return Err(Error::msg("Pixl: TrueTypeFont::decode: font has no supported outlines"));
+        };
+
+        Ok(TrueTypeFont {
+            data: bytes.to_vec(),
+            units_per_em: f64::from(units_per_em),
+            ascender, descender, line_gap,
+            advances: read_advances(required(b"hmtx")?, metric_count, glyph_count)?,
+            characters: read_character_map(required(b"cmap")?, glyph_count)?,
+            kernings: table(b"kern").map(read_kernings).transpose()?.unwrap_or_default(),
+            outline_table, locations, cff,
+            cache: RefCell::new(HashMap::new()),
+        })
+    }
+
+    /// Gets a `Font` which draws this font at a specific size, for use with
+    /// `TextNode`. This is cheap, so it can be called whenever needed.
+    /// # Arguments
+    /// * `size` - The size of the font's em square in pixels. This is roughly
+    ///   the distance from the top of the tallest characters to the bottom of
+    ///   the lowest.
+    /// # Returns
+    /// A `ScaledFont` which borrows this font.
+    /// # Example
+    /// ```rust
+    /// let label_font = font.at_size(14.0);
+    /// let label = TextNode::with_font(&label_font, "Name:", 4.0, 4.0, Color::WHITE);
+    /// ```
+    #[must_use]
+    pub const fn at_size(&self, size: f64) -> ScaledFont<'_> {
+        ScaledFont { font: self, size }
+    }
+
+    /// Checks whether the font has a glyph for a character.
+    /// # Arguments
+    /// * `character` - The character to look for.
+    /// # Returns
+    /// `true` if the character is drawn with its own glyph, or `false` if it
+    /// is drawn with the missing glyph.
+    #[must_use]
+    pub fn has_char(&self, character: char) -> bool {
+        self.characters.contains_key(&character)
+    }
+
+    /// Gets a character rasterized at a specific size. Glyphs are cached, so
+    /// rasterizing the same character at the same size again is fast.
+    /// # Arguments
+    /// * `character` - The character to rasterize.
+    /// * `size` - The size of the font's em square in pixels.
+    /// # Returns
+    /// The rasterized glyph, and where to draw it relative to the pen
+    /// position on the baseline.
+    /// # Example
+    /// ```rust
+    /// let glyph = font.get_glyph('@', 64.0);
+    /// glyph.texture.save_bmp("at_sign.bmp").unwrap();
+    /// ```
+    #[must_use]
+    pub fn get_glyph(&self, character: char, size: f64) -> Rc<RasterGlyph> {
+        let glyph = self.glyph_index(character);
+        let key = (glyph, size.to_bits());
+        if let Some(cached) = self.cache.borrow().get(&key) {
+            return Rc::clone(cached);
+        }
+        let rasterized = Rc::new(self.rasterize(glyph, size));
+        self.cache.borrow_mut().insert(key, Rc::clone(&rasterized));
+        rasterized
+    }
+
+    /// Empties the glyph cache, freeing the memory used by every glyph
+    /// rasterized so far. Glyphs are rasterized again when next drawn.
+    /// This is only needed when drawing text at many different sizes, such as
+    /// while animating its size.
+    pub fn clear_cache(&self) {
+        self.cache.borrow_mut().clear();
+    }
+
+    /// Gets the glyph drawn for a character, which is the missing glyph (0)
+    /// if the font does not contain it.
+    fn glyph_index(&self, character: char) -> u16 {
+        self.characters.get(&character).copied().unwrap_or(0)
+    }
+
+    /// Rasterizes a glyph at a specific size.
+    fn rasterize(&self, glyph: u16, size: f64) -> RasterGlyph {
+        let scale = size / self.units_per_em;
+        // a broken outline is drawn as nothing, rather than stopping the rest
+        // of the text from being drawn
+        let segments: Vec<Segment> = self.outline(glyph).unwrap_or_default().into_iter()
+            .map(|segment| segment.transformed(Transform::scaling(scale, -scale)))
+            .collect();
+        let empty = RasterGlyph { texture: Texture::new(0, 0), offset_x: 0.0, offset_y: 0.0 };
+        let Some(((left, top), (right, bottom))) = raster::bounds(&segments) else {
+            return empty;
+        };
+        let (pixel_left, pixel_top) = (left.floor(), top.floor());
+        let max_size = size.abs().mul_add(MAX_GLYPH_EMS, 2.0f64);
+        if right - pixel_left > max_size || bottom - pixel_top > max_size {
+            return empty;
+        }
+        let (Ok(width), Ok(height)) = (cast::usize(right.ceil() - pixel_left), cast::usize(bottom.ceil() - pixel_top)) else {
+            return empty;
+        };
+        let mut rasterizer = Rasterizer::new(width, height);
+        for segment in segments {
+            rasterizer.add(segment.transformed(Transform::translation(-pixel_left, -pixel_top)));
+        }
+        let pixels = rasterizer.coverage(FillRule::NonZero).into_iter()
+            .map(|coverage| Color::rgba(255, 255, 255, cast::u8((coverage * 255.0).round()).unwrap_or(u8::MAX)))
+            .collect();
+        Texture::from_pixels(width, height, pixels).map_or(empty, |texture| {
+            RasterGlyph { texture, offset_x: pixel_left, offset_y: pixel_top }
+        })
+    }
+
+    /// Gets the outline of a glyph in font units, with y pointing up.
+    fn outline(&self, glyph: u16) -> Result<Vec<Segment>, Error> {
+        let table = self.data.get(self.outline_table.0..self.outline_table.1).unwrap_or_default();
+        self.cff.as_ref().map_or_else(|| {
+            let mut segments = Vec::new();
+            let mut components_left = MAX_COMPONENTS;
+            read_glyph(table, &self.locations, glyph, Transform::IDENTITY, 0, &mut components_left, &mut segments)?;
+            Ok(segments)
+        }, |cff| cff.outline(table, glyph))
+    }
+}
+
+/// A `TrueTypeFont` drawn at a specific size, made with
+/// `TrueTypeFont::at_size`.
+/// Glyphs are snapped to whole pixels, and drawn in the text's color with
+/// their edges anti-aliased.
+#[derive(Copy, Clone)]
+#[non_exhaustive]
+pub struct ScaledFont<'font> {
+    /// The font being drawn.
+    font: &'font TrueTypeFont,
+    /// The size of the font's em square in pixels.
+    size: f64,
+}
+
+impl ScaledFont<'_> {
+    /// Gets the size the font is drawn at.
+    /// # Returns
+    /// The size of the font's em square in pixels.
+    #[must_use]
+    pub const fn get_size(&self) -> f64 {
+        self.size
+    }
+
+    /// Gets how far above the baseline the tallest characters reach. This is
+    /// also the distance from the top of a line to its baseline.
+    /// # Returns
+    /// The ascent in pixels.
+    #[must_use]
+    pub fn get_ascent(&self) -> f64 {
+        self.font.ascender * self.scale()
+    }
+
+    /// Gets how far below the baseline the lowest characters reach.
+    /// # Returns
+    /// The descent in pixels, as a positive number.
+    #[must_use]
+    pub fn get_descent(&self) -> f64 {
+        -self.font.descender * self.scale()
+    }
+
+    /// Gets the number of pixels per font unit.
+    fn scale(&self) -> f64 {
+        self.size / self.font.units_per_em
+    }
+}
+
+impl Font for ScaledFont<'_> {
+    fn get_line_height(&self) -> f64 {
+        (self.font.ascender - self.font.descender + self.font.line_gap) * self.scale()
+    }
+
+    fn get_advance(&self, character: char) -> f64 {
+        let advances = &self.font.advances;
+        let advance = advances.get(usize::from(self.font.glyph_index(character))).copied().unwrap_or_default();
+        f64::from(advance) * self.scale()
+    }
+
+    fn get_kerning(&self, left: char, right: char) -> f64 {
+        let pair = (self.font.glyph_index(left), self.font.glyph_index(right));
+        self.font.kernings.get(&pair).map_or(0.0, |&kerning| f64::from(kerning) * self.scale())
+    }
+
+    fn draw_char(&self, texture: &mut Texture, character: char, x: f64, y: f64, color: Color, blend_mode: BlendMode) {
+        let glyph = self.font.get_glyph(character, self.size);
+        let region = Rect::new(0, 0, glyph.texture.get_width(), glyph.texture.get_height());
+        let baseline = (y + self.get_ascent()).round();
+        let transform = Transform::translation(x.round() + glyph.offset_x, baseline + glyph.offset_y);
+        texture.draw_texture(&glyph.texture, region, transform, Filter::Nearest, color, blend_mode);
+    }
+}
+
+/// Reads the table directory at the start of a font, giving the (start, end)
+/// of every table.
+#[expect(clippy::single_call_fn, reason = "kept separate from TrueTypeFont::decode for readability")]
+fn read_table_directory(bytes: &[u8]) -> Result<HashMap<[u8; 4], (usize, usize)>, Error> {
+    let mut reader = ByteReader::new(bytes, DECODE_CONTEXT);
+    let mut version = reader.array::<4>()?;
+    if &version == b"ttcf" {
+        reader.skip(8)?; // version and font count
+        let first_font = cast::usize(reader.u32_be()?);
+        reader.seek(first_font)?;
+        version = reader.array()?;
+    }
+    if !matches!(&version, [0, 1, 0, 0] | b"true" | b"OTTO") {
+        return Err(Error::msg("Pixl: TrueTypeFont::decode: not a TrueType or OpenType font"));
+    }
+    let table_count = reader.u16_be()?;
+    reader.skip(6)?; // search hints
+    let mut tables = HashMap::new();
+    for _ in 0..table_count {
+        let tag = reader.array()?;
+        reader.skip(4)?; // checksum
+        let start = cast::usize(reader.u32_be()?);
+        let end = start.saturating_add(cast::usize(reader.u32_be()?));
+        if end > bytes.len() {
+            return Err(Error::msg("Pixl: TrueTypeFont::decode: font table is out of bounds"));
+        }
+        tables.insert(tag, (start, end));
+    }
+    Ok(tables)
+}
+
+/// Reads where each glyph starts in the `glyf` table, from the `loca` table.
+#[expect(clippy::single_call_fn, reason = "kept separate from TrueTypeFont::decode for readability")]
+fn read_locations(loca: &[u8], glyph_count: usize, long_locations: bool) -> Result<Vec<usize>, Error> {
+    let mut reader = ByteReader::new(loca, DECODE_CONTEXT);
+    iter::repeat_with(|| if long_locations {
+        Ok(cast::usize(reader.u32_be()?))
+    } else {
+        // short locations are stored halved
+        Ok(usize::from(reader.u16_be()?).saturating_mul(2))
+    }).take(glyph_count.saturating_add(1)).collect()
+}
+
+/// Reads the advance width of every glyph from the `hmtx` table. Glyphs after
+/// the last metric share its advance, as is common in monospaced fonts.
+#[expect(clippy::single_call_fn, reason = "kept separate from TrueTypeFont::decode for readability")]
+fn read_advances(hmtx: &[u8], metric_count: usize, glyph_count: usize) -> Result<Vec<u16>, Error> {
+    let mut reader = ByteReader::new(hmtx, DECODE_CONTEXT);
+    let mut advances = Vec::with_capacity(glyph_count);
+    for _ in 0..metric_count.min(glyph_count) {
+        advances.push(reader.u16_be()?);
+        reader.skip(2)?; // left side bearing
+    }
+    let last = advances.last().copied().unwrap_or_default();
+    advances.resize(glyph_count, last);
+    Ok(advances)
+}
+
+/// Reads which glyph is drawn for each character from the `cmap` table,
+/// using its Unicode subtable in format 4 or 12.
+#[expect(clippy::single_call_fn, reason = "kept separate from TrueTypeFont::decode for readability")]
+fn read_character_map(cmap: &[u8], glyph_count: usize) -> Result<HashMap<char, u16>, Error> {
+    let mut reader = ByteReader::new(cmap, DECODE_CONTEXT);
+    reader.skip(2)?; // version
+    let subtable_count = reader.u16_be()?;
+    let mut best: Option<(u16, usize)> = None;
+    for _ in 0..subtable_count {
+        let platform = reader.u16_be()?;
+        let encoding = reader.u16_be()?;
+        let offset = cast::usize(reader.u32_be()?);
+        let is_unicode = platform == 0 || (platform == 3 && matches!(encoding, 1 | 10));
+        let mut subtable = ByteReader::new(cmap, DECODE_CONTEXT);
+        subtable.seek(offset)?;
+        let format = subtable.u16_be()?;
+        // format 12 covers every character, while format 4 only covers the
+        // Basic Multilingual Plane
+        if is_unicode && matches!(format, 4 | 12) && best.is_none_or(|(best_format, _)| format > best_format) {
+            best = Some((format, offset));
+        }
+    }
+    let Some((format, offset)) = best else {
+        return Err(Error::msg("Pixl: TrueTypeFont::decode: font has no supported Unicode character map"));
+    };
+    reader.seek(offset)?;
+    let mut characters = HashMap::new();
+    let mut characters_left = MAX_MAPPED_CHARACTERS;
+    // returns whether any more characters can be mapped
+    let mut map = |code_point: u32, raw_glyph: u32| {
+        if let (Some(character), Ok(glyph)) = (char::from_u32(code_point), u16::try_from(raw_glyph))
+            && glyph != 0 && usize::from(glyph) < glyph_count {
+            characters.entry(character).or_insert(glyph);
+        }
+        characters_left = characters_left.saturating_sub(1);
+        characters_left > 0
+    };
+    if format == 4 {
+        read_character_map_4(&mut reader, &mut map)?;
+    } else {
+        reader.skip(12)?; // format, reserved, length and language
+        for _ in 0..reader.u32_be()? {
+            let first = reader.u32_be()?;
+            let last = reader.u32_be()?;
+            let first_glyph = reader.u32_be()?;
+            // groups running past the last glyph are broken, and skipped
+            let last_glyph = last.checked_sub(first).and_then(|length| first_glyph.checked_add(length));
+            if last_glyph.is_none_or(|glyph| cast::usize(glyph) >= glyph_count) {
+                continue;
+            }
+            for code_point in first..=last {
+                if !map(code_point, first_glyph.saturating_add(code_point.saturating_sub(first))) {
+                    return Ok(characters);
+                }
+            }
+        }
+    }
+    Ok(characters)
+}
+
+/// Reads a format 4 character map subtable, which maps ranges of characters
+/// to glyphs either by adding a delta or through an array of glyphs.
+#[expect(clippy::single_call_fn, reason = "kept separate from read_character_map for readability")]
+fn read_character_map_4<F: FnMut(u32, u32) -> bool>(reader: &mut ByteReader, map: &mut F) -> Result<(), Error> {
+    reader.skip(6)?; // format, length and language
+    let segment_count = usize::from(reader.u16_be()? / 2);
+    reader.skip(6)?; // search hints
+    let arrays_start = reader.position();
+    let array = |index: usize, segment: usize| {
+        // end codes, padding, start codes, deltas, then range offsets
+        let array_offset = segment_count.saturating_mul(2).saturating_mul(index).saturating_add(if index == 0 { 0 } else { 2 });
+        arrays_start.saturating_add(array_offset).saturating_add(segment.saturating_mul(2))
+    };
+    for segment in 0..segment_count {
+        reader.seek(array(0, segment))?;
+        let last = reader.u16_be()?;
+        reader.seek(array(1, segment))?;
+        let first = reader.u16_be()?;
+        reader.seek(array(2, segment))?;
+        let delta = reader.u16_be()?;
+        let range_offset_position = array(3, segment);
+        reader.seek(range_offset_position)?;
+        let range_offset = usize::from(reader.u16_be()?);
+        for code_point in first..=last {
+            if code_point == 0xFFFF {
+                break;
+            }
+            let glyph = if range_offset == 0 {
+                code_point.wrapping_add(delta)
+            } else {
+                // the range offset counts from its own position
+                let glyph_position = range_offset_position.saturating_add(range_offset)
+                    .saturating_add(usize::from(code_point.saturating_sub(first)).saturating_mul(2));
+                reader.seek(glyph_position)?;
+                match reader.u16_be()? {
+                    0 => 0,
+                    array_glyph => array_glyph.wrapping_add(delta),
+                }
+            };
+            if !map(u32::from(code_point), u32::from(glyph)) {
+                return Ok(());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Reads the kerning pairs of a `kern` table. Only horizontal kerning in
+/// format 0 subtables is supported.
+#[expect(clippy::single_call_fn, reason = "kept separate from TrueTypeFont::decode for readability")]
+fn read_kernings(kern: &[u8]) -> Result<HashMap<(u16, u16), i16>, Error> {
+    let mut reader = ByteReader::new(kern, DECODE_CONTEXT);
+    let mut kernings = HashMap::new();
+    // newer Apple kern tables have a 32-bit version, and are not supported
+    if reader.u16_be()? != 0 {
+        return Ok(kernings);
+    }
+    for _ in 0..reader.u16_be()? {
+        let start = reader.position();
+        reader.skip(2)?; // version
+        let length = usize::from(reader.u16_be()?);
+        let coverage = reader.u16_be()?;
+        // format 0, horizontal, and neither minimum values nor cross-stream
+        if coverage & 0xFF07 == 0x0001 {
+            let pair_count = reader.u16_be()?;
+            reader.skip(6)?; // search hints
+            for _ in 0..pair_count {
+                let pair = (reader.u16_be()?, reader.u16_be()?);
+                kernings.insert(pair, reader.i16_be()?);
+            }
+        }
+        reader.seek(start.saturating_add(length))?;
+    }
+    Ok(kernings)
+}
+
+/// Reads the outline of a glyph from the `glyf` table, moved by `transform`,
+/// into `segments`. Composite glyphs are made of other glyphs, which are read
+/// recursively, using up `components_left`.
+fn read_glyph(glyf: &[u8], locations: &[usize], glyph: u16, transform: Transform, depth: usize, components_left: &mut usize, segments: &mut Vec<Segment>) -> Result<(), Error> {
+    if depth > MAX_COMPONENT_DEPTH {
+        return Err(Error::msg("Pixl: TrueTypeFont::get_glyph: composite glyph is nested too deeply"));
+    }
+    let index = usize::from(glyph);
+    let (Some(&start), Some(&end)) = (locations.get(index), locations.get(index.saturating_add(1))) else {
+        return Err(Error::msg("Pixl: TrueTypeFont::get_glyph: glyph does not exist"));
+    };
+    if start >= end {
+        return Ok(()); // glyphs without outlines, such as spaces, are empty
+    }
+    let data = glyf.get(start..end)
+        .ok_or_else(|| Error::msg("Pixl: TrueTypeFont::get_glyph: glyph is out of bounds"))?;
+    let mut reader = ByteReader::new(data, "TrueTypeFont::get_glyph");
+    let contour_count = reader.i16_be()?;
+    reader.skip(8)?; // bounding box
+    match usize::try_from(contour_count) {
+        Ok(simple_contour_count) => {
+            for contour in read_simple_glyph(&mut reader, simple_contour_count)? {
+                add_contour(&contour, transform, segments);
+            }
+            Ok(())
+        },
+        Err(_) => read_composite_glyph(&mut reader, glyf, locations, transform, depth, components_left, segments),
+    }
+}
+
+/// Reads the points of a simple glyph, split into its contours. Each point is
+/// paired with whether it is on the outline, rather than a control point.
+#[expect(clippy::single_call_fn, reason = "kept separate from read_glyph for readability")]
+fn read_simple_glyph(reader: &mut ByteReader, contour_count: usize) -> Result<Vec<Vec<(Point, bool)>>, Error> {
+    let contour_ends = iter::repeat_with(|| Ok(usize::from(reader.u16_be()?)))
+        .take(contour_count)
+        .collect::<Result<Vec<usize>, Error>>()?;
+    let point_count = contour_ends.last().map_or(0, |&last| last.saturating_add(1));
+    let instruction_length = usize::from(reader.u16_be()?);
+    reader.skip(instruction_length)?;
+
+    let mut flags = Vec::with_capacity(point_count);
+    while flags.len() < point_count {
+        let flag = reader.u8()?;
+        let repeats = if flag & 0x08 == 0 { 0 } else { usize::from(reader.u8()?) };
+        flags.extend(iter::repeat_n(flag, repeats.saturating_add(1)));
+    }
+    flags.truncate(point_count);
+    let xs = read_coordinates(reader, &flags, 0x02, 0x10)?;
+    let ys = read_coordinates(reader, &flags, 0x04, 0x20)?;
+
+    let mut contours = Vec::with_capacity(contour_count);
+    let mut contour_start = 0;
+    for &contour_end in &contour_ends {
+        let end = contour_end.saturating_add(1).min(point_count);
+        contours.push((contour_start..end).filter_map(|point| {
+            Some(((*xs.get(point)?, *ys.get(point)?), flags.get(point)? & 0x01 != 0))
+        }).collect());
+        contour_start = end;
+    }
+    Ok(contours)
+}
+
+/// Reads the x or y coordinates of a simple glyph's points, which are stored
+/// as the difference from the previous point. The `short` flag bit means the
+/// difference is a single byte, and the `same` flag bit means it is positive
+/// for short differences, or 0 otherwise.
+fn read_coordinates(reader: &mut ByteReader, flags: &[u8], short: u8, same: u8) -> Result<Vec<f64>, Error> {
+    let mut value = 0.0f64;
+    flags.iter().map(|&flag| {
+        let difference = match (flag & short != 0, flag & same != 0) {
+            (true, true) => f64::from(reader.u8()?),
+            (true, false) => -f64::from(reader.u8()?),
+            (false, true) => 0.0f64,
+            (false, false) => f64::from(reader.i16_be()?),
+        };
+        value += difference;
+        Ok(value)
+    }).collect()
+}
+
+/// Reads a composite glyph: a list of other glyphs, each moved, scaled or
+/// rotated by its own transform.
+#[expect(clippy::single_call_fn, reason = "kept separate from read_glyph for readability")]
+fn read_composite_glyph(reader: &mut ByteReader, glyf: &[u8], locations: &[usize], transform: Transform, depth: usize, components_left: &mut usize, segments: &mut Vec<Segment>) -> Result<(), Error> {
+    loop {
+        let flags = reader.u16_be()?;
+        let component = reader.u16_be()?;
+        let (first, second) = if flags & 0x0001 == 0 {
+            (signed_byte(reader.u8()?), signed_byte(reader.u8()?))
+        } else {
+            (f64::from(reader.i16_be()?), f64::from(reader.i16_be()?))
+        };
+        // otherwise the arguments are points to line up, which is rare and
+        // not supported
+        let (offset_x, offset_y) = if flags & 0x0002 == 0 { (0.0, 0.0) } else { (first, second) };
+        let (xx, yx, xy, yy) = if flags & 0x0008 != 0 {
+            let scale = read_f2dot14(reader)?;
+            (scale, 0.0, 0.0, scale)
+        } else if flags & 0x0040 != 0 {
+            (read_f2dot14(reader)?, 0.0, 0.0, read_f2dot14(reader)?)
+        } else if flags & 0x0080 != 0 {
+            (read_f2dot14(reader)?, read_f2dot14(reader)?, read_f2dot14(reader)?, read_f2dot14(reader)?)
+        } else {
+            (1.0, 0.0, 0.0, 1.0)
+        };
+        let component_transform = Transform::new(xx, yx, xy, yy, offset_x, offset_y).then(transform);
+        *components_left = components_left.checked_sub(1)
+            .ok_or_else(|| Error::msg("Pixl: TrueTypeFont::get_glyph: composite glyph has too many components"))?;
+        read_glyph(glyf, locations, component, component_transform, depth.saturating_add(1), components_left, segments)?;
+        if flags & 0x0020 == 0 {
+            return Ok(());
+        }
+    }
+}
+
+/// Reads a signed fixed-point number with 14 fractional bits.
+fn read_f2dot14(reader: &mut ByteReader) -> Result<f64, Error> {
+    Ok(f64::from(reader.i16_be()?) / 16384.0)
+}
+
+/// Interprets a byte as a signed number.
+fn signed_byte(byte: u8) -> f64 {
+    let value = f64::from(byte);
+    if byte >= 0x80 { value - 256.0 } else { value }
+}
+
+/// Adds the segments of one TrueType contour, moved by `transform`. Two
+/// control points in a row have an implied point on the outline halfway
+/// between them.
+#[expect(clippy::single_call_fn, reason = "kept separate from read_glyph for readability")]
+fn add_contour(points: &[(Point, bool)], transform: Transform, segments: &mut Vec<Segment>) {
+    let (Some(&(first, first_on)), Some(&(last, last_on))) = (points.first(), points.last()) else {
+        return;
+    };
+    // the contour has to start on the outline
+    let (start, rest) = if first_on {
+        (first, points.get(1..).unwrap_or_default())
+    } else if last_on {
+        (last, points.get(..points.len().saturating_sub(1)).unwrap_or_default())
+    } else {
+        (midpoint(first, last), points)
+    };
+    let mut current = start;
+    let mut control = None;
+    for &(point, on_curve) in rest.iter().chain(iter::once(&(start, true))) {
+        if on_curve {
+            let segment = control.take().map_or(Segment::Line(current, point), |control_point| {
+                Segment::Quadratic(current, control_point, point)
+            });
+            segments.push(segment.transformed(transform));
+            current = point;
+        } else {
+            if let Some(control_point) = control {
+                let middle = midpoint(control_point, point);
+                segments.push(Segment::Quadratic(current, control_point, middle).transformed(transform));
+                current = middle;
+            }
+            control = Some(point);
+        }
+    }
+}
+
+/// Gets the point halfway between two points.
+const fn midpoint(first: Point, second: Point) -> Point {
+    (f64::midpoint(first.0, second.0), f64::midpoint(first.1, second.1))
+}